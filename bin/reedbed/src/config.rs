@@ -0,0 +1,696 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use anyhow::{anyhow, Context};
+use clap::{Args, ValueEnum};
+use rug::{float::Special, Float};
+use serde::Deserialize;
+use std::{
+    borrow::{Borrow, Cow},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use reedbed_lib::{
+    greens::{
+        self, CancellableError, FlatTopBeam, LargeBeam, Layer, MultiLayer, ThermalProperties,
+    },
+    quadrature::{
+        self, AdaptiveSimpson, GaussKronrod, QuadratureError, Romberg, TanhSinh, Tolerance, G7_K15,
+    },
+};
+
+/// Parameters describing a simulation
+///
+/// This is shared between the command line flags and the configuration file
+/// format, with flags taking precedence over fields set in a configuration
+/// file. Numeric values are kept as strings until the precision is known so
+/// that they may be parsed without any loss
+#[derive(Args, Deserialize, Clone, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationArgs {
+    /// Floating point precision (in bits) for MPFR floats
+    #[arg(long)]
+    pub precision: Option<u64>,
+
+    /// Tolerance passed to the quadrature method. Defaults to
+    /// `2^-(precision - 11)`, a few bits short of the precision, as a
+    /// tolerance below what the precision can resolve is never met
+    #[arg(long)]
+    pub epsilon: Option<String>,
+
+    /// The quadrature method used to integrate over time. Each of them
+    /// treats `epsilon` as a tolerance relative to the integral
+    #[arg(long, value_enum)]
+    pub quadrature: Option<QuadratureKind>,
+
+    #[command(flatten)]
+    pub thermal_properties: ThermalPropertiesArgs,
+
+    /// A layer of tissue, given as `d,z0,mu_a,e0`. May be repeated
+    #[arg(long = "layer", value_parser = parse_layer)]
+    pub layers: Vec<LayerArgs>,
+
+    /// Path to a file listing layers of tissue, one per line as
+    /// `d z0 mu_a e0`, or `-` to read them from stdin. Used in place of
+    /// `--layer`
+    #[arg(long = "layers", value_name = "PATH", conflicts_with = "layers")]
+    #[serde(skip)]
+    pub layers_from: Option<PathBuf>,
+
+    /// The beam profile to use
+    #[arg(long, value_enum)]
+    pub beam: Option<BeamKind>,
+
+    /// Radius of the beam, for beams which have one. Units: cm
+    #[arg(long)]
+    pub radius: Option<String>,
+
+    /// Depth of the point to observe. Units: cm
+    #[arg(long)]
+    pub z: Option<String>,
+
+    /// Radial distance of the point to observe. Units: cm
+    #[arg(long)]
+    pub r: Option<String>,
+
+    /// Time at which integration begins. Units: s
+    #[arg(long)]
+    pub start: Option<String>,
+
+    /// Time at which integration ends. Units: s
+    #[arg(long)]
+    pub end: Option<String>,
+
+    /// Duration of a pulse beginning at a time of 0, in place of a
+    /// continuous exposure. If `end` is omitted, it is chosen to be several
+    /// thermal relaxation times of the slowest layer after the pulse ends.
+    /// Units: s
+    #[arg(long)]
+    pub pulse_width: Option<String>,
+
+    /// Number of evenly spaced times in start..end to report the temperature
+    /// rise at
+    #[arg(long)]
+    pub steps: Option<u64>,
+}
+
+/// Thermal properties shared by all layers of tissue
+#[derive(Args, Deserialize, Clone, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThermalPropertiesArgs {
+    /// Density. Units: g*cm^3
+    #[arg(long)]
+    pub rho: Option<String>,
+
+    /// Specific heat capacity. Units: J*g^-1*K^-1
+    #[arg(long)]
+    pub c: Option<String>,
+
+    /// Thermal conductivity. Units: W*cm^-1*K^-1
+    #[arg(long)]
+    pub k: Option<String>,
+}
+
+/// A single layer of tissue
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LayerArgs {
+    /// Units: cm
+    pub d: String,
+
+    /// Units: cm
+    pub z0: String,
+
+    /// Units: cm^-1
+    pub mu_a: String,
+
+    /// Units: W*cm^-2
+    pub e0: String,
+}
+
+/// The beam profiles selectable from the command line
+#[derive(ValueEnum, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum BeamKind {
+    Large,
+    FlatTop,
+}
+
+/// The quadrature methods selectable from the command line
+#[derive(ValueEnum, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuadratureKind {
+    GaussKronrod,
+    TanhSinh,
+    AdaptiveSimpson,
+    Romberg,
+}
+
+/// A fully resolved simulation, ready to be run
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    pub precision: u64,
+    pub epsilon: Float,
+    pub quadrature: Quadrature,
+    pub thermal_properties: ThermalProperties<'static>,
+    pub layers: MultiLayer<'static>,
+    pub beam: Beam,
+    pub z: Float,
+    pub r: Float,
+    pub start: Float,
+    pub times: Vec<Float>,
+    pub pulse_width: Option<Float>,
+}
+
+/// A simulation which was cancelled before every time was completed
+#[derive(Clone, Debug)]
+pub struct Cancelled {
+    /// The temperature rise and approximate error at each of the times
+    /// completed before the simulation was cancelled
+    pub completed: Vec<(Float, Float)>,
+
+    /// The partial temperature rise at the next time, as reported by the
+    /// quadrature when it was cancelled
+    pub error: Box<QuadratureError<Float>>,
+}
+
+/// A beam selected at runtime
+#[derive(Clone, PartialEq, Debug)]
+pub enum Beam {
+    Large(LargeBeam),
+    FlatTop(FlatTopBeam<'static>),
+}
+
+impl greens::Beam for Beam {
+    fn evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        match self {
+            Self::Large(beam) => beam.evaluate_with(precision, thermal_properties, layer, z, r, tp),
+            Self::FlatTop(beam) => {
+                beam.evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Beam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Large(_) => write!(f, "large"),
+            Self::FlatTop(beam) => write!(f, "flat-top, radius = {:.6} cm", beam.radius()),
+        }
+    }
+}
+
+/// A quadrature method selected at runtime
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Quadrature {
+    GaussKronrod(GaussKronrod<'static>),
+    TanhSinh(TanhSinh),
+    AdaptiveSimpson(AdaptiveSimpson),
+    Romberg(Romberg),
+}
+
+impl Quadrature {
+    /// Creates the quadrature method of the given kind, with limits on
+    /// refinement generous enough for most simulations
+    pub fn new(kind: QuadratureKind, precision: u64) -> Self {
+        match kind {
+            QuadratureKind::GaussKronrod => Self::GaussKronrod(GaussKronrod {
+                interval_limit: 1024,
+                precision,
+                rule: &G7_K15,
+            }),
+            QuadratureKind::TanhSinh => Self::TanhSinh(TanhSinh {
+                iteration_limit: 10,
+                precision,
+            }),
+            QuadratureKind::AdaptiveSimpson => Self::AdaptiveSimpson(AdaptiveSimpson {
+                depth_limit: 48,
+                precision,
+                tolerance: Tolerance::Relative,
+            }),
+            QuadratureKind::Romberg => Self::Romberg(Romberg {
+                iteration_limit: 20,
+                precision,
+                tolerance: Tolerance::Relative,
+            }),
+        }
+    }
+}
+
+impl quadrature::Quadrature<Float> for Quadrature {
+    fn integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> (Float, Float) {
+        match self {
+            Self::GaussKronrod(quadrature) => quadrature.integrate(f, epsilon, bounds),
+            Self::TanhSinh(quadrature) => quadrature.integrate(f, epsilon, bounds),
+            Self::AdaptiveSimpson(quadrature) => quadrature.integrate(f, epsilon, bounds),
+            Self::Romberg(quadrature) => quadrature.integrate(f, epsilon, bounds),
+        }
+    }
+
+    fn try_integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        match self {
+            Self::GaussKronrod(quadrature) => quadrature.try_integrate(f, epsilon, bounds),
+            Self::TanhSinh(quadrature) => quadrature.try_integrate(f, epsilon, bounds),
+            Self::AdaptiveSimpson(quadrature) => quadrature.try_integrate(f, epsilon, bounds),
+            Self::Romberg(quadrature) => quadrature.try_integrate(f, epsilon, bounds),
+        }
+    }
+
+    fn try_integrate_cancellable(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        match self {
+            Self::GaussKronrod(quadrature) => {
+                quadrature.try_integrate_cancellable(f, epsilon, bounds, cancel)
+            }
+            Self::TanhSinh(quadrature) => {
+                quadrature.try_integrate_cancellable(f, epsilon, bounds, cancel)
+            }
+            Self::AdaptiveSimpson(quadrature) => {
+                quadrature.try_integrate_cancellable(f, epsilon, bounds, cancel)
+            }
+            Self::Romberg(quadrature) => {
+                quadrature.try_integrate_cancellable(f, epsilon, bounds, cancel)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Quadrature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GaussKronrod(_) => write!(f, "gauss-kronrod"),
+            Self::TanhSinh(_) => write!(f, "tanh-sinh"),
+            Self::AdaptiveSimpson(_) => write!(f, "adaptive-simpson"),
+            Self::Romberg(_) => write!(f, "romberg"),
+        }
+    }
+}
+
+impl fmt::Display for Simulation {
+    /// Writes a summary of every resolved parameter, with each layer on its
+    /// own line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "precision: {} bits", self.precision)?;
+        writeln!(f, "epsilon: {:.6}", self.epsilon)?;
+        writeln!(f, "quadrature: {}", self.quadrature)?;
+        writeln!(f, "thermal properties: {}", self.thermal_properties)?;
+        writeln!(f, "layers:")?;
+        for layer in self.layers.to_string().lines() {
+            writeln!(f, "  {layer}")?;
+        }
+        writeln!(f, "beam: {}", self.beam)?;
+        writeln!(f, "point: z = {:.6} cm, r = {:.6} cm", self.z, self.r)?;
+        if let Some(pulse_width) = &self.pulse_width {
+            writeln!(f, "pulse width: {pulse_width:.6} s")?;
+        }
+        write!(
+            f,
+            "times: {} over {:.6}..{:.6} s",
+            self.times.len(),
+            self.start,
+            self.times
+                .last()
+                .expect("simulations always have at least one time")
+        )
+    }
+}
+
+impl Simulation {
+    /// Calculates the temperature rise at each of the simulation's times at
+    /// the point (z, r), stopping early once `cancel` is set
+    pub fn series_at(
+        &self,
+        z: &Float,
+        r: &Float,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<(Float, Float)>, Cancelled> {
+        self.series_at_with_progress(z, r, |_| (), cancel)
+    }
+
+    /// Calculates the temperature rise at each of the simulation's times at
+    /// the point (z, r) as in [`fn@Self::series_at`], calling `progress` with
+    /// the number of completed times each time one is completed
+    ///
+    /// With a pulse, each time is integrated over separately as the exposure
+    /// observed at it, and `start` only determines where the times begin
+    pub fn series_at_with_progress(
+        &self,
+        z: &Float,
+        r: &Float,
+        mut progress: impl FnMut(usize),
+        cancel: &AtomicBool,
+    ) -> Result<Vec<(Float, Float)>, Cancelled> {
+        let mut series = Vec::with_capacity(self.times.len());
+
+        let result = match &self.pulse_width {
+            None => self.layers.temperature_rise_stream_cancellable(
+                self.precision,
+                &self.quadrature,
+                &self.beam,
+                &self.thermal_properties,
+                z,
+                r,
+                &self.epsilon,
+                &self.start,
+                &self.times,
+                |_, temperature_rise, error| {
+                    series.push((temperature_rise.clone(), error.clone()));
+                    progress(series.len());
+                },
+                cancel,
+            ),
+            Some(pulse_width) => {
+                let exposure_start = Float::with_val_64(self.precision, Special::Zero);
+
+                self.times.iter().try_for_each(|time| {
+                    series.push(self.layers.temperature_rise_exposure_cancellable(
+                        self.precision,
+                        &self.quadrature,
+                        &self.beam,
+                        &self.thermal_properties,
+                        z,
+                        r,
+                        &self.epsilon,
+                        (&exposure_start, pulse_width),
+                        time,
+                        cancel,
+                    )?);
+                    progress(series.len());
+                    Ok(())
+                })
+            }
+        };
+
+        match result {
+            Ok(()) => Ok(series),
+            Err(CancellableError::Quadrature(error)) => Err(Cancelled {
+                completed: series,
+                error: Box::new(error),
+            }),
+            Err(CancellableError::Bounds(error)) => {
+                panic!("the pulse width and times are validated when resolved: {error}")
+            }
+        }
+    }
+
+    /// Evaluates the kernel integrated over time by [`fn@Self::series_at`],
+    /// the instantaneous response to the beam, at each of the simulation's
+    /// times at the point (z, r)
+    pub fn kernel_at(&self, z: &Float, r: &Float) -> Vec<Float> {
+        self.times
+            .iter()
+            .map(|tp| {
+                self.layers.evaluate_with(
+                    self.precision,
+                    &self.beam,
+                    &self.thermal_properties,
+                    z,
+                    r,
+                    tp,
+                )
+            })
+            .collect()
+    }
+}
+
+fn parse_layer(value: &str) -> anyhow::Result<LayerArgs> {
+    let fields = value.split(',').map(str::trim).collect::<Vec<_>>();
+
+    match fields[..] {
+        [d, z0, mu_a, e0] => Ok(LayerArgs {
+            d: d.to_string(),
+            z0: z0.to_string(),
+            mu_a: mu_a.to_string(),
+            e0: e0.to_string(),
+        }),
+        _ => Err(anyhow!(
+            "expected a layer in the form `d,z0,mu_a,e0`, got `{value}`"
+        )),
+    }
+}
+
+/// Reads layers from the file at `path`, or from stdin if `path` is `-`
+///
+/// Each line gives a single layer as `d z0 mu_a e0`, separated by whitespace.
+/// Blank lines are skipped, and errors report the number of the offending
+/// line. The values are only checked to be numbers here, and are parsed at
+/// the simulation's precision once it is resolved
+pub fn read_layers(path: &Path) -> anyhow::Result<Vec<LayerArgs>> {
+    let contents = if path == Path::new("-") {
+        io::read_to_string(io::stdin()).context("unable to read layers from stdin")?
+    } else {
+        fs::read_to_string(path).with_context(|| format!("unable to read layers from {path:?}"))?
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_layer_line(line).with_context(|| format!("invalid layer on line {}", i + 1))
+        })
+        .collect()
+}
+
+fn parse_layer_line(line: &str) -> anyhow::Result<LayerArgs> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+
+    let [d, z0, mu_a, e0] = fields[..] else {
+        return Err(anyhow!(
+            "expected a layer in the form `d z0 mu_a e0`, got `{line}`"
+        ));
+    };
+
+    for (name, value) in [("d", d), ("z0", z0), ("mu_a", mu_a), ("e0", e0)] {
+        Float::parse(value).with_context(|| format!("invalid value for `{name}`: `{value}`"))?;
+    }
+
+    Ok(LayerArgs {
+        d: d.to_string(),
+        z0: z0.to_string(),
+        mu_a: mu_a.to_string(),
+        e0: e0.to_string(),
+    })
+}
+
+fn parse_float(name: &str, value: &str, precision: u64) -> anyhow::Result<Float> {
+    Float::parse(value)
+        .map(|parsed| Float::with_val_64(precision, parsed))
+        .with_context(|| format!("invalid value for `{name}`: `{value}`"))
+}
+
+fn require<'a>(name: &str, value: &'a Option<String>) -> anyhow::Result<&'a str> {
+    value
+        .as_deref()
+        .ok_or_else(|| anyhow!("`{name}` must be provided in the configuration or as a flag"))
+}
+
+impl SimulationArgs {
+    /// Reads [`struct@SimulationArgs`] from a YAML configuration file
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("unable to read the configuration at {path:?}"))?;
+
+        serde_yml::from_str(&contents)
+            .with_context(|| format!("unable to parse the configuration at {path:?}"))
+    }
+
+    /// Resolves a [`struct@Simulation`] from `self`, with fields missing from
+    /// `self` read from the configuration file at `config`, if provided
+    ///
+    /// If `self` names a file to read layers from, they are read before
+    /// anything else and take precedence as if given with `--layer`
+    pub fn load(mut self, config: Option<&Path>) -> anyhow::Result<Simulation> {
+        if let Some(path) = self.layers_from.take() {
+            self.layers = read_layers(&path)?;
+        }
+
+        match config {
+            Some(path) => Self::from_file(path)?.merge(self),
+            None => self,
+        }
+        .resolve()
+    }
+
+    /// Overrides fields of `self` with those set in `overrides`
+    pub fn merge(self, overrides: Self) -> Self {
+        Self {
+            precision: overrides.precision.or(self.precision),
+            epsilon: overrides.epsilon.or(self.epsilon),
+            quadrature: overrides.quadrature.or(self.quadrature),
+            thermal_properties: ThermalPropertiesArgs {
+                rho: overrides
+                    .thermal_properties
+                    .rho
+                    .or(self.thermal_properties.rho),
+                c: overrides.thermal_properties.c.or(self.thermal_properties.c),
+                k: overrides.thermal_properties.k.or(self.thermal_properties.k),
+            },
+            layers: if overrides.layers.is_empty() {
+                self.layers
+            } else {
+                overrides.layers
+            },
+            layers_from: overrides.layers_from.or(self.layers_from),
+            beam: overrides.beam.or(self.beam),
+            radius: overrides.radius.or(self.radius),
+            z: overrides.z.or(self.z),
+            r: overrides.r.or(self.r),
+            start: overrides.start.or(self.start),
+            end: overrides.end.or(self.end),
+            pulse_width: overrides.pulse_width.or(self.pulse_width),
+            steps: overrides.steps.or(self.steps),
+        }
+    }
+
+    /// Parses and validates all parameters, producing a [`struct@Simulation`]
+    pub fn resolve(&self) -> anyhow::Result<Simulation> {
+        let precision = self.precision.unwrap_or(64);
+        let float = |name: &str, value: &str| parse_float(name, value, precision);
+
+        let epsilon = match &self.epsilon {
+            Some(epsilon) => float("epsilon", epsilon)?,
+            None => quadrature::default_epsilon(precision),
+        };
+        let quadrature = Quadrature::new(
+            self.quadrature.unwrap_or(QuadratureKind::GaussKronrod),
+            precision,
+        );
+
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Owned(float("rho", require("rho", &self.thermal_properties.rho)?)?),
+            c: Cow::Owned(float("c", require("c", &self.thermal_properties.c)?)?),
+            k: Cow::Owned(float("k", require("k", &self.thermal_properties.k)?)?),
+        };
+        thermal_properties
+            .validate()
+            .context("invalid thermal properties")?;
+
+        if self.layers.is_empty() {
+            return Err(anyhow!(
+                "at least one layer must be provided in the configuration or as a flag"
+            ));
+        }
+
+        let mut layers = Vec::with_capacity(self.layers.len());
+        for layer in &self.layers {
+            layers.push(Layer {
+                d: Cow::Owned(float("d", &layer.d)?),
+                z0: Cow::Owned(float("z0", &layer.z0)?),
+                mu_a: Cow::Owned(float("mu_a", &layer.mu_a)?),
+                e0: Cow::Owned(float("e0", &layer.e0)?),
+                n: None,
+            });
+        }
+
+        let pulse_width = self
+            .pulse_width
+            .as_deref()
+            .map(|pulse_width| float("pulse_width", pulse_width))
+            .transpose()?;
+
+        if let Some(pulse_width) = &pulse_width {
+            if !pulse_width.is_finite() || pulse_width.is_sign_negative() {
+                return Err(anyhow!("`pulse_width` must be finite and not negative"));
+            }
+        }
+
+        let end = match (&self.end, &pulse_width) {
+            (Some(end), _) => float("end", end)?,
+
+            // layers which absorb nothing have an infinite relaxation time,
+            // but there's no heat in them to wait on
+            (None, Some(pulse_width)) => layers
+                .iter()
+                .map(|layer| {
+                    greens::auto_time_bound(layer, &thermal_properties, pulse_width, precision)
+                })
+                .filter(Float::is_finite)
+                .max_by(|a, b| a.total_cmp(b))
+                .ok_or_else(|| anyhow!("`end` must be provided when no layer absorbs anything"))?,
+            (None, None) => float("end", require("end", &self.end)?)?,
+        };
+
+        let layers = MultiLayer::new(layers).context("unable to construct the layers")?;
+
+        let beam = match self.beam {
+            Some(BeamKind::Large) => Beam::Large(LargeBeam),
+            Some(BeamKind::FlatTop) => Beam::FlatTop(
+                FlatTopBeam::new(&float("radius", require("radius", &self.radius)?)?)
+                    .context("unable to construct the beam")?,
+            ),
+            None => {
+                return Err(anyhow!(
+                    "`beam` must be provided in the configuration or as a flag"
+                ))
+            }
+        };
+
+        let z = float("z", self.z.as_deref().unwrap_or("0"))?;
+        let r = float("r", self.r.as_deref().unwrap_or("0"))?;
+        let start = float("start", self.start.as_deref().unwrap_or("0"))?;
+
+        if pulse_width.is_some() && !(start.is_finite() && end.is_finite()) {
+            return Err(anyhow!("`start` and `end` must be finite with a pulse"));
+        }
+
+        let steps = self.steps.unwrap_or(1);
+        if steps == 0 {
+            return Err(anyhow!("`steps` must be at least 1"));
+        }
+
+        // times are evenly spaced over start..end, excluding start itself
+
+        let mut width = end.clone();
+        width -= &start;
+        width /= steps;
+
+        let mut times = Vec::with_capacity(steps as usize);
+        for step in 1..steps {
+            let mut time = width.clone();
+            time *= step;
+            time += &start;
+            times.push(time);
+        }
+        times.push(end);
+
+        Ok(Simulation {
+            precision,
+            epsilon,
+            quadrature,
+            thermal_properties,
+            layers,
+            beam,
+            z,
+            r,
+            start,
+            times,
+            pulse_width,
+        })
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use anyhow::{anyhow, Context};
+use rayon::prelude::*;
+use rug::Float;
+use std::sync::atomic::AtomicBool;
+
+use crate::config::{Cancelled, Simulation};
+
+/// The largest number of values a [`struct@Range`] may produce, beyond which
+/// the step is almost certainly a mistake
+pub const MAX_POINTS: u32 = 1 << 20;
+
+/// An evenly spaced range of values, given as `start,stop,step`
+#[derive(Clone, Debug)]
+pub struct Range {
+    start: String,
+    stop: String,
+    step: String,
+}
+
+pub fn parse_range(value: &str) -> anyhow::Result<Range> {
+    let fields = value.split(',').map(str::trim).collect::<Vec<_>>();
+
+    match fields[..] {
+        [start, stop, step] => Ok(Range {
+            start: start.to_string(),
+            stop: stop.to_string(),
+            step: step.to_string(),
+        }),
+        _ => Err(anyhow!(
+            "expected a range in the form `start,stop,step`, got `{value}`"
+        )),
+    }
+}
+
+impl Range {
+    /// Produces every value in the range at the given precision, including
+    /// `stop` if it falls on a step
+    pub fn points(&self, name: &str, precision: u64) -> anyhow::Result<Vec<Float>> {
+        let float = |value: &str| {
+            Float::parse(value)
+                .map(|parsed| Float::with_val_64(precision, parsed))
+                .with_context(|| format!("invalid value in `{name}`: `{value}`"))
+        };
+
+        let start = float(&self.start)?;
+        let stop = float(&self.stop)?;
+        let step = float(&self.step)?;
+
+        if step.is_nan() || step <= 0 {
+            return Err(anyhow!("the step of `{name}` must be positive"));
+        }
+
+        if stop < start {
+            return Err(anyhow!("the stop of `{name}` must not precede its start"));
+        }
+
+        // the count is nudged upward slightly so that `stop` isn't excluded
+        // due to rounding in the division
+
+        let mut count = stop.clone();
+        count -= &start;
+        count /= &step;
+        count += 1e-9;
+        count.floor_mut();
+
+        // this is checked before anything is allocated, as a tiny step would
+        // otherwise exhaust memory
+
+        if count >= MAX_POINTS {
+            return Err(anyhow!(
+                "`{name}` would produce more than {MAX_POINTS} values; use a larger step"
+            ));
+        }
+
+        let count = count
+            .to_u32_saturating()
+            .context("the range is too large")?;
+
+        let mut points = Vec::with_capacity(count as usize + 1);
+        for i in 0..=count {
+            let mut point = step.clone();
+            point *= i;
+            point += &start;
+            points.push(point);
+        }
+
+        Ok(points)
+    }
+}
+
+/// Calculates the temperature rise at every (z, r) point, in parallel
+///
+/// Points are returned in row-major order (all radial distances for the first
+/// depth, then the second, and so on) regardless of the order they were
+/// computed in. If `peak` is set, the value at each point is the peak over
+/// all of the simulation's times rather than the value at the final time
+///
+/// Once `cancel` is set, every point stops at its next opportunity, and
+/// whichever of them was cancelled first to be collected is returned
+pub fn evaluate(
+    simulation: &Simulation,
+    zs: &[Float],
+    rs: &[Float],
+    peak: bool,
+    cancel: &AtomicBool,
+) -> Result<Vec<(Float, Float, Float)>, Cancelled> {
+    let points = zs
+        .iter()
+        .flat_map(|z| rs.iter().map(move |r| (z, r)))
+        .collect::<Vec<_>>();
+
+    points
+        .into_par_iter()
+        .map(|(z, r)| {
+            let series = simulation.series_at(z, r, cancel)?;
+
+            let value = if peak {
+                series
+                    .into_iter()
+                    .map(|(value, _)| value)
+                    .reduce(|peak, value| peak.max(&value))
+            } else {
+                series.into_iter().next_back().map(|(value, _)| value)
+            }
+            .expect("simulations always have at least one time");
+
+            Ok((z.clone(), r.clone(), value))
+        })
+        .collect()
+}
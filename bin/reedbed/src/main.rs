@@ -13,7 +13,7 @@
 #![deny(clippy::cast_lossless)]
 #![deny(clippy::clone_on_ref_ptr)]
 #![deny(clippy::doc_markdown)]
-#![deny(clippy::empty_enum)]
+#![deny(clippy::empty_enums)]
 #![deny(clippy::enum_glob_use)]
 #![deny(clippy::exit)]
 #![deny(clippy::explicit_into_iter_loop)]
@@ -28,14 +28,61 @@
 #![deny(clippy::option_option)]
 #![deny(clippy::mut_mut)]
 
-use clap::{Parser, Subcommand};
-use rug::Float;
+mod config;
+mod grid;
+mod output;
+mod progress;
 
-use reedbed_lib::utilities;
+use anyhow::{anyhow, Context};
+use clap::{Args, Parser, Subcommand};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    config::{Cancelled, SimulationArgs},
+    grid::{parse_range, Range},
+    output::Format,
+    progress::Progress,
+};
 
 #[global_allocator]
 static GLOBAL_ALLOCATOR: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+/// Set once ctrl-c is pressed, after which the simulation stops at its next
+/// opportunity
+///
+/// This has to be a static rather than shared through an `Arc`, as it is set
+/// from within a signal handler
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Sets [`static@INTERRUPTED`] upon the first ctrl-c, restoring the default
+/// handler so that a second one terminates the process as usual
+extern "C" fn interrupt(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+
+    // SAFETY: signal is async-signal-safe
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+    }
+}
+
+/// Installs [`fn@interrupt`] as the handler for ctrl-c
+fn handle_interrupts() {
+    // SAFETY: the handler only stores to an atomic and calls signal, both of
+    // which are async-signal-safe
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
 /// Command line interface for a Green's function based model for calculating
 /// temperature rise resulting from laser exposure in retinal tissue
 #[derive(Parser, Debug)]
@@ -49,15 +96,820 @@ struct Cli {
 }
 
 #[derive(Subcommand, Debug)]
-enum Commands {}
+enum Commands {
+    /// Calculate the temperature rise at a point over a series of times
+    TemperatureRise(TemperatureRise),
+
+    /// Calculate the temperature rise over a grid of points in parallel
+    Grid(Grid),
+
+    /// Evaluate the kernel integrated over time by `temperature-rise` (the
+    /// instantaneous response to the beam) at a point over a series of times
+    Kernel(Kernel),
+}
+
+#[derive(Args, Debug)]
+struct TemperatureRise {
+    /// Path to a YAML file describing the simulation. Flags take precedence
+    /// over any fields set in it
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    #[command(flatten)]
+    simulation: SimulationArgs,
+
+    /// The format to write results in
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+
+    /// Path to write results to rather than stdout. For the npy format, this
+    /// is the stem of the names of the files written
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Round results to this many significant decimal digits when writing
+    /// them. This does not affect the precision they are computed at, and has
+    /// no effect on the npy format
+    #[arg(long)]
+    digits: Option<NonZeroUsize>,
+
+    /// Write the approximate error of each temperature rise alongside it, to
+    /// check whether `epsilon` was achieved at each time
+    #[arg(long)]
+    with_error: bool,
+
+    /// Display a progress bar on stderr. This has no effect if stderr is not
+    /// a terminal
+    #[arg(long)]
+    progress: bool,
+
+    /// Validate the simulation and print it as resolved without computing
+    /// anything
+    #[arg(long)]
+    check: bool,
+}
+
+impl TemperatureRise {
+    /// Runs the simulation, writing the results to `output`
+    ///
+    /// If `cancel` is set partway through, the times completed until then are
+    /// still written before the cancellation is reported
+    fn run(self, output: &mut impl Write, cancel: &AtomicBool) -> anyhow::Result<()> {
+        let simulation = self.simulation.load(self.config.as_deref())?;
+
+        if self.check {
+            return writeln!(output, "{simulation}").context("unable to write the simulation");
+        }
+
+        let progress = Progress::new(simulation.times.len(), self.progress);
+        let result = simulation.series_at_with_progress(
+            &simulation.z,
+            &simulation.r,
+            |completed| progress.update(completed),
+            cancel,
+        );
+        progress.finish();
+
+        let (series, cancelled) = match result {
+            Ok(series) => (series, None),
+            Err(Cancelled { completed, error }) => (completed, Some(error)),
+        };
+        let times = &simulation.times[..series.len()];
+
+        match (self.format, &self.output) {
+            (Format::Npy, Some(stem)) => {
+                output::write_series_npy(stem, times, &series, self.with_error)
+            }
+            (format, Some(path)) => {
+                let mut file = BufWriter::new(
+                    File::create(path).with_context(|| format!("unable to create {path:?}"))?,
+                );
+                output::write_series(
+                    format,
+                    times,
+                    &series,
+                    self.digits,
+                    self.with_error,
+                    &mut file,
+                )?;
+                file.flush()
+                    .with_context(|| format!("unable to write to {path:?}"))
+            }
+            (format, None) => {
+                output::write_series(format, times, &series, self.digits, self.with_error, output)
+            }
+        }?;
+
+        match cancelled {
+            Some(error) => Err(anyhow!(error).context(format!(
+                "interrupted after {} of {} times",
+                series.len(),
+                simulation.times.len()
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+struct Grid {
+    /// Path to a YAML file describing the simulation. Flags take precedence
+    /// over any fields set in it
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Parameters describing the simulation. `z` and `r` are ignored in
+    /// favor of the grid
+    #[command(flatten)]
+    simulation: SimulationArgs,
+
+    /// Depths to evaluate at, given as `start,stop,step`. Units: cm
+    #[arg(long, value_parser = parse_range)]
+    z_range: Range,
+
+    /// Radial distances to evaluate at, given as `start,stop,step`. Units: cm
+    #[arg(long, value_parser = parse_range)]
+    r_range: Range,
+
+    /// Report the peak temperature rise over all of the simulation's times
+    /// rather than the temperature rise at the final time
+    #[arg(long)]
+    peak: bool,
+
+    /// Round results to this many significant decimal digits when writing
+    /// them. This does not affect the precision they are computed at
+    #[arg(long)]
+    digits: Option<NonZeroUsize>,
+
+    /// Validate the simulation and the ranges and print the simulation as
+    /// resolved without computing anything
+    #[arg(long)]
+    check: bool,
+}
+
+impl Grid {
+    /// Runs the simulation over the grid, writing the results to `output`
+    ///
+    /// If `cancel` is set partway through, nothing is written, as the points
+    /// completed until then are scattered throughout the grid
+    fn run(self, output: &mut impl Write, cancel: &AtomicBool) -> anyhow::Result<()> {
+        let simulation = self.simulation.load(self.config.as_deref())?;
+
+        let zs = self.z_range.points("z-range", simulation.precision)?;
+        let rs = self.r_range.points("r-range", simulation.precision)?;
+
+        if self.check {
+            return writeln!(
+                output,
+                "{simulation}\ngrid: {} depths, {} radial distances",
+                zs.len(),
+                rs.len()
+            )
+            .context("unable to write the simulation");
+        }
+
+        let values = grid::evaluate(&simulation, &zs, &rs, self.peak, cancel)
+            .map_err(|Cancelled { error, .. }| anyhow!(error).context("interrupted"))?;
+
+        output::write_grid(&values, self.digits, output)
+    }
+}
+
+#[derive(Args, Debug)]
+struct Kernel {
+    /// Path to a YAML file describing the simulation. Flags take precedence
+    /// over any fields set in it
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Parameters describing the simulation. Its times are used as the times
+    /// since deposition to evaluate the kernel at, and `start`, `epsilon`,
+    /// `quadrature`, and `pulse_width` are ignored
+    #[command(flatten)]
+    simulation: SimulationArgs,
+
+    /// The format to write results in
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+
+    /// Path to write results to rather than stdout. For the npy format, this
+    /// is the stem of the names of the files written
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Round results to this many significant decimal digits when writing
+    /// them. This does not affect the precision they are computed at, and has
+    /// no effect on the npy format
+    #[arg(long)]
+    digits: Option<NonZeroUsize>,
+
+    /// Validate the simulation and print it as resolved without computing
+    /// anything
+    #[arg(long)]
+    check: bool,
+}
+
+impl Kernel {
+    fn run(self, output: &mut impl Write) -> anyhow::Result<()> {
+        let simulation = self.simulation.load(self.config.as_deref())?;
+
+        if self.check {
+            return writeln!(output, "{simulation}").context("unable to write the simulation");
+        }
+
+        let kernel = simulation.kernel_at(&simulation.z, &simulation.r);
+
+        match (self.format, &self.output) {
+            (Format::Npy, Some(stem)) => output::write_kernel_npy(stem, &simulation.times, &kernel),
+            (format, Some(path)) => {
+                let mut file = BufWriter::new(
+                    File::create(path).with_context(|| format!("unable to create {path:?}"))?,
+                );
+                output::write_kernel(format, &simulation.times, &kernel, self.digits, &mut file)?;
+                file.flush()
+                    .with_context(|| format!("unable to write to {path:?}"))
+            }
+            (format, None) => {
+                output::write_kernel(format, &simulation.times, &kernel, self.digits, output)
+            }
+        }
+    }
+}
 
 fn main() -> anyhow::Result<()> {
-    let _app = Cli::parse();
+    let cli = Cli::parse();
+
+    handle_interrupts();
+
+    match cli.command {
+        Commands::TemperatureRise(command) => command.run(&mut io::stdout().lock(), &INTERRUPTED),
+        Commands::Grid(command) => command.run(&mut io::stdout().lock(), &INTERRUPTED),
+        Commands::Kernel(command) => command.run(&mut io::stdout().lock()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rug::Float;
+
+    fn run(arguments: &[&str]) -> String {
+        let cli = Cli::try_parse_from(arguments).expect("Unable to parse the arguments");
+        let mut output = Vec::new();
+        let cancel = AtomicBool::new(false);
+
+        match cli.command {
+            Commands::TemperatureRise(command) => command
+                .run(&mut output, &cancel)
+                .expect("Unable to run the simulation"),
+            Commands::Grid(command) => command
+                .run(&mut output, &cancel)
+                .expect("Unable to run the simulation"),
+            Commands::Kernel(command) => command
+                .run(&mut output)
+                .expect("Unable to run the simulation"),
+        }
+
+        String::from_utf8(output).expect("The output was not UTF-8")
+    }
+
+    #[test]
+    fn config_matches_flags() {
+        let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+
+        let from_config = run(&["reedbed", "temperature-rise", "--config", config]);
+        let from_flags = run(&[
+            "reedbed",
+            "temperature-rise",
+            "--precision",
+            "64",
+            "--epsilon",
+            "1e-9",
+            "--rho",
+            "1",
+            "--c",
+            "4.178",
+            "--k",
+            "0.0063",
+            "--layer",
+            "0.001,0,1000,1",
+            "--layer",
+            "0.01,0.001,10,0",
+            "--beam",
+            "flat-top",
+            "--radius",
+            "0.01",
+            "--z",
+            "0.0005",
+            "--r",
+            "0",
+            "--start",
+            "0",
+            "--end",
+            "0.1",
+            "--steps",
+            "4",
+        ]);
+
+        assert_eq!(from_config, from_flags);
+        assert_eq!(from_config.lines().count(), 5);
+
+        // flags take precedence over the configuration
+
+        let overridden = run(&[
+            "reedbed",
+            "temperature-rise",
+            "--config",
+            config,
+            "--steps",
+            "2",
+        ]);
+
+        assert_ne!(overridden, from_config);
+        assert_eq!(overridden.lines().count(), 3);
+    }
+
+    #[test]
+    fn progress_does_not_alter_output() {
+        let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+
+        assert_eq!(
+            run(&["reedbed", "temperature-rise", "--config", config]),
+            run(&[
+                "reedbed",
+                "temperature-rise",
+                "--config",
+                config,
+                "--progress"
+            ]),
+        );
+    }
+
+    #[test]
+    fn npy_output() {
+        let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+        let stem = std::env::temp_dir().join(format!("reedbed-npy-{}", std::process::id()));
+
+        let output = run(&[
+            "reedbed",
+            "temperature-rise",
+            "--config",
+            config,
+            "--steps",
+            "3",
+            "--format",
+            "npy",
+            "-o",
+            stem.to_str()
+                .expect("The temporary directory was not UTF-8"),
+        ]);
+        assert!(output.is_empty());
+
+        for suffix in ["time", "temperature_rise"] {
+            let path = stem.with_extension(format!("{suffix}.npy"));
+            let contents = std::fs::read(&path).expect("Unable to read the npy file");
+            std::fs::remove_file(&path).expect("Unable to remove the npy file");
+
+            assert_eq!(&contents[..8], b"\x93NUMPY\x01\x00");
+
+            let header_length = u16::from_le_bytes([contents[8], contents[9]]) as usize;
+            let header = std::str::from_utf8(&contents[10..10 + header_length])
+                .expect("The header was not UTF-8");
+
+            assert_eq!((10 + header_length) % 64, 0);
+            assert!(header.contains("'descr': '<f8'"));
+            assert!(header.contains("'shape': (3,)"));
+            assert!(header.ends_with('\n'));
+            assert_eq!(contents.len(), 10 + header_length + 3 * 8);
+        }
+    }
+
+    #[test]
+    fn overlapping_layers() {
+        let cli = Cli::try_parse_from([
+            "reedbed",
+            "temperature-rise",
+            "--rho",
+            "1",
+            "--c",
+            "1",
+            "--k",
+            "1",
+            "--layer",
+            "1,0,1,1",
+            "--layer",
+            "1,0.5,1,1",
+            "--beam",
+            "large",
+            "--end",
+            "1",
+        ])
+        .expect("Unable to parse the arguments");
+
+        let Commands::TemperatureRise(command) = cli.command else {
+            panic!("Parsed the wrong command");
+        };
+        let error = command
+            .run(&mut Vec::new(), &AtomicBool::new(false))
+            .expect_err("Overlapping layers were accepted");
+
+        assert!(format!("{error:#}").contains("overlaps"));
+    }
+
+    #[test]
+    fn interrupted_runs_report_cancellation() {
+        let simulation = [
+            "--rho",
+            "1",
+            "--c",
+            "4.178",
+            "--k",
+            "0.0063",
+            "--layer",
+            "0.001,0,1000,1",
+            "--beam",
+            "large",
+            "--end",
+            "0.01",
+            "--steps",
+            "2",
+        ];
+        let cancel = AtomicBool::new(true);
+
+        // the times completed before the interruption (none, as it came
+        // before the first) are still written, followed by the error
+
+        let mut arguments = vec!["reedbed", "temperature-rise"];
+        arguments.extend(simulation);
+        let Commands::TemperatureRise(command) = Cli::try_parse_from(arguments)
+            .expect("Unable to parse the arguments")
+            .command
+        else {
+            panic!("Parsed the wrong command");
+        };
+        let mut output = Vec::new();
+        let error = command
+            .run(&mut output, &cancel)
+            .expect_err("The interruption was ignored");
+
+        assert!(format!("{error:#}").contains("interrupted after 0 of 2 times"));
+        assert_eq!(
+            String::from_utf8(output).expect("The output was not UTF-8"),
+            "time,temperature_rise\n"
+        );
+
+        let mut arguments = vec!["reedbed", "grid"];
+        arguments.extend(simulation);
+        arguments.extend(["--z-range", "0,0.001,0.0005", "--r-range", "0,0.02,0.01"]);
+        let Commands::Grid(command) = Cli::try_parse_from(arguments)
+            .expect("Unable to parse the arguments")
+            .command
+        else {
+            panic!("Parsed the wrong command");
+        };
+        let mut output = Vec::new();
+        let error = command
+            .run(&mut output, &cancel)
+            .expect_err("The interruption was ignored");
+
+        assert!(format!("{error:#}").contains("cancelled"));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn grid_matches_serial() {
+        let simulation = [
+            "--rho",
+            "1",
+            "--c",
+            "4.178",
+            "--k",
+            "0.0063",
+            "--layer",
+            "0.001,0,1000,1",
+            "--beam",
+            "large",
+            "--end",
+            "0.01",
+            "--steps",
+            "2",
+        ];
+
+        let mut arguments = vec!["reedbed", "grid"];
+        arguments.extend(simulation);
+        arguments.extend(["--z-range", "0,0.001,0.0005", "--r-range", "0,0.02,0.01"]);
+        let parallel = run(&arguments);
+
+        let mut arguments = vec!["reedbed", "temperature-rise"];
+        arguments.extend(simulation);
+        let Commands::TemperatureRise(command) = Cli::try_parse_from(arguments)
+            .expect("Unable to parse the arguments")
+            .command
+        else {
+            panic!("Parsed the wrong command");
+        };
+        let simulation = command
+            .simulation
+            .load(None)
+            .expect("Unable to resolve the simulation");
+
+        let mut serial = vec!["z,r,value".to_string()];
+        for z in ["0", "0.0005", "0.001"] {
+            for r in ["0", "0.01", "0.02"] {
+                let z = Float::with_val(64, Float::parse(z).unwrap());
+                let r = Float::with_val(64, Float::parse(r).unwrap());
+                let (value, _) = simulation
+                    .series_at(&z, &r, &AtomicBool::new(false))
+                    .expect("The simulation was cancelled")
+                    .pop()
+                    .unwrap();
+                serial.push(format!("{z},{r},{value}"));
+            }
+        }
+
+        assert_eq!(parallel.lines().collect::<Vec<_>>(), serial);
+    }
+
+    #[test]
+    fn grid_rejects_tiny_steps() {
+        let error = parse_range("0,1,1e-12")
+            .and_then(|range| range.points("--z-range", 64))
+            .expect_err("A range of 10^12 points was accepted");
+
+        assert!(format!("{error:#}").contains("use a larger step"));
+
+        let points = parse_range("0,1,1e-3")
+            .and_then(|range| range.points("--z-range", 64))
+            .expect("Unable to produce the points of a range");
+        assert_eq!(points.len(), 1001);
+    }
+
+    #[test]
+    fn digits() {
+        let simulation = [
+            "--precision",
+            "256",
+            "--rho",
+            "1",
+            "--c",
+            "4.178",
+            "--k",
+            "0.0063",
+            "--layer",
+            "0.001,0,1000,1",
+            "--beam",
+            "large",
+            "--start",
+            "0.001",
+            "--end",
+            "0.01",
+            "--steps",
+            "2",
+        ];
+
+        // the number of significant digits in the mantissa of each value
+        let significant = |line: &str| {
+            line.split(',')
+                .map(|value| {
+                    let mantissa = value.split('e').next().unwrap();
+                    mantissa.chars().filter(char::is_ascii_digit).count()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut arguments = vec!["reedbed", "temperature-rise"];
+        arguments.extend(simulation);
+
+        let full = run(&arguments);
+        assert!(full.lines().skip(1).all(|line| significant(line)[1] > 70));
+
+        arguments.extend(["--digits", "8"]);
+
+        let rounded = run(&arguments);
+        assert_eq!(rounded.lines().count(), 3);
+        for line in rounded.lines().skip(1) {
+            assert_eq!(significant(line), [8, 8]);
+        }
+
+        arguments.extend(["--format", "json"]);
+        assert!(run(&arguments).contains("\"time\":5.5000000e-3,"));
+
+        let mut arguments = vec!["reedbed", "grid"];
+        arguments.extend(simulation);
+        arguments.extend([
+            "--z-range",
+            "0.0005,0.0005,1",
+            "--r-range",
+            "0,0,1",
+            "--digits",
+            "3",
+        ]);
+        assert_eq!(
+            run(&arguments).lines().nth(1).map(significant),
+            Some(vec![3, 1, 3])
+        );
+    }
+
+    #[test]
+    fn with_error() {
+        let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+
+        let output = run(&[
+            "reedbed",
+            "temperature-rise",
+            "--config",
+            config,
+            "--steps",
+            "2",
+            "--with-error",
+        ]);
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("time,temperature_rise,error"));
+
+        for line in lines {
+            let error = line.split(',').nth(2).expect("The error column is missing");
+            assert!(Float::parse(error).is_ok());
+        }
+
+        let output = run(&[
+            "reedbed",
+            "temperature-rise",
+            "--config",
+            config,
+            "--steps",
+            "2",
+            "--with-error",
+            "--format",
+            "json",
+        ]);
+        assert_eq!(output.matches("\"error\":").count(), 2);
+        assert!(!output.contains("\"error\":null"));
+    }
+
+    #[test]
+    fn pulse_width() {
+        let simulation = [
+            "--rho",
+            "1",
+            "--c",
+            "4.178",
+            "--k",
+            "0.0063",
+            "--layer",
+            "0.001,0,1000,1",
+            "--layer",
+            "0.01,0.001,10,0",
+            "--beam",
+            "large",
+            "--z",
+            "0.0005",
+            "--steps",
+            "2",
+            "--pulse-width",
+            "0.001",
+        ];
+
+        let mut arguments = vec!["reedbed", "temperature-rise"];
+        arguments.extend(simulation);
+        let Commands::TemperatureRise(command) = Cli::try_parse_from(&arguments)
+            .expect("Unable to parse the arguments")
+            .command
+        else {
+            panic!("Parsed the wrong command");
+        };
+        let simulation = command
+            .simulation
+            .load(None)
+            .expect("Unable to resolve the simulation");
+
+        // the weakly absorbing layer relaxes the slowest, so the observation
+        // window is chosen to accommodate it
+
+        let pulse_width = Float::with_val(64, 0.001);
+        let layer = reedbed_lib::greens::Layer::from_f64(64, 0.01, 0.001, 10.0, 0.0);
+        assert_eq!(
+            simulation.times.last(),
+            Some(&reedbed_lib::greens::auto_time_bound(
+                &layer,
+                &simulation.thermal_properties,
+                &pulse_width,
+                64,
+            ))
+        );
+
+        // long after the pulse, the layers have mostly cooled, which is far
+        // below the temperature rise of a continuous exposure
+
+        let last = |output: String| {
+            let value = output
+                .lines()
+                .last()
+                .and_then(|line| line.split(',').nth(1))
+                .expect("The temperature rise is missing");
+            Float::with_val(64, Float::parse(value).expect("Invalid temperature rise"))
+        };
+
+        let pulse = last(run(&arguments));
+
+        let end = simulation.times[1].to_string();
+        let mut arguments = arguments[..arguments.len() - 2].to_vec();
+        arguments.extend(["--end", &end]);
+        let continuous = last(run(&arguments));
+
+        assert!(pulse.is_sign_positive());
+        assert!(pulse < continuous / 10);
+    }
+
+    #[test]
+    fn quadrature() {
+        let simulation = [
+            "--rho",
+            "1",
+            "--c",
+            "4.178",
+            "--k",
+            "0.0063",
+            "--layer",
+            "0.001,0,1000,1",
+            "--beam",
+            "large",
+            "--z",
+            "0.0005",
+            "--start",
+            "0.001",
+            "--end",
+            "0.01",
+            "--steps",
+            "2",
+            "--epsilon",
+            "1e-9",
+        ];
+
+        let series = |quadrature: &str| {
+            let mut arguments = vec!["reedbed", "temperature-rise"];
+            arguments.extend(simulation);
+            arguments.extend(["--quadrature", quadrature]);
+
+            run(&arguments)
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let value = line
+                        .split(',')
+                        .nth(1)
+                        .expect("The temperature rise is missing");
+                    Float::with_val(64, Float::parse(value).expect("Invalid temperature rise"))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let gauss_kronrod = series("gauss-kronrod");
+        assert_eq!(gauss_kronrod.len(), 2);
+
+        for quadrature in ["tanh-sinh", "adaptive-simpson", "romberg"] {
+            for (value, reference) in series(quadrature).into_iter().zip(&gauss_kronrod) {
+                let mut error = value - reference;
+                error /= reference;
+                error.abs_mut();
+                assert!(error < 1e-9, "{quadrature} differs by {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn kernel() {
+        let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+
+        let output = run(&["reedbed", "kernel", "--config", config]);
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("time,kernel"));
 
-    let a = Float::with_val_64(64, 20.0);
-    let b = Float::with_val_64(64, 20.0);
+        let Commands::Kernel(command) =
+            Cli::try_parse_from(["reedbed", "kernel", "--config", config])
+                .expect("Unable to parse the arguments")
+                .command
+        else {
+            panic!("Parsed the wrong command");
+        };
+        let simulation = command
+            .simulation
+            .load(command.config.as_deref())
+            .expect("Unable to resolve the simulation");
 
-    println!("{:?}", utilities::marcum_q(1, &a, &b, 64));
+        let lines = lines.collect::<Vec<_>>();
+        assert_eq!(lines.len(), simulation.times.len());
 
-    Ok(())
+        for (line, time) in lines.into_iter().zip(&simulation.times) {
+            let expected = simulation.layers.evaluate_with(
+                simulation.precision,
+                &simulation.beam,
+                &simulation.thermal_properties,
+                &simulation.z,
+                &simulation.r,
+                time,
+            );
+            assert_eq!(line, format!("{time},{expected}"));
+        }
+    }
 }
@@ -0,0 +1,254 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use anyhow::{anyhow, Context};
+use clap::ValueEnum;
+use rug::Float;
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{BufWriter, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+/// The formats results may be written in
+#[derive(ValueEnum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Format {
+    Csv,
+    Json,
+
+    /// `.npy` arrays of `float64`s, written to files named after the output
+    /// path. Values are downcast to `f64`, losing any precision beyond it
+    Npy,
+}
+
+/// Writes a temperature rise series, as returned by
+/// `MultiLayer::temperature_rise_series`, alongside the times it was
+/// evaluated at
+///
+/// If `digits` is provided, values are rounded to that many significant
+/// decimal digits. Otherwise, every digit is written. If `with_error` is set,
+/// the approximate error of each value is written alongside it as `error`
+pub fn write_series(
+    format: Format,
+    times: &[Float],
+    series: &[(Float, Float)],
+    digits: Option<NonZeroUsize>,
+    with_error: bool,
+    output: &mut impl Write,
+) -> anyhow::Result<()> {
+    match format {
+        Format::Csv => {
+            write!(output, "time,temperature_rise")?;
+            if with_error {
+                write!(output, ",error")?;
+            }
+            writeln!(output)?;
+
+            for (time, (temperature_rise, error)) in times.iter().zip(series) {
+                write!(
+                    output,
+                    "{},{}",
+                    number(time, digits),
+                    number(temperature_rise, digits)
+                )?;
+                if with_error {
+                    write!(output, ",{}", number(error, digits))?;
+                }
+                writeln!(output)?;
+            }
+        }
+        Format::Json => {
+            write!(output, "[")?;
+
+            for (i, (time, (temperature_rise, error))) in times.iter().zip(series).enumerate() {
+                if i != 0 {
+                    write!(output, ",")?;
+                }
+
+                write!(
+                    output,
+                    "{{\"time\":{},\"temperature_rise\":{}",
+                    json_number(time, digits),
+                    json_number(temperature_rise, digits)
+                )?;
+                if with_error {
+                    write!(output, ",\"error\":{}", json_number(error, digits))?;
+                }
+                write!(output, "}}")?;
+            }
+
+            writeln!(output, "]")?;
+        }
+        Format::Npy => {
+            return Err(anyhow!(
+                "the npy format must be written to files named with `--output`"
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a temperature rise series alongside the times it was evaluated at as
+/// two `.npy` files, `<stem>.time.npy` and `<stem>.temperature_rise.npy`
+///
+/// If `with_error` is set, the approximate errors are written to a third,
+/// `<stem>.error.npy`
+pub fn write_series_npy(
+    stem: &Path,
+    times: &[Float],
+    series: &[(Float, Float)],
+    with_error: bool,
+) -> anyhow::Result<()> {
+    let (temperature_rises, errors): (Vec<_>, Vec<_>) = series.iter().cloned().unzip();
+
+    let mut arrays = vec![("time", times), ("temperature_rise", &temperature_rises)];
+    if with_error {
+        arrays.push(("error", &errors));
+    }
+
+    write_arrays_npy(stem, &arrays)
+}
+
+/// Writes each of `arrays` as a `.npy` file named `<stem>.<suffix>.npy`
+fn write_arrays_npy(stem: &Path, arrays: &[(&str, &[Float])]) -> anyhow::Result<()> {
+    for &(suffix, values) in arrays {
+        let mut path = OsString::from(stem);
+        path.push(format!(".{suffix}.npy"));
+        let path = PathBuf::from(path);
+
+        let mut file = BufWriter::new(
+            File::create(&path).with_context(|| format!("unable to create {path:?}"))?,
+        );
+        write_npy(values, &mut file)?;
+        file.flush()
+            .with_context(|| format!("unable to write to {path:?}"))?;
+    }
+
+    Ok(())
+}
+
+/// Writes the values of the kernel integrated over time for a temperature
+/// rise, as returned by `Simulation::kernel_at`, alongside the times it was
+/// evaluated at
+///
+/// `digits` is handled in the same way as in [`fn@write_series`]
+pub fn write_kernel(
+    format: Format,
+    times: &[Float],
+    kernel: &[Float],
+    digits: Option<NonZeroUsize>,
+    output: &mut impl Write,
+) -> anyhow::Result<()> {
+    match format {
+        Format::Csv => {
+            writeln!(output, "time,kernel")?;
+
+            for (time, value) in times.iter().zip(kernel) {
+                writeln!(output, "{},{}", number(time, digits), number(value, digits))?;
+            }
+        }
+        Format::Json => {
+            write!(output, "[")?;
+
+            for (i, (time, value)) in times.iter().zip(kernel).enumerate() {
+                if i != 0 {
+                    write!(output, ",")?;
+                }
+
+                write!(
+                    output,
+                    "{{\"time\":{},\"kernel\":{}}}",
+                    json_number(time, digits),
+                    json_number(value, digits)
+                )?;
+            }
+
+            writeln!(output, "]")?;
+        }
+        Format::Npy => {
+            return Err(anyhow!(
+                "the npy format must be written to files named with `--output`"
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the values of the kernel alongside the times it was evaluated at
+/// as two `.npy` files, `<stem>.time.npy` and `<stem>.kernel.npy`
+pub fn write_kernel_npy(stem: &Path, times: &[Float], kernel: &[Float]) -> anyhow::Result<()> {
+    write_arrays_npy(stem, &[("time", times), ("kernel", kernel)])
+}
+
+/// Writes a one-dimensional array in version 1.0 of the `.npy` format, as
+/// described at <https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html>
+pub fn write_npy(values: &[Float], output: &mut impl Write) -> anyhow::Result<()> {
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({},), }}",
+        values.len()
+    );
+
+    // the magic string, version, and header length take up 10 bytes, and the
+    // header is padded with spaces and a newline such that the data begins
+    // on a 64-byte boundary
+
+    let padding = (64 - (10 + header.len() + 1) % 64) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    output.write_all(b"\x93NUMPY\x01\x00")?;
+    output.write_all(&u16::try_from(header.len())?.to_le_bytes())?;
+    output.write_all(header.as_bytes())?;
+
+    for value in values {
+        output.write_all(&value.to_f64().to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Formats a value with `digits` significant decimal digits, or with every
+/// digit if `digits` is not provided
+fn number(value: &Float, digits: Option<NonZeroUsize>) -> String {
+    match digits {
+        Some(digits) => format!("{value:.*}", digits.get()),
+        None => value.to_string(),
+    }
+}
+
+/// JSON has no representation for non-finite numbers, so they are written as
+/// `null`
+fn json_number(value: &Float, digits: Option<NonZeroUsize>) -> String {
+    if value.is_finite() {
+        number(value, digits)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Writes values computed over a grid of points, as returned by
+/// `grid::evaluate`, as CSV
+///
+/// `digits` is handled in the same way as in [`fn@write_series`]
+pub fn write_grid(
+    values: &[(Float, Float, Float)],
+    digits: Option<NonZeroUsize>,
+    output: &mut impl Write,
+) -> anyhow::Result<()> {
+    writeln!(output, "z,r,value")?;
+
+    for (z, r, value) in values {
+        writeln!(
+            output,
+            "{},{},{}",
+            number(z, digits),
+            number(r, digits),
+            number(value, digits)
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use std::io::{self, IsTerminal, Write};
+
+/// Width of the bar, in characters
+const WIDTH: usize = 40;
+
+/// A progress bar drawn to stderr, so as to not interfere with results
+/// written to stdout
+///
+/// If stderr is not a terminal, nothing is drawn
+#[derive(Debug)]
+pub struct Progress {
+    total: usize,
+    enabled: bool,
+}
+
+impl Progress {
+    /// Creates a new [`struct@Progress`] for `total` items of work, which is
+    /// only drawn if `enabled` is set and stderr is a terminal
+    pub fn new(total: usize, enabled: bool) -> Self {
+        Self {
+            total,
+            enabled: enabled && io::stderr().is_terminal(),
+        }
+    }
+
+    /// Redraws the bar with `completed` items of work done
+    pub fn update(&self, completed: usize) {
+        if !self.enabled {
+            return;
+        }
+
+        let filled = (completed * WIDTH).checked_div(self.total).unwrap_or(WIDTH);
+
+        // failing to draw the progress bar is not worth aborting over
+        let _ = write!(
+            io::stderr().lock(),
+            "\r[{}{}] {completed}/{} time points",
+            "=".repeat(filled),
+            " ".repeat(WIDTH - filled),
+            self.total,
+        );
+    }
+
+    /// Moves past the bar, so that anything written to stderr afterward
+    /// doesn't overwrite it
+    pub fn finish(&self) {
+        if self.enabled {
+            let _ = writeln!(io::stderr().lock());
+        }
+    }
+}
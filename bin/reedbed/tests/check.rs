@@ -0,0 +1,46 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use std::process::{Command, Output};
+
+fn reedbed(arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_reedbed"))
+        .args(arguments)
+        .output()
+        .expect("Unable to run reedbed")
+}
+
+#[test]
+fn check_valid() {
+    let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+
+    let output = reedbed(&["temperature-rise", "--config", config, "--check"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("The output was not UTF-8");
+    assert!(stdout.contains("mu_a = 1000.00 cm^-1"));
+    assert!(stdout.contains("quadrature: gauss-kronrod"));
+    assert!(stdout.contains("beam: flat-top, radius = 1.00000e-2 cm"));
+    assert!(stdout.contains("times: 4 over 0..1.00000e-1 s"));
+}
+
+#[test]
+fn check_overlapping_layers() {
+    let config = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/simulation.yml");
+
+    let output = reedbed(&[
+        "temperature-rise",
+        "--config",
+        config,
+        "--layer",
+        "1,0,1,1",
+        "--layer",
+        "1,0.5,1,1",
+        "--check",
+    ]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let stderr = String::from_utf8(output.stderr).expect("The output was not UTF-8");
+    assert!(stderr.contains("unable to construct the layers"));
+    assert!(stderr.contains("overlaps the layer above it"));
+}
@@ -0,0 +1,92 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+const SIMULATION: [&str; 12] = [
+    "--rho", "1", "--c", "4.178", "--k", "0.0063", "--beam", "large", "--z", "0.0005", "--end",
+    "0.01",
+];
+
+fn reedbed(arguments: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_reedbed"))
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Unable to run reedbed");
+
+    child
+        .stdin
+        .take()
+        .expect("stdin was not piped")
+        .write_all(stdin.as_bytes())
+        .expect("Unable to write to stdin");
+
+    child.wait_with_output().expect("Unable to run reedbed")
+}
+
+#[test]
+fn layers_from_stdin() {
+    let config = std::env::temp_dir().join(format!("reedbed-layers-{}.yml", std::process::id()));
+    std::fs::write(
+        &config,
+        concat!(
+            "layers:\n",
+            "  - { d: \"0.001\", z0: \"0\", mu_a: \"1000\", e0: \"1\" }\n",
+            "  - { d: \"0.002\", z0: \"0.001\", mu_a: \"100\", e0: \"0\" }\n",
+            "  - { d: \"0.01\", z0: \"0.003\", mu_a: \"10\", e0: \"0\" }\n",
+        ),
+    )
+    .expect("Unable to write the configuration");
+
+    let mut arguments = vec!["temperature-rise"];
+    arguments.extend(SIMULATION);
+    arguments.extend([
+        "--config",
+        config
+            .to_str()
+            .expect("The temporary directory was not UTF-8"),
+    ]);
+    let from_config = reedbed(&arguments, "");
+    std::fs::remove_file(&config).expect("Unable to remove the configuration");
+
+    let mut arguments = vec!["temperature-rise"];
+    arguments.extend(SIMULATION);
+    arguments.extend(["--layers", "-"]);
+    let from_stdin = reedbed(
+        &arguments,
+        "0.001 0 1000 1\n\n0.002  0.001 100 0\n0.01\t0.003 10 0\n",
+    );
+
+    assert!(from_config.status.success());
+    assert!(from_stdin.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&from_config.stdout).lines().count(),
+        2
+    );
+    assert_eq!(from_stdin.stdout, from_config.stdout);
+}
+
+#[test]
+fn layers_from_stdin_invalid() {
+    let mut arguments = vec!["temperature-rise"];
+    arguments.extend(SIMULATION);
+    arguments.extend(["--layers", "-"]);
+
+    let output = reedbed(&arguments, "0.001 0 1000 1\n0.002 0.001 x 0\n");
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).expect("The output was not UTF-8");
+    assert!(stderr.contains("invalid layer on line 2"));
+    assert!(stderr.contains("invalid value for `mu_a`: `x`"));
+
+    let output = reedbed(&arguments, "0.001 0 1000\n");
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).expect("The output was not UTF-8");
+    assert!(stderr.contains("invalid layer on line 1"));
+}
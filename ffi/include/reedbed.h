@@ -0,0 +1,85 @@
+/* SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later */
+
+/*
+ * C bindings for reedbed
+ *
+ * all structures are exposed as opaque handles. every handle returned by a
+ * reedbed_*_new function is owned by the caller and must be released with the
+ * matching reedbed_*_free function exactly once. functions taking handles as
+ * arguments only borrow them, so the caller retains ownership. passing null to
+ * a reedbed_*_free function does nothing
+ *
+ * precision is the number of bits used for MPFR floats internally, and must
+ * be within the range MPFR supports (at least 1). values are downcast to
+ * doubles when returned
+ */
+
+#ifndef REEDBED_H
+#define REEDBED_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* status codes */
+#define REEDBED_OK 0
+#define REEDBED_NULL_POINTER 1
+#define REEDBED_OVERLAP 2
+#define REEDBED_INVALID_BOUNDS 3
+#define REEDBED_UNKNOWN_BEAM 4
+#define REEDBED_INVALID_BEAM 5
+#define REEDBED_INVALID_PRECISION 6
+
+/* beams */
+#define REEDBED_BEAM_LARGE 0
+#define REEDBED_BEAM_FLAT_TOP 1
+
+typedef struct ReedbedThermalProperties ReedbedThermalProperties;
+typedef struct ReedbedLayer ReedbedLayer;
+typedef struct ReedbedMultiLayer ReedbedMultiLayer;
+
+/*
+ * creates thermal properties from an array of { rho, c, k }. returns null if
+ * properties is null, precision is invalid, or any of rho, c, and k is not
+ * positive and finite
+ */
+ReedbedThermalProperties *reedbed_thermal_properties_new(uint64_t precision,
+                                                         const double *properties);
+void reedbed_thermal_properties_free(ReedbedThermalProperties *thermal_properties);
+
+/*
+ * creates a layer from an array of { d, z0, mu_a, e0 }. returns null if layer
+ * is null or precision is invalid
+ */
+ReedbedLayer *reedbed_layer_new(uint64_t precision, const double *layer);
+void reedbed_layer_free(ReedbedLayer *layer);
+
+/*
+ * creates a multi-layer from count layers, writing it to *multi_layer. the
+ * layers are copied, so they may be released afterward. returns
+ * REEDBED_OVERLAP, writing nothing, if any of the layers overlap
+ */
+int reedbed_multi_layer_new(const ReedbedLayer *const *layers, size_t count,
+                            ReedbedMultiLayer **multi_layer);
+void reedbed_multi_layer_free(ReedbedMultiLayer *multi_layer);
+
+/*
+ * calculates the temperature rise at the point (z, r) over the interval a..b,
+ * writing it to *result. radius is only used by beams which have one. returns
+ * REEDBED_INVALID_BOUNDS if a is not finite, b is nan, or a > b,
+ * REEDBED_INVALID_BEAM if the beam has a radius which is not positive, and
+ * REEDBED_INVALID_PRECISION if precision is invalid
+ */
+int reedbed_temperature_rise(uint64_t precision, const ReedbedMultiLayer *multi_layer,
+                             const ReedbedThermalProperties *thermal_properties, int beam,
+                             double radius, double z, double r, double a, double b,
+                             double epsilon, double *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,447 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+#![allow(clippy::cognitive_complexity)]
+#![warn(clippy::cargo_common_metadata)]
+#![warn(clippy::dbg_macro)]
+#![warn(clippy::explicit_deref_methods)]
+#![warn(clippy::filetype_is_file)]
+#![warn(clippy::imprecise_flops)]
+#![warn(clippy::large_stack_arrays)]
+#![warn(clippy::todo)]
+#![warn(clippy::unimplemented)]
+#![deny(clippy::await_holding_lock)]
+#![deny(clippy::cast_lossless)]
+#![deny(clippy::clone_on_ref_ptr)]
+#![deny(clippy::doc_markdown)]
+#![deny(clippy::empty_enums)]
+#![deny(clippy::enum_glob_use)]
+#![deny(clippy::exit)]
+#![deny(clippy::explicit_into_iter_loop)]
+#![deny(clippy::explicit_iter_loop)]
+#![deny(clippy::fallible_impl_from)]
+#![deny(clippy::inefficient_to_string)]
+#![deny(clippy::large_digit_groups)]
+#![deny(clippy::wildcard_dependencies)]
+#![deny(clippy::wildcard_imports)]
+#![deny(clippy::unused_self)]
+#![deny(clippy::single_match_else)]
+#![deny(clippy::option_option)]
+#![deny(clippy::mut_mut)]
+
+//! C bindings for `reedbed_lib`
+//!
+//! All structures are exposed as opaque handles. Every handle returned by a
+//! `reedbed_*_new` function is owned by the caller and must be released with
+//! the matching `reedbed_*_free` function exactly once. Functions taking
+//! handles as arguments only borrow them, so the caller retains ownership.
+//! The declarations for C are in `include/reedbed.h`
+//!
+//! If the `python` feature is enabled, the library may also be loaded as the
+//! `reedbed` Python module. See [`mod@python`]
+
+use rug::{float, Float};
+use std::{os::raw::c_int, ptr, slice};
+
+use reedbed_lib::{
+    greens::{FlatTopBeam, LargeBeam, Layer, MultiLayer, ThermalProperties},
+    quadrature::{GaussKronrod, G7_K15},
+};
+
+#[cfg(feature = "python")]
+pub mod python;
+
+/// The operation succeeded
+pub const REEDBED_OK: c_int = 0;
+
+/// A required pointer was null
+pub const REEDBED_NULL_POINTER: c_int = 1;
+
+/// The layers passed to `reedbed_multi_layer_new` overlap
+pub const REEDBED_OVERLAP: c_int = 2;
+
+/// The bounds passed to `reedbed_temperature_rise` are invalid
+pub const REEDBED_INVALID_BOUNDS: c_int = 3;
+
+/// The beam passed to `reedbed_temperature_rise` is unknown
+pub const REEDBED_UNKNOWN_BEAM: c_int = 4;
+
+/// The radius passed to `reedbed_temperature_rise` is not positive
+pub const REEDBED_INVALID_BEAM: c_int = 5;
+
+/// The precision passed to a function is outside of the range MPFR supports
+pub const REEDBED_INVALID_PRECISION: c_int = 6;
+
+/// Selects a `LargeBeam`
+pub const REEDBED_BEAM_LARGE: c_int = 0;
+
+/// Selects a `FlatTopBeam`
+pub const REEDBED_BEAM_FLAT_TOP: c_int = 1;
+
+/// Whether MPFR supports floats with `precision` bits
+fn is_valid_precision(precision: u64) -> bool {
+    (u64::from(float::prec_min())..=u64::from(float::prec_max())).contains(&precision)
+}
+
+/// An opaque handle to a `ThermalProperties`
+#[derive(Debug)]
+pub struct ReedbedThermalProperties(ThermalProperties<'static>);
+
+/// An opaque handle to a `Layer`
+#[derive(Debug)]
+pub struct ReedbedLayer(Layer<'static>);
+
+/// An opaque handle to a `MultiLayer`
+#[derive(Debug)]
+pub struct ReedbedMultiLayer(MultiLayer<'static>);
+
+/// Creates a new `ThermalProperties` from an array of `rho`, `c`, and `k`, at
+/// the given precision
+///
+/// Returns null if `properties` is null, if `precision` is outside of the
+/// range MPFR supports, or if the properties are invalid (see
+/// `ThermalProperties::validate`)
+///
+/// # Safety
+///
+/// `properties` must be null or point to at least 3 `double`s
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_thermal_properties_new(
+    precision: u64,
+    properties: *const f64,
+) -> *mut ReedbedThermalProperties {
+    if properties.is_null() || !is_valid_precision(precision) {
+        return ptr::null_mut();
+    }
+
+    let [rho, c, k] = *properties.cast::<[f64; 3]>();
+    let thermal_properties = ThermalProperties::from_f64(precision, rho, c, k);
+
+    if thermal_properties.validate().is_err() {
+        return ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(ReedbedThermalProperties(thermal_properties)))
+}
+
+/// Releases a `ThermalProperties` created by `reedbed_thermal_properties_new`
+///
+/// # Safety
+///
+/// `thermal_properties` must be null or a handle returned by
+/// `reedbed_thermal_properties_new` which has not yet been released
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_thermal_properties_free(
+    thermal_properties: *mut ReedbedThermalProperties,
+) {
+    if !thermal_properties.is_null() {
+        drop(Box::from_raw(thermal_properties));
+    }
+}
+
+/// Creates a new `Layer` from an array of `d`, `z0`, `mu_a`, and `e0`, at the
+/// given precision
+///
+/// Returns null if `layer` is null or `precision` is outside of the range
+/// MPFR supports
+///
+/// # Safety
+///
+/// `layer` must be null or point to at least 4 `double`s
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_layer_new(precision: u64, layer: *const f64) -> *mut ReedbedLayer {
+    if layer.is_null() || !is_valid_precision(precision) {
+        return ptr::null_mut();
+    }
+
+    let [d, z0, mu_a, e0] = *layer.cast::<[f64; 4]>();
+
+    Box::into_raw(Box::new(ReedbedLayer(Layer::from_f64(
+        precision, d, z0, mu_a, e0,
+    ))))
+}
+
+/// Releases a `Layer` created by `reedbed_layer_new`
+///
+/// # Safety
+///
+/// `layer` must be null or a handle returned by `reedbed_layer_new` which has
+/// not yet been released
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_layer_free(layer: *mut ReedbedLayer) {
+    if !layer.is_null() {
+        drop(Box::from_raw(layer));
+    }
+}
+
+/// Creates a new `MultiLayer` from an array of `count` `Layer`s, writing it to
+/// `multi_layer`
+///
+/// The layers are copied, so they may be released afterward. If any of the
+/// layers overlap, `REEDBED_OVERLAP` is returned and nothing is written
+///
+/// # Safety
+///
+/// `layers` must point to `count` valid `Layer` handles, and `multi_layer`
+/// must be null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_multi_layer_new(
+    layers: *const *const ReedbedLayer,
+    count: usize,
+    multi_layer: *mut *mut ReedbedMultiLayer,
+) -> c_int {
+    if (layers.is_null() && count != 0) || multi_layer.is_null() {
+        return REEDBED_NULL_POINTER;
+    }
+
+    let handles = if count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(layers, count)
+    };
+
+    if handles.iter().any(|handle| handle.is_null()) {
+        return REEDBED_NULL_POINTER;
+    }
+
+    match MultiLayer::new(handles.iter().map(|handle| (**handle).0.clone())) {
+        Ok(layers) => {
+            *multi_layer = Box::into_raw(Box::new(ReedbedMultiLayer(layers)));
+            REEDBED_OK
+        }
+        Err(_) => REEDBED_OVERLAP,
+    }
+}
+
+/// Releases a `MultiLayer` created by `reedbed_multi_layer_new`
+///
+/// # Safety
+///
+/// `multi_layer` must be null or a handle written by
+/// `reedbed_multi_layer_new` which has not yet been released
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_multi_layer_free(multi_layer: *mut ReedbedMultiLayer) {
+    if !multi_layer.is_null() {
+        drop(Box::from_raw(multi_layer));
+    }
+}
+
+/// Calculates the temperature rise at the point (z, r) over the interval a..b,
+/// writing it to `result`
+///
+/// `beam` is one of the `REEDBED_BEAM_*` constants, and `radius` is only used
+/// by beams which have one. Integration is done with Gauss-Kronrod quadrature
+/// at the given precision, and the result is downcast to a `double`. If the
+/// precision is outside of the range MPFR supports,
+/// `REEDBED_INVALID_PRECISION` is returned
+///
+/// # Safety
+///
+/// `multi_layer` and `thermal_properties` must be null or valid handles, and
+/// `result` must be null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn reedbed_temperature_rise(
+    precision: u64,
+    multi_layer: *const ReedbedMultiLayer,
+    thermal_properties: *const ReedbedThermalProperties,
+    beam: c_int,
+    radius: f64,
+    z: f64,
+    r: f64,
+    a: f64,
+    b: f64,
+    epsilon: f64,
+    result: *mut f64,
+) -> c_int {
+    if multi_layer.is_null() || thermal_properties.is_null() || result.is_null() {
+        return REEDBED_NULL_POINTER;
+    }
+
+    if !is_valid_precision(precision) {
+        return REEDBED_INVALID_PRECISION;
+    }
+
+    let multi_layer = &(*multi_layer).0;
+    let thermal_properties = &(*thermal_properties).0;
+
+    let float = |value| Float::with_val_64(precision, value);
+    let quadrature = GaussKronrod {
+        interval_limit: 1024,
+        precision,
+        rule: &G7_K15,
+    };
+
+    let (z, r, a, b, epsilon) = (float(z), float(r), float(a), float(b), float(epsilon));
+
+    let temperature_rise = match beam {
+        REEDBED_BEAM_LARGE => multi_layer.temperature_rise(
+            precision,
+            &quadrature,
+            &LargeBeam,
+            thermal_properties,
+            &z,
+            &r,
+            &epsilon,
+            (&a, &b),
+        ),
+        REEDBED_BEAM_FLAT_TOP => multi_layer.temperature_rise(
+            precision,
+            &quadrature,
+            &match FlatTopBeam::new(&float(radius)) {
+                Ok(beam) => beam,
+                Err(_) => return REEDBED_INVALID_BEAM,
+            },
+            thermal_properties,
+            &z,
+            &r,
+            &epsilon,
+            (&a, &b),
+        ),
+        _ => return REEDBED_UNKNOWN_BEAM,
+    };
+
+    match temperature_rise {
+        Ok((temperature_rise, _)) => {
+            *result = temperature_rise.to_f64();
+            REEDBED_OK
+        }
+        Err(_) => REEDBED_INVALID_BOUNDS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reedbed_lib::greens;
+
+    #[test]
+    fn round_trip() {
+        unsafe {
+            let thermal_properties = reedbed_thermal_properties_new(64, [1.0, 1.0, 1.0].as_ptr());
+            let layers = [
+                reedbed_layer_new(64, [1.0, 0.0, 1.0, 1.0].as_ptr()),
+                reedbed_layer_new(64, [1.0, 0.5, 1.0, 0.0].as_ptr()),
+            ];
+
+            let mut multi_layer = ptr::null_mut();
+            assert_eq!(
+                reedbed_multi_layer_new(layers.as_ptr().cast(), 2, &mut multi_layer),
+                REEDBED_OVERLAP
+            );
+            assert!(multi_layer.is_null());
+            assert_eq!(
+                reedbed_multi_layer_new(layers.as_ptr().cast(), 1, &mut multi_layer),
+                REEDBED_OK
+            );
+
+            for layer in layers {
+                reedbed_layer_free(layer);
+            }
+
+            let mut result = 0.0;
+            assert_eq!(
+                reedbed_temperature_rise(
+                    64,
+                    multi_layer,
+                    thermal_properties,
+                    REEDBED_BEAM_LARGE,
+                    0.0,
+                    1.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    1e-9,
+                    &mut result
+                ),
+                REEDBED_OK
+            );
+
+            let expected = greens::temperature_rise(
+                64,
+                &GaussKronrod {
+                    interval_limit: 1024,
+                    precision: 64,
+                    rule: &G7_K15,
+                },
+                &LargeBeam,
+                &ThermalProperties::from_f64(64, 1.0, 1.0, 1.0),
+                &Layer::from_f64(64, 1.0, 0.0, 1.0, 1.0),
+                &Float::with_val_64(64, 1.0),
+                &Float::with_val_64(64, 0.0),
+                &Float::with_val_64(64, 1e-9),
+                (&Float::with_val_64(64, 0.0), &Float::with_val_64(64, 1.0)),
+            )
+            .expect("Invalid bounds")
+            .0;
+            assert_eq!(result, expected.to_f64());
+
+            assert_eq!(
+                reedbed_temperature_rise(
+                    64,
+                    multi_layer,
+                    thermal_properties,
+                    REEDBED_BEAM_LARGE,
+                    0.0,
+                    1.0,
+                    0.0,
+                    1.0,
+                    0.0,
+                    1e-9,
+                    &mut result
+                ),
+                REEDBED_INVALID_BOUNDS
+            );
+            assert_eq!(
+                reedbed_temperature_rise(
+                    64,
+                    ptr::null(),
+                    thermal_properties,
+                    REEDBED_BEAM_LARGE,
+                    0.0,
+                    1.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    1e-9,
+                    &mut result
+                ),
+                REEDBED_NULL_POINTER
+            );
+
+            // precisions MPFR doesn't support are rejected rather than
+            // panicking across the boundary
+
+            for precision in [0, u64::MAX] {
+                assert!(
+                    reedbed_thermal_properties_new(precision, [1.0, 1.0, 1.0].as_ptr()).is_null()
+                );
+                assert!(reedbed_layer_new(precision, [1.0, 0.0, 1.0, 1.0].as_ptr()).is_null());
+                assert_eq!(
+                    reedbed_temperature_rise(
+                        precision,
+                        multi_layer,
+                        thermal_properties,
+                        REEDBED_BEAM_LARGE,
+                        0.0,
+                        1.0,
+                        0.0,
+                        0.0,
+                        1.0,
+                        1e-9,
+                        &mut result
+                    ),
+                    REEDBED_INVALID_PRECISION
+                );
+            }
+
+            // as are nonphysical thermal properties
+
+            for properties in [[1.0, 1.0, -1.0], [0.0, 1.0, 1.0], [1.0, f64::NAN, 1.0]] {
+                assert!(reedbed_thermal_properties_new(64, properties.as_ptr()).is_null());
+            }
+
+            reedbed_multi_layer_free(multi_layer);
+            reedbed_thermal_properties_free(thermal_properties);
+        }
+    }
+}
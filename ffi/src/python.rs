@@ -0,0 +1,391 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! Python bindings for `reedbed_lib`, built as the `reedbed` module
+//!
+//! Values are accepted and returned as Python floats, which are converted to
+//! and from MPFR floats at the precision given by the `precision` keyword
+//! argument (128 bits by default)
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+use rug::Float;
+
+use reedbed_lib::{
+    greens::{
+        self, FlatTopBeam, LaguerreGaussianBeam, LargeBeam, Layer, MultiLayer, ThermalProperties,
+    },
+    quadrature::{GaussKronrod, G7_K15},
+};
+
+create_exception!(reedbed, MultiLayerError, PyValueError);
+create_exception!(reedbed, BoundsError, PyValueError);
+create_exception!(reedbed, BeamError, PyValueError);
+
+/// The default precision (in bits) for MPFR floats
+const PRECISION: u64 = 128;
+
+/// The `Quadrature` implementation used to integrate over time
+fn quadrature(precision: u64) -> GaussKronrod<'static> {
+    GaussKronrod {
+        interval_limit: 1024,
+        precision,
+        rule: &G7_K15,
+    }
+}
+
+fn bounds_error(error: greens::BoundsError) -> PyErr {
+    BoundsError::new_err(error.to_string())
+}
+
+fn beam_error(error: greens::BeamError) -> PyErr {
+    BeamError::new_err(error.to_string())
+}
+
+/// Raises a `ValueError` if MPFR doesn't support floats with `precision` bits
+fn check_precision(precision: u64) -> PyResult<()> {
+    if crate::is_valid_precision(precision) {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(format!(
+            "a precision of {precision} bits is not supported"
+        )))
+    }
+}
+
+#[pyclass(name = "ThermalProperties", module = "reedbed")]
+#[derive(Clone)]
+struct PyThermalProperties(ThermalProperties<'static>);
+
+#[pymethods]
+impl PyThermalProperties {
+    #[new]
+    #[pyo3(signature = (rho, c, k, *, precision = PRECISION))]
+    fn new(rho: f64, c: f64, k: f64, precision: u64) -> PyResult<Self> {
+        check_precision(precision)?;
+
+        let thermal_properties = ThermalProperties::from_f64(precision, rho, c, k);
+        thermal_properties
+            .validate()
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+        Ok(Self(thermal_properties))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[pyclass(name = "Layer", module = "reedbed")]
+#[derive(Clone)]
+struct PyLayer(Layer<'static>);
+
+#[pymethods]
+impl PyLayer {
+    #[new]
+    #[pyo3(signature = (d, z0, mu_a, e0, *, precision = PRECISION))]
+    fn new(d: f64, z0: f64, mu_a: f64, e0: f64, precision: u64) -> PyResult<Self> {
+        check_precision(precision)?;
+
+        Ok(Self(Layer::from_f64(precision, d, z0, mu_a, e0)))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[pyclass(name = "LargeBeam", module = "reedbed")]
+#[derive(Clone)]
+struct PyLargeBeam;
+
+#[pyclass(name = "FlatTopBeam", module = "reedbed")]
+#[derive(Clone)]
+struct PyFlatTopBeam(FlatTopBeam<'static>);
+
+#[pyclass(name = "LaguerreGaussianBeam", module = "reedbed")]
+#[derive(Clone)]
+struct PyLaguerreGaussianBeam(LaguerreGaussianBeam<'static, GaussKronrod<'static>>);
+
+#[pymethods]
+impl PyLargeBeam {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+}
+
+#[pymethods]
+impl PyFlatTopBeam {
+    #[new]
+    #[pyo3(signature = (radius, *, precision = PRECISION))]
+    fn new(radius: f64, precision: u64) -> PyResult<Self> {
+        check_precision(precision)?;
+
+        FlatTopBeam::new(&Float::with_val_64(precision, radius))
+            .map(Self)
+            .map_err(beam_error)
+    }
+}
+
+#[pymethods]
+impl PyLaguerreGaussianBeam {
+    #[new]
+    #[pyo3(signature = (radius, p, l, *, epsilon = 1e-9, precision = PRECISION))]
+    fn new(radius: f64, p: u32, l: i32, epsilon: f64, precision: u64) -> PyResult<Self> {
+        check_precision(precision)?;
+
+        LaguerreGaussianBeam::new(
+            &Float::with_val_64(precision, radius),
+            p,
+            l,
+            quadrature(precision),
+            &Float::with_val_64(precision, epsilon),
+        )
+        .map(Self)
+        .map_err(beam_error)
+    }
+}
+
+/// Any of the beams exposed to Python
+#[derive(FromPyObject)]
+enum AnyBeam {
+    Large(PyLargeBeam),
+    FlatTop(PyFlatTopBeam),
+    LaguerreGaussian(PyLaguerreGaussianBeam),
+}
+
+impl greens::Beam for AnyBeam {
+    fn evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        match self {
+            Self::Large(_) => {
+                LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            }
+            Self::FlatTop(beam) => {
+                beam.0
+                    .evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            }
+            Self::LaguerreGaussian(beam) => {
+                beam.0
+                    .evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            }
+        }
+    }
+}
+
+#[pyclass(name = "MultiLayer", module = "reedbed")]
+struct PyMultiLayer(MultiLayer<'static>);
+
+#[pymethods]
+impl PyMultiLayer {
+    #[new]
+    fn new(layers: Vec<PyLayer>) -> PyResult<Self> {
+        MultiLayer::new(layers.into_iter().map(|layer| layer.0))
+            .map(Self)
+            .map_err(|error| MultiLayerError::new_err(error.to_string()))
+    }
+
+    #[pyo3(signature = (beam, thermal_properties, z, r, a, b, *, epsilon = 1e-9, precision = PRECISION))]
+    #[allow(clippy::too_many_arguments)]
+    fn temperature_rise(
+        &self,
+        beam: AnyBeam,
+        thermal_properties: PyThermalProperties,
+        z: f64,
+        r: f64,
+        a: f64,
+        b: f64,
+        epsilon: f64,
+        precision: u64,
+    ) -> PyResult<(f64, f64)> {
+        check_precision(precision)?;
+
+        let float = |value| Float::with_val_64(precision, value);
+
+        let (temperature_rise, error) = self
+            .0
+            .temperature_rise(
+                precision,
+                &quadrature(precision),
+                &beam,
+                &thermal_properties.0,
+                &float(z),
+                &float(r),
+                &float(epsilon),
+                (&float(a), &float(b)),
+            )
+            .map_err(bounds_error)?;
+
+        Ok((temperature_rise.to_f64(), error.to_f64()))
+    }
+
+    #[pyo3(signature = (beam, thermal_properties, z, r, start, times, *, epsilon = 1e-9, precision = PRECISION))]
+    #[allow(clippy::too_many_arguments)]
+    fn temperature_rise_series(
+        &self,
+        beam: AnyBeam,
+        thermal_properties: PyThermalProperties,
+        z: f64,
+        r: f64,
+        start: f64,
+        times: Vec<f64>,
+        epsilon: f64,
+        precision: u64,
+    ) -> PyResult<Vec<(f64, f64)>> {
+        check_precision(precision)?;
+
+        let float = |value| Float::with_val_64(precision, value);
+        let times = times.into_iter().map(float).collect::<Vec<_>>();
+
+        Ok(self
+            .0
+            .temperature_rise_series(
+                precision,
+                &quadrature(precision),
+                &beam,
+                &thermal_properties.0,
+                &float(z),
+                &float(r),
+                &float(epsilon),
+                &float(start),
+                &times,
+            )
+            .into_iter()
+            .map(|(temperature_rise, error)| (temperature_rise.to_f64(), error.to_f64()))
+            .collect())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Runs a beam over a single layer at the time `tp`
+#[pyfunction]
+#[pyo3(signature = (beam, thermal_properties, layer, z, r, tp, *, precision = PRECISION))]
+fn evaluate_with(
+    beam: AnyBeam,
+    thermal_properties: PyThermalProperties,
+    layer: PyLayer,
+    z: f64,
+    r: f64,
+    tp: f64,
+    precision: u64,
+) -> PyResult<f64> {
+    check_precision(precision)?;
+
+    let float = |value| Float::with_val_64(precision, value);
+
+    Ok(greens::Beam::evaluate_with(
+        &beam,
+        precision,
+        &thermal_properties.0,
+        &layer.0,
+        &float(z),
+        &float(r),
+        &float(tp),
+    )
+    .to_f64())
+}
+
+/// Calculates the temperature rise over the interval a..b, returning it
+/// alongside the approximate error
+#[pyfunction]
+#[pyo3(signature = (beam, thermal_properties, layer, z, r, a, b, *, epsilon = 1e-9, precision = PRECISION))]
+#[allow(clippy::too_many_arguments)]
+fn temperature_rise(
+    beam: AnyBeam,
+    thermal_properties: PyThermalProperties,
+    layer: PyLayer,
+    z: f64,
+    r: f64,
+    a: f64,
+    b: f64,
+    epsilon: f64,
+    precision: u64,
+) -> PyResult<(f64, f64)> {
+    check_precision(precision)?;
+
+    let float = |value| Float::with_val_64(precision, value);
+
+    let (temperature_rise, error) = greens::temperature_rise(
+        precision,
+        &quadrature(precision),
+        &beam,
+        &thermal_properties.0,
+        &layer.0,
+        &float(z),
+        &float(r),
+        &float(epsilon),
+        (&float(a), &float(b)),
+    )
+    .map_err(bounds_error)?;
+
+    Ok((temperature_rise.to_f64(), error.to_f64()))
+}
+
+/// Calculates the temperature rise at each of the provided times, with
+/// integration beginning at `start`, as a list of pairs of the temperature
+/// rise and approximate error
+#[pyfunction]
+#[pyo3(signature = (beam, thermal_properties, layer, z, r, start, times, *, epsilon = 1e-9, precision = PRECISION))]
+#[allow(clippy::too_many_arguments)]
+fn temperature_rise_series(
+    beam: AnyBeam,
+    thermal_properties: PyThermalProperties,
+    layer: PyLayer,
+    z: f64,
+    r: f64,
+    start: f64,
+    times: Vec<f64>,
+    epsilon: f64,
+    precision: u64,
+) -> PyResult<Vec<(f64, f64)>> {
+    check_precision(precision)?;
+
+    let float = |value| Float::with_val_64(precision, value);
+    let times = times.into_iter().map(float).collect::<Vec<_>>();
+
+    Ok(greens::temperature_rise_series(
+        precision,
+        &quadrature(precision),
+        &beam,
+        &thermal_properties.0,
+        &layer.0,
+        &float(z),
+        &float(r),
+        &float(epsilon),
+        &float(start),
+        &times,
+    )
+    .into_iter()
+    .map(|(temperature_rise, error)| (temperature_rise.to_f64(), error.to_f64()))
+    .collect())
+}
+
+#[pymodule]
+fn reedbed(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyThermalProperties>()?;
+    module.add_class::<PyLayer>()?;
+    module.add_class::<PyMultiLayer>()?;
+    module.add_class::<PyLargeBeam>()?;
+    module.add_class::<PyFlatTopBeam>()?;
+    module.add_class::<PyLaguerreGaussianBeam>()?;
+
+    module.add("MultiLayerError", module.py().get_type::<MultiLayerError>())?;
+    module.add("BoundsError", module.py().get_type::<BoundsError>())?;
+    module.add("BeamError", module.py().get_type::<BeamError>())?;
+
+    module.add_function(wrap_pyfunction!(evaluate_with, module)?)?;
+    module.add_function(wrap_pyfunction!(temperature_rise, module)?)?;
+    module.add_function(wrap_pyfunction!(temperature_rise_series, module)?)?;
+
+    Ok(())
+}
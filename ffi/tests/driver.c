@@ -0,0 +1,66 @@
+/* SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later */
+
+/*
+ * a small test driver for the C bindings. from the root of the repository:
+ *
+ *   cargo build -p reedbed-ffi --release
+ *   cc ffi/tests/driver.c -Iffi/include -Ltarget/release -lreedbed -o driver
+ *   LD_LIBRARY_PATH=target/release ./driver
+ */
+
+#include <math.h>
+#include <stdio.h>
+
+#include "reedbed.h"
+
+#define CHECK(condition)                                                       \
+    do {                                                                       \
+        if (!(condition)) {                                                    \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+                    #condition);                                               \
+            return 1;                                                          \
+        }                                                                      \
+    } while (0)
+
+int main(void) {
+    const double properties[] = {1, 4.178, 0.0063};
+    const double top[] = {0.001, 0, 1000, 1};
+    const double bottom[] = {0.01, 0.001, 10, 0};
+    const double overlapping[] = {0.01, 0.0005, 10, 0};
+
+    ReedbedThermalProperties *thermal_properties =
+        reedbed_thermal_properties_new(64, properties);
+    ReedbedLayer *layers[] = {
+        reedbed_layer_new(64, top),
+        reedbed_layer_new(64, bottom),
+        reedbed_layer_new(64, overlapping),
+    };
+    CHECK(thermal_properties && layers[0] && layers[1] && layers[2]);
+
+    ReedbedMultiLayer *multi_layer = NULL;
+    CHECK(reedbed_multi_layer_new((const ReedbedLayer *const *)layers, 3, &multi_layer) ==
+          REEDBED_OVERLAP);
+    CHECK(multi_layer == NULL);
+    CHECK(reedbed_multi_layer_new((const ReedbedLayer *const *)layers, 2, &multi_layer) ==
+          REEDBED_OK);
+
+    for (size_t i = 0; i < 3; i++) {
+        reedbed_layer_free(layers[i]);
+    }
+
+    double result = 0;
+    CHECK(reedbed_temperature_rise(64, multi_layer, thermal_properties, REEDBED_BEAM_LARGE, 0,
+                                   0.0005, 0, 0, 0.1, 1e-9, &result) == REEDBED_OK);
+    CHECK(isfinite(result) && result > 0);
+    printf("temperature rise: %g K\n", result);
+
+    CHECK(reedbed_temperature_rise(64, multi_layer, thermal_properties, REEDBED_BEAM_LARGE, 0,
+                                   0.0005, 0, 0.1, 0, 1e-9, &result) == REEDBED_INVALID_BOUNDS);
+    CHECK(reedbed_temperature_rise(64, multi_layer, thermal_properties, 42, 0, 0.0005, 0, 0, 0.1,
+                                   1e-9, &result) == REEDBED_UNKNOWN_BEAM);
+
+    reedbed_multi_layer_free(multi_layer);
+    reedbed_thermal_properties_free(thermal_properties);
+
+    return 0;
+}
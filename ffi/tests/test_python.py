@@ -0,0 +1,77 @@
+# SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+import pytest
+
+import reedbed
+
+
+def test_large_beam_sanity():
+    thermal_properties = reedbed.ThermalProperties(1.0, 1.0, 1.0)
+    layer = reedbed.Layer(1.0, 0.0, 1.0, 1.0)
+    beam = reedbed.LargeBeam()
+
+    assert reedbed.evaluate_with(
+        beam, thermal_properties, layer, 1.0, 0.0, 1.0
+    ) == pytest.approx(1.6110045756833416583e-1, rel=1e-15)
+    assert reedbed.evaluate_with(beam, thermal_properties, layer, 0.0, 0.0, 0.0) == 0.5
+
+
+def test_temperature_rise():
+    thermal_properties = reedbed.ThermalProperties(1.0, 4.178, 0.0063)
+    layer = reedbed.Layer(0.01, 0.0, 100.0, 1.0)
+    beam = reedbed.LargeBeam()
+
+    temperature_rise, _ = reedbed.temperature_rise(
+        beam, thermal_properties, layer, 0.0, 0.0, 0.0, 0.05, precision=64
+    )
+    series = reedbed.temperature_rise_series(
+        beam, thermal_properties, layer, 0.0, 0.0, 0.0, [0.05], precision=64
+    )
+    assert series[0][0] == pytest.approx(temperature_rise)
+
+    multi_layer = reedbed.MultiLayer([layer])
+    assert multi_layer.temperature_rise(
+        beam, thermal_properties, 0.0, 0.0, 0.0, 0.05, precision=64
+    )[0] == pytest.approx(temperature_rise)
+
+
+def test_errors():
+    with pytest.raises(reedbed.MultiLayerError):
+        reedbed.MultiLayer(
+            [reedbed.Layer(1.0, 0.0, 1.0, 1.0), reedbed.Layer(1.0, 0.5, 1.0, 1.0)]
+        )
+
+    with pytest.raises(reedbed.BoundsError):
+        reedbed.temperature_rise(
+            reedbed.LargeBeam(),
+            reedbed.ThermalProperties(1.0, 1.0, 1.0),
+            reedbed.Layer(1.0, 0.0, 1.0, 1.0),
+            0.0,
+            0.0,
+            1.0,
+            0.0,
+        )
+
+    for radius in [0.0, -1.0]:
+        with pytest.raises(reedbed.BeamError):
+            reedbed.FlatTopBeam(radius)
+
+    for precision in [0, 2**64 - 1]:
+        with pytest.raises(ValueError):
+            reedbed.ThermalProperties(1.0, 1.0, 1.0, precision=precision)
+
+        with pytest.raises(ValueError):
+            reedbed.temperature_rise(
+                reedbed.LargeBeam(),
+                reedbed.ThermalProperties(1.0, 1.0, 1.0),
+                reedbed.Layer(1.0, 0.0, 1.0, 1.0),
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+                precision=precision,
+            )
+
+    for properties in [(1.0, 1.0, -1.0), (0.0, 1.0, 1.0)]:
+        with pytest.raises(ValueError):
+            reedbed.ThermalProperties(*properties)
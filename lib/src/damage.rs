@@ -0,0 +1,388 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! Thermal damage accumulated over an exposure, and the irradiance needed to
+//! reach a given amount of it
+//!
+//! Damage is the integral over time of a rate depending only upon the
+//! absolute temperature, which is the temperature rise from
+//! [`MultiLayer::temperature_rise`] plus a baseline. The exposure is taken to
+//! be continuous, beginning at a time of 0, as in
+//! [`MultiLayer::time_to_threshold`]
+
+use rug::{float::Special, ops::PowAssign, Float};
+use std::borrow::Cow;
+
+use crate::{
+    greens::{self, Beam, BoundsError, MultiLayer, ThermalProperties},
+    quadrature::Quadrature,
+};
+
+/// The molar gas constant. Units: J*mol^-1*K^-1
+pub const GAS_CONSTANT: f64 = 8.314_462_618;
+
+/// The offset between the Kelvin and Celsius scales. Units: K
+const CELSIUS_OFFSET: f64 = 273.15;
+
+/// The upper limit on the number of times [`fn@threshold_irradiance`]
+/// doubles the irradiance while searching for one which reaches the target
+pub const THRESHOLD_DOUBLING_LIMIT: u64 = 256;
+
+/// A model of the rate at which thermal damage accumulates
+#[derive(Clone, PartialEq, Debug)]
+pub enum DamageModel<'a> {
+    /// The Arrhenius damage integral `Omega`, accumulating at the rate
+    /// `a * exp(-e_a / (R * T))` (units: s^-1), where `R` is
+    /// [`GAS_CONSTANT`]. An `Omega` of 1 is conventionally taken as the
+    /// threshold of damage
+    Arrhenius {
+        /// The frequency factor. Units: s^-1
+        a: Cow<'a, Float>,
+
+        /// The activation energy. Units: J*mol^-1
+        e_a: Cow<'a, Float>,
+    },
+
+    /// The cumulative equivalent minutes at 43 degrees Celsius (CEM43),
+    /// accumulating at the rate `q^(43 - T)` (units: min*s^-1, with `T` in
+    /// degrees Celsius) divided by 60, where `q` is 0.5 at or above 43
+    /// degrees Celsius and 0.25 below
+    Cem43,
+}
+
+impl DamageModel<'static> {
+    /// Creates a new [`DamageModel::Arrhenius`] from `f64`s, converting them
+    /// to owned [`struct@Float`]s at the given precision
+    pub fn arrhenius_from_f64(precision: u64, a: f64, e_a: f64) -> Self {
+        Self::Arrhenius {
+            a: Cow::Owned(Float::with_val_64(precision, a)),
+            e_a: Cow::Owned(Float::with_val_64(precision, e_a)),
+        }
+    }
+}
+
+impl<'a> DamageModel<'a> {
+    /// Calculates the rate at which damage accumulates at the absolute
+    /// temperature `temperature` (units: K)
+    pub fn rate(&self, precision: u64, temperature: &Float) -> Float {
+        match self {
+            Self::Arrhenius { a, e_a } => {
+                let mut rate = Float::with_val_64(precision, temperature);
+                rate *= GAS_CONSTANT;
+                rate.recip_mut();
+                rate *= e_a.as_ref();
+                rate *= -1;
+                rate.exp_mut();
+                rate *= a.as_ref();
+                rate
+            }
+            Self::Cem43 => {
+                let mut exponent = Float::with_val_64(precision, 43.0 + CELSIUS_OFFSET);
+                exponent -= temperature;
+
+                let mut rate =
+                    Float::with_val_64(precision, if exponent <= 0 { 0.5 } else { 0.25 });
+                rate.pow_assign(&exponent);
+                rate /= 60;
+                rate
+            }
+        }
+    }
+}
+
+/// Calculates the damage accumulated at the point (z, r) over the interval
+/// a..b of observation times (units: s) under a continuous exposure
+/// beginning at a time of 0, returning it alongside its approximate error
+///
+/// The rate given by `model` at `baseline` (units: K) plus the temperature
+/// rise over 0..t (as in [`MultiLayer::temperature_rise`]) is integrated
+/// over a..b with `quadrature`, as is the temperature rise at each t. Both
+/// are given `epsilon`
+///
+/// A [`enum@BoundsError`] is returned if `bounds` is invalid (as in
+/// [`MultiLayer::time_to_threshold`])
+#[allow(clippy::too_many_arguments)]
+pub fn damage(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    multi_layer: &MultiLayer,
+    z: &Float,
+    r: &Float,
+    baseline: &Float,
+    model: &DamageModel<'_>,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, Float), BoundsError> {
+    greens::check_exposure_bounds(bounds)?;
+
+    let (a, b) = bounds;
+    let zero = Float::with_val_64(precision, Special::Zero);
+
+    Ok(quadrature.integrate(
+        |t| {
+            // the bounds were checked above, so this can't fail
+
+            let (mut temperature, _) = multi_layer
+                .temperature_rise(
+                    precision,
+                    quadrature,
+                    beam,
+                    thermal_properties,
+                    z,
+                    r,
+                    epsilon,
+                    (&zero, &t),
+                )
+                .unwrap_or_else(|_| (Float::with_val_64(precision, Special::Nan), zero.clone()));
+            temperature += baseline;
+
+            model.rate(precision, &temperature)
+        },
+        epsilon,
+        (a, b),
+    ))
+}
+
+/// Finds the irradiance incident on the top of the stack (units: W*cm^-2)
+/// at which the damage accumulated at the point (z, r) over `bounds`, as in
+/// [`fn@damage`], reaches `target` (e.g. an `Omega` of 1)
+///
+/// `stack_template` is rescaled with [`MultiLayer::scale_irradiance`],
+/// keeping the ratios between the irradiances of its layers, so its top
+/// layer must receive a positive irradiance. As the damage only increases
+/// with the irradiance, a bracket is found by doubling the scale factor from
+/// 1 (at most [`THRESHOLD_DOUBLING_LIMIT`] times) until the target is
+/// reached, then narrowed by bisection until it is known to within a
+/// relative tolerance of `epsilon` (which is also used for each
+/// integration). If the damage at `baseline` alone reaches the target, the
+/// irradiance is zero
+///
+/// `None` is returned if the target is never reached, or if the top layer of
+/// `stack_template` receives no irradiance. A [`enum@BoundsError`] is
+/// returned if `bounds` is invalid (as in [`fn@damage`]). Each step
+/// integrates the damage afresh, integrating the temperature rise at every
+/// time, so this is costly for beams without a closed form
+#[allow(clippy::too_many_arguments)]
+pub fn threshold_irradiance(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    stack_template: &MultiLayer,
+    z: &Float,
+    r: &Float,
+    baseline: &Float,
+    model: &DamageModel<'_>,
+    target: &Float,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<Option<Float>, BoundsError> {
+    greens::check_exposure_bounds(bounds)?;
+
+    let incident = stack_template.incident_irradiance(precision);
+    if !incident.is_finite() || incident <= 0 {
+        return Ok(None);
+    }
+
+    let damage_at = |scale: &Float| {
+        let mut multi_layer = stack_template.clone();
+        multi_layer.scale_irradiance(scale);
+
+        damage(
+            precision,
+            quadrature,
+            beam,
+            thermal_properties,
+            &multi_layer,
+            z,
+            r,
+            baseline,
+            model,
+            epsilon,
+            bounds,
+        )
+        .map(|(damage, _)| damage)
+    };
+
+    let mut lower = Float::with_val_64(precision, Special::Zero);
+    if damage_at(&lower)? >= *target {
+        return Ok(Some(lower));
+    }
+
+    let mut upper = Float::with_val_64(precision, 1);
+    let mut doublings = 0;
+
+    while damage_at(&upper)? < *target {
+        if doublings == THRESHOLD_DOUBLING_LIMIT {
+            return Ok(None);
+        }
+
+        lower.clone_from(&upper);
+        upper *= 2;
+        doublings += 1;
+    }
+
+    // each iteration halves the bracket, so after `precision` of them it
+    // can't be narrowed any further
+
+    for _ in 0..precision {
+        let mut tolerance = Float::with_val_64(precision, &upper);
+        tolerance *= epsilon;
+
+        if Float::with_val_64(precision, &upper - &lower) <= tolerance {
+            break;
+        }
+
+        let mut middle = Float::with_val_64(precision, &lower + &upper);
+        middle /= 2;
+
+        if damage_at(&middle)? >= *target {
+            upper = middle;
+        } else {
+            lower = middle;
+        }
+    }
+
+    upper *= incident;
+
+    Ok(Some(upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        greens::{LargeBeam, Layer},
+        quadrature::{GaussKronrod, G7_K15},
+    };
+
+    #[test]
+    fn cem43_at_constant_temperature() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 0.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let zero = Float::with_val_64(64, Special::Zero);
+        let epsilon = Float::with_val_64(64, 1e-12);
+
+        // a minute at 43 degrees Celsius is, by definition, one equivalent
+        // minute, and each degree below that quarters the rate
+
+        for (celsius, expected) in [(43.0, 1.0), (42.0, 0.25), (44.0, 2.0)] {
+            let (damage, _) = super::damage(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &multi_layer,
+                &zero,
+                &zero,
+                &Float::with_val_64(64, celsius + CELSIUS_OFFSET),
+                &DamageModel::Cem43,
+                &epsilon,
+                (&zero, &Float::with_val_64(64, 60.0)),
+            )
+            .expect("Invalid bounds");
+
+            let mut error = damage;
+            error -= expected;
+            error.abs_mut();
+            assert!(error < 1e-9);
+        }
+    }
+
+    #[test]
+    fn threshold_irradiance() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let stack_template = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let zero = Float::with_val_64(64, Special::Zero);
+        let end = Float::with_val_64(64, 0.1);
+        let baseline = Float::with_val_64(64, 37.0 + CELSIUS_OFFSET);
+        let epsilon = Float::with_val_64(64, 1e-4);
+
+        // the coefficients of Henriques for skin
+
+        let model = DamageModel::arrhenius_from_f64(64, 3.1e98, 6.28e5);
+
+        let threshold = |target: f64| {
+            super::threshold_irradiance(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &stack_template,
+                &zero,
+                &zero,
+                &baseline,
+                &model,
+                &Float::with_val_64(64, target),
+                &epsilon,
+                (&zero, &end),
+            )
+            .expect("Invalid bounds")
+            .expect("The target was never reached")
+        };
+
+        let single = threshold(1.0);
+        let double = threshold(2.0);
+        assert!(single > 0);
+        assert!(double > single);
+
+        // the damage at the recovered irradiance is the target, to within
+        // the sensitivity of the rate to the temperature
+
+        let mut multi_layer = stack_template.clone();
+        multi_layer.scale_irradiance(&single);
+
+        let (damage, _) = super::damage(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &multi_layer,
+            &zero,
+            &zero,
+            &baseline,
+            &model,
+            &epsilon,
+            (&zero, &end),
+        )
+        .expect("Invalid bounds");
+
+        let mut error = damage;
+        error -= 1.0;
+        error.abs_mut();
+        assert!(error < 1e-2, "{error}");
+
+        assert_eq!(
+            super::threshold_irradiance(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &stack_template,
+                &zero,
+                &zero,
+                &baseline,
+                &model,
+                &Float::with_val_64(64, 1.0),
+                &epsilon,
+                (&end, &zero),
+            ),
+            Err(BoundsError::Reversed {
+                a: end.clone(),
+                b: zero.clone(),
+            })
+        );
+    }
+}
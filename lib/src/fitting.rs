@@ -0,0 +1,448 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use rug::{float::Special, Float};
+use std::{borrow::Cow, error::Error, fmt};
+
+use crate::{
+    greens::{self, Beam, BeamError, BoundsError, Layer, ThermalProperties},
+    quadrature::Quadrature,
+    utilities,
+};
+
+/// An error encountered while fitting a beam with [`fn@fit_radius`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum FitError {
+    /// The beam could not be constructed with the initial radius
+    Beam(BeamError),
+
+    /// The time at which the samples were measured is not a valid upper
+    /// bound of the integral over 0..tp
+    Bounds(BoundsError),
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Beam(error) => write!(f, "{error}"),
+            Self::Bounds(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for FitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Beam(error) => Some(error),
+            Self::Bounds(error) => Some(error),
+        }
+    }
+}
+
+impl From<BeamError> for FitError {
+    fn from(error: BeamError) -> Self {
+        Self::Beam(error)
+    }
+}
+
+impl From<BoundsError> for FitError {
+    fn from(error: BoundsError) -> Self {
+        Self::Bounds(error)
+    }
+}
+
+/// Fits the absorption coefficient of a [`struct@Layer`] to measured
+/// temperature rise data
+///
+/// `samples` are pairs of time (units: s) and measured temperature rise
+/// (units: K) at the point (z, r), sorted in ascending order of time, with
+/// exposure beginning at a time of 0. All other parameters, including every
+/// field of `layer_template` other than `mu_a`, are held fixed
+///
+/// The sum of squared residuals between [`fn@greens::temperature_rise_series`]
+/// and `samples` is minimized by first expanding a bracket around the `mu_a`
+/// of `layer_template` (which must be positive) by factors of 2 until it
+/// contains a minimum, then narrowing it with a golden-section search until
+/// its width relative to the estimate is within `epsilon`. `epsilon` is also
+/// passed to `quadrature`. If `epsilon` is too small to be reached at
+/// `precision`, the search stops once the bracket can't be narrowed any
+/// further (see [`fn@utilities::minimize`])
+#[allow(clippy::too_many_arguments)]
+pub fn fit_mu_a(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer_template: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    samples: &[(Float, Float)],
+    epsilon: &Float,
+) -> Float {
+    let start = Float::with_val_64(precision, Special::Zero);
+    let times = samples
+        .iter()
+        .map(|(time, _)| time.clone())
+        .collect::<Vec<_>>();
+
+    let residual = |mu_a: &Float| {
+        let layer = Layer {
+            mu_a: Cow::Borrowed(mu_a),
+            ..layer_template.clone()
+        };
+
+        let series = greens::temperature_rise_series(
+            precision,
+            quadrature,
+            beam,
+            thermal_properties,
+            &layer,
+            z,
+            r,
+            epsilon,
+            &start,
+            &times,
+        );
+
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+        for ((temperature_rise, _), (_, measured)) in series.into_iter().zip(samples) {
+            let mut difference = temperature_rise;
+            difference -= measured;
+            difference.square_mut();
+            sum += difference;
+        }
+
+        sum
+    };
+
+    utilities::minimize(precision, layer_template.mu_a.as_ref(), epsilon, residual)
+}
+
+/// Fits the radius of a beam to temperature rise data measured at varying
+/// radial distances at a single time
+///
+/// `samples` are pairs of radial distance (units: cm) and measured
+/// temperature rise (units: K) at the depth `z` and the time `tp` (units: s),
+/// with exposure beginning at a time of 0. `beam` constructs the beam for a
+/// given radius, e.g. [`FlatTopBeam::new`] or, for a Gaussian beam,
+/// [`LaguerreGaussianBeam::new`] with `p` and `l` of 0. All other parameters
+/// are held fixed
+///
+/// The sum of squared residuals between [`fn@greens::temperature_rise`] and
+/// `samples` is minimized in the same manner as in [`fn@fit_mu_a`], starting
+/// from `radius`. As the irradiance at the center of either beam is fixed,
+/// the temperature rise at any radial distance grows monotonically with the
+/// radius, so each squared residual has a single minimum and expanding the
+/// bracket by factors of 2 cannot skip over it. Any error from constructing
+/// the beam with `radius` is returned, as is a [`enum@BoundsError`] if `tp`
+/// is negative or NaN, before anything is evaluated
+///
+/// [`FlatTopBeam::new`]: crate::greens::FlatTopBeam::new
+/// [`LaguerreGaussianBeam::new`]: crate::greens::LaguerreGaussianBeam::new
+#[allow(clippy::too_many_arguments)]
+pub fn fit_radius<B: Beam>(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: impl Fn(&Float) -> Result<B, BeamError>,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    tp: &Float,
+    samples: &[(Float, Float)],
+    radius: &Float,
+    epsilon: &Float,
+) -> Result<Float, FitError> {
+    // every radius tried afterwards is a positive multiple of this one, so
+    // only it needs to be checked
+
+    beam(radius)?;
+
+    let start = Float::with_val_64(precision, Special::Zero);
+
+    // the bounds are the same for every sample, so they're checked here
+    // rather than for each residual
+
+    if tp.is_nan() {
+        return Err(BoundsError::NanUpper.into());
+    }
+
+    if *tp < start {
+        return Err(BoundsError::Reversed {
+            a: start,
+            b: tp.clone(),
+        }
+        .into());
+    }
+
+    let residual = |radius: &Float| {
+        let beam = beam(radius).expect("the radius was not positive");
+
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+        for (r, measured) in samples {
+            let (mut difference, _) = greens::temperature_rise(
+                precision,
+                quadrature,
+                &beam,
+                thermal_properties,
+                layer,
+                z,
+                r,
+                epsilon,
+                (&start, tp),
+            )
+            .unwrap_or_else(|_| unreachable!("the bounds were checked above"));
+            difference -= measured;
+            difference.square_mut();
+            sum += difference;
+        }
+
+        sum
+    };
+
+    Ok(utilities::minimize(precision, radius, epsilon, residual))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        greens::{FlatTopBeam, LargeBeam},
+        quadrature::{GaussKronrod, G7_K15},
+        test_beams::GaussianBeam,
+    };
+
+    #[test]
+    fn recover_mu_a() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-6);
+        let zero = Float::with_val_64(64, Special::Zero);
+
+        let times = [0.01, 0.02, 0.03, 0.04, 0.05]
+            .map(|time| Float::with_val_64(64, time))
+            .to_vec();
+        let samples = greens::temperature_rise_series(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &zero,
+            &zero,
+            &epsilon,
+            &zero,
+            &times,
+        )
+        .into_iter()
+        .zip(times)
+        .map(|((temperature_rise, _), time)| (time, temperature_rise))
+        .collect::<Vec<_>>();
+
+        let template = Layer::from_f64(64, 0.01, 0.0, 10.0, 1.0);
+        let mut result = fit_mu_a(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &template,
+            &zero,
+            &zero,
+            &samples,
+            &epsilon,
+        );
+
+        result -= 100.0;
+        result.abs_mut();
+        assert!(result < 1e-3);
+
+        // a tolerance far below what 64 bits can represent still terminates,
+        // with the quadrature limited so that each residual is cheap
+
+        let quadrature = GaussKronrod {
+            interval_limit: 4,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let mut result = fit_mu_a(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &template,
+            &zero,
+            &zero,
+            &samples,
+            &Float::with_val_64(64, 1e-30),
+        );
+
+        result -= 100.0;
+        result.abs_mut();
+        assert!(result < 1e-3);
+    }
+
+    #[test]
+    fn recover_radius() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-6);
+        let zero = Float::with_val_64(64, Special::Zero);
+        let tp = Float::with_val_64(64, 0.01);
+
+        let gaussian = |radius: &Float| {
+            if *radius > 0 {
+                Ok(GaussianBeam {
+                    radius: radius.clone(),
+                })
+            } else {
+                Err(BeamError::NonPositiveRadius {
+                    name: "radius",
+                    value: radius.clone(),
+                })
+            }
+        };
+
+        let beam = GaussianBeam {
+            radius: Float::with_val_64(64, 0.01),
+        };
+        let samples = [0.0, 0.005, 0.01, 0.02]
+            .map(|r| {
+                let r = Float::with_val_64(64, r);
+                let (temperature_rise, _) = greens::temperature_rise(
+                    64,
+                    &quadrature,
+                    &beam,
+                    &thermal_properties,
+                    &layer,
+                    &zero,
+                    &r,
+                    &epsilon,
+                    (&zero, &tp),
+                )
+                .expect("Invalid bounds");
+
+                (r, temperature_rise)
+            })
+            .to_vec();
+
+        let mut result = fit_radius(
+            64,
+            &quadrature,
+            gaussian,
+            &thermal_properties,
+            &layer,
+            &zero,
+            &tp,
+            &samples,
+            &Float::with_val_64(64, 0.003),
+            &epsilon,
+        )
+        .expect("Invalid beam");
+
+        result -= 0.01;
+        result.abs_mut();
+        assert!(result < 1e-5);
+
+        assert!(fit_radius(
+            64,
+            &quadrature,
+            gaussian,
+            &thermal_properties,
+            &layer,
+            &zero,
+            &tp,
+            &samples,
+            &zero,
+            &epsilon,
+        )
+        .is_err());
+
+        let negative = Float::with_val_64(64, -0.01);
+        assert_eq!(
+            fit_radius(
+                64,
+                &quadrature,
+                gaussian,
+                &thermal_properties,
+                &layer,
+                &zero,
+                &negative,
+                &samples,
+                &Float::with_val_64(64, 0.003),
+                &epsilon,
+            ),
+            Err(FitError::Bounds(BoundsError::Reversed {
+                a: zero.clone(),
+                b: negative.clone(),
+            }))
+        );
+    }
+
+    #[test]
+    fn recover_flat_top_radius() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-6);
+        let zero = Float::with_val_64(64, Special::Zero);
+        let tp = Float::with_val_64(64, 0.01);
+
+        // off of the axis, a flat-top beam is evaluated through the Marcum Q
+        // function, which is too slow (and, per the TODO in `FlatTopBeam`,
+        // too inexact) to be integrated for every residual. the temperature
+        // rise on the axis still grows monotonically with the radius, so a
+        // single sample there is enough to recover it
+
+        let beam = FlatTopBeam::new(&Float::with_val_64(64, 0.01)).expect("Invalid beam");
+        let samples = [0.0]
+            .map(|r| {
+                let r = Float::with_val_64(64, r);
+                let (temperature_rise, _) = greens::temperature_rise(
+                    64,
+                    &quadrature,
+                    &beam,
+                    &thermal_properties,
+                    &layer,
+                    &zero,
+                    &r,
+                    &epsilon,
+                    (&zero, &tp),
+                )
+                .expect("Invalid bounds");
+
+                (r, temperature_rise)
+            })
+            .to_vec();
+
+        let mut result = fit_radius(
+            64,
+            &quadrature,
+            FlatTopBeam::new,
+            &thermal_properties,
+            &layer,
+            &zero,
+            &tp,
+            &samples,
+            &Float::with_val_64(64, 0.003),
+            &epsilon,
+        )
+        .expect("Invalid beam");
+
+        result -= 0.01;
+        result.abs_mut();
+        assert!(result < 1e-5);
+    }
+}
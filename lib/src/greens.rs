@@ -1,9 +1,20 @@
 // SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
 
-use rug::{float::Special, Assign, Float};
-use std::borrow::Cow;
+use rug::{
+    float::{self, Constant, Special},
+    ops::{Pow, PowAssign},
+    Assign, Float,
+};
+use std::{borrow::Cow, cmp::Ordering, error::Error, fmt, sync::atomic::AtomicBool};
 
-use crate::{quadrature::Quadrature, utilities};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    bessel,
+    quadrature::{self, Quadrature, QuadratureError},
+    units, utilities,
+};
 
 /// A configuration structure for specific thermal properties
 #[derive(Clone, PartialEq, Debug)]
@@ -18,6 +29,102 @@ pub struct ThermalProperties<'a> {
     pub k: Cow<'a, Float>,
 }
 
+impl ThermalProperties<'static> {
+    /// Creates a new [`struct@ThermalProperties`] from `f64`s, converting them
+    /// to owned [`struct@Float`]s at the given precision
+    pub fn from_f64(precision: u64, rho: f64, c: f64, k: f64) -> Self {
+        Self {
+            rho: Cow::Owned(Float::with_val_64(precision, rho)),
+            c: Cow::Owned(Float::with_val_64(precision, c)),
+            k: Cow::Owned(Float::with_val_64(precision, k)),
+        }
+    }
+
+    /// Creates a new [`struct@ThermalProperties`] from quantities tagged with
+    /// their units (see [`mod@units`])
+    pub fn from_units(
+        rho: units::GramsPerCm3,
+        c: units::JoulesPerGramKelvin,
+        k: units::WattsPerCmKelvin,
+    ) -> Self {
+        Self {
+            rho: Cow::Owned(rho.into_inner()),
+            c: Cow::Owned(c.into_inner()),
+            k: Cow::Owned(k.into_inner()),
+        }
+    }
+}
+
+impl<'a> ThermalProperties<'a> {
+    /// Calculates the thermal relaxation time (units: s) of a beam of the
+    /// given radius (units: cm), `radius^2 / (4 * alpha)`
+    ///
+    /// Exposures much shorter than this are thermally confined in the radial
+    /// direction. See [`Layer::thermal_relaxation_time`] for the axial analog
+    pub fn radial_thermal_relaxation_time(&self, radius: &Float, precision: u64) -> Float {
+        let mut tau = Float::with_val_64(precision, radius.square_ref());
+        tau /= 4.0;
+        tau /= self.k.as_ref();
+        tau *= self.rho.as_ref();
+        tau *= self.c.as_ref();
+        tau
+    }
+
+    /// Creates an owned copy of `self` with every [`struct@Float`] field
+    /// re-created at the given precision
+    ///
+    /// Raising the precision extends each mantissa exactly, while lowering it
+    /// rounds to nearest
+    pub fn with_precision(&self, precision: u64) -> ThermalProperties<'static> {
+        ThermalProperties {
+            rho: Cow::Owned(Float::with_val_64(precision, self.rho.as_ref())),
+            c: Cow::Owned(Float::with_val_64(precision, self.c.as_ref())),
+            k: Cow::Owned(Float::with_val_64(precision, self.k.as_ref())),
+        }
+    }
+
+    /// Checks that `rho`, `c`, and `k` are all finite and positive,
+    /// reporting the first which isn't
+    ///
+    /// Anything else is nonphysical, and a zero or negative `rho` or `c`
+    /// leads to an infinite or negative thermal diffusivity. A `k` of zero is
+    /// also rejected here, even though it is handled as the limit of no
+    /// conduction (see `LargeBeam::evaluate_with`)
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        for (name, value) in [
+            ("rho", self.rho.as_ref()),
+            ("c", self.c.as_ref()),
+            ("k", self.k.as_ref()),
+        ] {
+            if !value.is_finite() {
+                return Err(ThermalError::NonFinite {
+                    name,
+                    value: value.clone(),
+                });
+            }
+
+            if *value <= 0 {
+                return Err(ThermalError::NonPositive {
+                    name,
+                    value: value.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for ThermalProperties<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rho = {:.6} g*cm^3, c = {:.6} J*g^-1*K^-1, k = {:.6} W*cm^-1*K^-1",
+            self.rho, self.c, self.k
+        )
+    }
+}
+
 /// A layer of tissue
 #[derive(Clone, PartialEq, Debug)]
 pub struct Layer<'a> {
@@ -32,46 +139,730 @@ pub struct Layer<'a> {
 
     /// Irradiance. Units: W*cm^-2
     pub e0: Cow<'a, Float>,
+
+    /// Refractive index, if known. Units: dimensionless
+    ///
+    /// When both layers on either side of an interface have one, part of the
+    /// irradiance is reflected there (see [`MultiLayer::new`])
+    pub n: Option<Cow<'a, Float>>,
+}
+
+impl Layer<'static> {
+    /// Creates a new [`struct@Layer`] from `f64`s, converting them to owned
+    /// [`struct@Float`]s at the given precision
+    pub fn from_f64(precision: u64, d: f64, z0: f64, mu_a: f64, e0: f64) -> Self {
+        Self {
+            d: Cow::Owned(Float::with_val_64(precision, d)),
+            z0: Cow::Owned(Float::with_val_64(precision, z0)),
+            mu_a: Cow::Owned(Float::with_val_64(precision, mu_a)),
+            e0: Cow::Owned(Float::with_val_64(precision, e0)),
+            n: None,
+        }
+    }
+
+    /// Creates a new [`struct@Layer`] from quantities tagged with their units
+    /// (see [`mod@units`]), converting them into those used internally
+    pub fn from_units(
+        d: impl Into<units::Centimeters>,
+        z0: impl Into<units::Centimeters>,
+        mu_a: impl Into<units::PerCentimeter>,
+        e0: impl Into<units::WattsPerCm2>,
+    ) -> Self {
+        Self {
+            d: Cow::Owned(d.into().into_inner()),
+            z0: Cow::Owned(z0.into().into_inner()),
+            mu_a: Cow::Owned(mu_a.into().into_inner()),
+            e0: Cow::Owned(e0.into().into_inner()),
+            n: None,
+        }
+    }
 }
 
 impl<'a> Layer<'a> {
+    /// Calculates the thermal relaxation time (units: s) of the layer,
+    /// `1 / (4 * alpha * mu_a^2)`
+    ///
+    /// This is the time taken for heat to diffuse across the optical
+    /// penetration depth `1 / mu_a`, so exposures much shorter than it are
+    /// thermally confined. See
+    /// [`ThermalProperties::radial_thermal_relaxation_time`] for the radial
+    /// analog
+    pub fn thermal_relaxation_time(
+        &self,
+        thermal_properties: &ThermalProperties<'_>,
+        precision: u64,
+    ) -> Float {
+        thermal_properties.radial_thermal_relaxation_time(
+            &Float::with_val_64(precision, self.mu_a.recip_ref()),
+            precision,
+        )
+    }
+
+    /// Creates an owned copy of `self` with every [`struct@Float`] field
+    /// re-created at the given precision, as in
+    /// [`ThermalProperties::with_precision`]
+    pub fn with_precision(&self, precision: u64) -> Layer<'static> {
+        Layer {
+            d: Cow::Owned(Float::with_val_64(precision, self.d.as_ref())),
+            z0: Cow::Owned(Float::with_val_64(precision, self.z0.as_ref())),
+            mu_a: Cow::Owned(Float::with_val_64(precision, self.mu_a.as_ref())),
+            e0: Cow::Owned(Float::with_val_64(precision, self.e0.as_ref())),
+            n: self
+                .n
+                .as_ref()
+                .map(|n| Cow::Owned(Float::with_val_64(precision, n.as_ref()))),
+        }
+    }
+
+    /// Splits the layer into two contiguous layers at the depth `z_split`,
+    /// returning `None` unless it is strictly within `z0..z0 + d`
+    ///
+    /// Both layers keep the `mu_a` and `n` of `self`. The upper layer keeps
+    /// its `e0`, and the `e0` of the lower layer is the irradiance reaching
+    /// `z_split` according to Beer's Law, so together they absorb exactly as
+    /// `self` does
+    pub fn split_at(
+        &self,
+        z_split: &Float,
+        precision: u64,
+    ) -> Option<(Layer<'static>, Layer<'static>)> {
+        let mut bottom = Float::with_val_64(precision, self.z0.as_ref());
+        bottom += self.d.as_ref();
+
+        if *z_split <= *self.z0 || *z_split >= bottom {
+            return None;
+        }
+
+        let mut upper = self.with_precision(precision);
+        upper.d = Cow::Owned(Float::with_val_64(precision, z_split - self.z0.as_ref()));
+
+        let mut e0 = Float::with_val_64(precision, upper.d.as_ref());
+        e0 *= self.mu_a.as_ref();
+        e0 *= -1;
+        e0.exp_mut();
+        e0 *= self.e0.as_ref();
+
+        bottom -= z_split;
+
+        let lower = Layer {
+            d: Cow::Owned(bottom),
+            z0: Cow::Owned(Float::with_val_64(precision, z_split)),
+            e0: Cow::Owned(e0),
+            ..upper.clone()
+        };
+
+        Some((upper, lower))
+    }
+
     fn into_owned(self) -> Layer<'static> {
         Layer {
             d: Cow::Owned(self.d.into_owned()),
             z0: Cow::Owned(self.z0.into_owned()),
             mu_a: Cow::Owned(self.mu_a.into_owned()),
             e0: Cow::Owned(self.e0.into_owned()),
+            n: self.n.map(|n| Cow::Owned(n.into_owned())),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Layer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "z0 = {:.6} cm, d = {:.6} cm, mu_a = {:.6} cm^-1, e0 = {:.6} W*cm^-2",
+            self.z0, self.d, self.mu_a, self.e0
+        )?;
+
+        if let Some(n) = &self.n {
+            write!(f, ", n = {n:.6}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error encountered while constructing a [`struct@MultiLayer`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum MultiLayerError {
+    /// A layer begins before the layer above it ends
+    Overlap {
+        /// Units: cm
+        z0: Float,
+
+        /// The bottom of the layer above. Units: cm
+        bottom: Float,
+    },
+}
+
+impl fmt::Display for MultiLayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap { z0, bottom } => write!(
+                f,
+                "the layer beginning at z0 = {z0} cm overlaps the layer above it, which ends at {bottom} cm"
+            ),
+        }
+    }
+}
+
+impl Error for MultiLayerError {}
+
+/// A difference between two [`struct@MultiLayer`]s, as reported by
+/// [`MultiLayer::diff`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum LayerDiff {
+    /// A field of the layer at `index` in both stacks differs
+    Changed {
+        /// The index of the layer in order of incidence
+        index: usize,
+
+        /// One of `d`, `z0`, `mu_a`, or `e0`
+        field: &'static str,
+
+        /// The value in the original stack
+        from: Float,
+
+        /// The value in the other stack
+        to: Float,
+    },
+
+    /// The other stack has a layer at `index` which the original does not
+    Added {
+        /// The index of the layer in order of incidence
+        index: usize,
+
+        /// The added layer
+        layer: Layer<'static>,
+    },
+
+    /// The original stack has a layer at `index` which the other does not
+    Removed {
+        /// The index of the layer in order of incidence
+        index: usize,
+
+        /// The removed layer
+        layer: Layer<'static>,
+    },
+}
+
+impl fmt::Display for LayerDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Changed {
+                index,
+                field,
+                from,
+                to,
+            } => write!(
+                f,
+                "layer {index}: {field} changed from {from:.6} to {to:.6}"
+            ),
+            Self::Added { index, layer } => write!(f, "layer {index} added ({layer})"),
+            Self::Removed { index, layer } => write!(f, "layer {index} removed ({layer})"),
+        }
+    }
+}
+
+/// An error encountered while validating the bounds of an integral over time
+#[derive(Clone, PartialEq, Debug)]
+pub enum BoundsError {
+    /// The lower bound is infinite or NaN
+    NonFiniteLower {
+        /// Units: s
+        a: Float,
+    },
+
+    /// The upper bound is NaN
+    NanUpper,
+
+    /// The lower bound is greater than the upper bound
+    Reversed {
+        /// Units: s
+        a: Float,
+
+        /// Units: s
+        b: Float,
+    },
+
+    /// The lower bound is negative, where only nonnegative bounds (such as
+    /// times since the exposure began) are accepted
+    NegativeLower {
+        /// Units: s
+        a: Float,
+    },
+
+    /// The upper bound is infinite, where only a finite one is accepted
+    InfiniteUpper,
+}
+
+impl fmt::Display for BoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFiniteLower { a } => {
+                write!(f, "the lower bound a = {a} s is not finite")
+            }
+            Self::NanUpper => write!(f, "the upper bound is NaN"),
+            Self::Reversed { a, b } => write!(
+                f,
+                "the lower bound a = {a} s is greater than the upper bound b = {b} s"
+            ),
+            Self::NegativeLower { a } => write!(f, "the lower bound a = {a} s is negative"),
+            Self::InfiniteUpper => write!(f, "the upper bound is infinite"),
+        }
+    }
+}
+
+impl Error for BoundsError {}
+
+/// An error encountered while constructing a [`trait@Beam`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum BeamError {
+    /// A radius of the beam is zero, negative or NaN
+    NonPositiveRadius {
+        /// The name of the radius
+        name: &'static str,
+
+        /// Units: cm
+        value: Float,
+    },
+}
+
+impl fmt::Display for BeamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonPositiveRadius { name, value } => {
+                write!(f, "the radius {name} = {value} cm is not positive")
+            }
+        }
+    }
+}
+
+impl Error for BeamError {}
+
+/// An error encountered while validating [`struct@ThermalProperties`] (see
+/// [`ThermalProperties::validate`])
+#[derive(Clone, PartialEq, Debug)]
+pub enum ThermalError {
+    /// A property is infinite or NaN
+    NonFinite {
+        /// One of `rho`, `c`, or `k`
+        name: &'static str,
+
+        /// The value of the property
+        value: Float,
+    },
+
+    /// A property is zero or negative
+    NonPositive {
+        /// One of `rho`, `c`, or `k`
+        name: &'static str,
+
+        /// The value of the property
+        value: Float,
+    },
+}
+
+impl fmt::Display for ThermalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite { name, value } => {
+                write!(f, "the thermal property {name} = {value} is not finite")
+            }
+            Self::NonPositive { name, value } => {
+                write!(f, "the thermal property {name} = {value} is not positive")
+            }
+        }
+    }
+}
+
+impl Error for ThermalError {}
+
+/// An error encountered while constructing a [`struct@TabulatedSource`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum TabulatedSourceError {
+    /// The number of depths differs from the number of power densities
+    LengthMismatch {
+        /// The number of depths
+        depths: usize,
+
+        /// The number of power densities
+        power_density: usize,
+    },
+
+    /// Fewer than two samples were given, so there is nothing to interpolate
+    /// between
+    TooFewSamples(usize),
+
+    /// The sample at this index is infinite or NaN
+    NonFinite(usize),
+
+    /// The depth at this index is not greater than the one before it
+    NotIncreasing(usize),
+}
+
+impl fmt::Display for TabulatedSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch {
+                depths,
+                power_density,
+            } => write!(
+                f,
+                "{depths} depths were given with {power_density} power densities"
+            ),
+            Self::TooFewSamples(samples) => {
+                write!(f, "at least 2 samples are needed, but {samples} were given")
+            }
+            Self::NonFinite(index) => write!(f, "the sample at index {index} is not finite"),
+            Self::NotIncreasing(index) => write!(
+                f,
+                "the depth at index {index} is not greater than the one before it"
+            ),
+        }
+    }
+}
+
+impl Error for TabulatedSourceError {}
+
+/// Returns [`BeamError::NonPositiveRadius`] if `value` is not positive
+fn check_radius(name: &'static str, value: &Float) -> Result<Cow<'static, Float>, BeamError> {
+    if *value > 0 {
+        Ok(Cow::Owned(value.clone()))
+    } else {
+        Err(BeamError::NonPositiveRadius {
+            name,
+            value: value.clone(),
+        })
+    }
+}
+
+/// An error encountered while escalating precision in
+/// [`fn@temperature_rise_to_digits`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum EscalationError {
+    /// The bounds of the integral are invalid
+    Bounds(BoundsError),
+
+    /// Successive results did not agree to the target number of digits before
+    /// the maximum precision was exceeded
+    NotConverged {
+        /// The last precision the temperature rise was calculated at
+        precision: u64,
+    },
+}
+
+impl fmt::Display for EscalationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bounds(error) => write!(f, "{error}"),
+            Self::NotConverged { precision } => write!(
+                f,
+                "the temperature rise did not converge by a precision of {precision} bits"
+            ),
+        }
+    }
+}
+
+impl Error for EscalationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Bounds(error) => Some(error),
+            Self::NotConverged { .. } => None,
+        }
+    }
+}
+
+impl From<BoundsError> for EscalationError {
+    fn from(error: BoundsError) -> Self {
+        Self::Bounds(error)
+    }
+}
+
+/// An error encountered while calculating a temperature rise with
+/// [`fn@temperature_rise_checked`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum CeilingError {
+    /// The bounds of the integral are invalid
+    Bounds(BoundsError),
+
+    /// The resulting temperature exceeds the physical ceiling, so the linear
+    /// model it was calculated with no longer holds
+    Exceeded {
+        /// The temperature rise as calculated. Units: K
+        temperature_rise: Float,
+
+        /// The approximate error of `temperature_rise`
+        error: Float,
+
+        /// The absolute temperature reached. Units: K
+        temperature: Float,
+    },
+}
+
+impl fmt::Display for CeilingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bounds(error) => write!(f, "{error}"),
+            Self::Exceeded { temperature, .. } => write!(
+                f,
+                "the temperature of {temperature} K exceeds the physical ceiling, beyond which the linear model does not hold"
+            ),
+        }
+    }
+}
+
+impl Error for CeilingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Bounds(error) => Some(error),
+            Self::Exceeded { .. } => None,
+        }
+    }
+}
+
+impl From<BoundsError> for CeilingError {
+    fn from(error: BoundsError) -> Self {
+        Self::Bounds(error)
+    }
+}
+
+/// An error encountered while calculating a temperature rise with one of the
+/// `*_cancellable` methods of [`struct@MultiLayer`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum CancellableError {
+    /// The bounds of the integral are invalid
+    Bounds(BoundsError),
+
+    /// The integration was cancelled, which is always reported as
+    /// [`QuadratureError::Cancelled`] with the partial result. Integrals
+    /// which otherwise fail to meet the tolerance are returned as they are by
+    /// `Quadrature::integrate`
+    Quadrature(QuadratureError<Float>),
+}
+
+impl fmt::Display for CancellableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bounds(error) => write!(f, "{error}"),
+            Self::Quadrature(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for CancellableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Bounds(error) => Some(error),
+            Self::Quadrature(error) => Some(error),
+        }
+    }
+}
+
+impl From<BoundsError> for CancellableError {
+    fn from(error: BoundsError) -> Self {
+        Self::Bounds(error)
+    }
+}
+
+impl From<QuadratureError<Float>> for CancellableError {
+    fn from(error: QuadratureError<Float>) -> Self {
+        Self::Quadrature(error)
+    }
+}
+
+/// An error encountered while evaluating a [`trait@Beam`] with
+/// `Beam::try_evaluate_with`
+#[derive(Clone, PartialEq, Debug)]
+pub enum EvalError {
+    /// A term of the evaluation is infinite or NaN, e.g. as the result of a
+    /// division by a zero `rho * c`
+    NonFinite {
+        /// A description of the term
+        term: &'static str,
+
+        /// The value the term evaluated to
+        value: Float,
+    },
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite { term, value } => {
+                write!(
+                    f,
+                    "the term {term} evaluated to {value}, which is not finite"
+                )
+            }
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+/// Returns [`EvalError::NonFinite`] if `value` is infinite or NaN
+fn check_finite(term: &'static str, value: &Float) -> Result<(), EvalError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(EvalError::NonFinite {
+            term,
+            value: value.clone(),
+        })
+    }
+}
+
+/// Warns if `thermal_properties` are invalid (see
+/// [`ThermalProperties::validate`]), as the result will then be infinite,
+/// NaN, or nonphysical
+///
+/// This is done once per call to a function such as [`fn@temperature_rise`]
+/// rather than once per evaluation of the integrand. As with
+/// [`fn@check_precision`], it is only done in debug builds with the `tracing`
+/// feature enabled, and otherwise does nothing
+#[cfg_attr(
+    not(all(feature = "tracing", debug_assertions)),
+    allow(unused_variables)
+)]
+fn check_thermal_properties(thermal_properties: &ThermalProperties<'_>) {
+    #[cfg(all(feature = "tracing", debug_assertions))]
+    if let Err(error) = thermal_properties.validate() {
+        tracing::warn!(%error, "thermal properties are invalid");
+    }
+}
+
+/// Warns of each input whose precision is below the requested `precision`,
+/// as the result can be no more accurate than its least precise input
+///
+/// Each of `layers` is checked, but `thermal_properties` and `inputs` are
+/// only checked once, so a stack of layers should be checked with a single
+/// call rather than one per layer
+///
+/// This is only done in debug builds with the `tracing` feature enabled, and
+/// otherwise does nothing
+#[cfg_attr(
+    not(all(feature = "tracing", debug_assertions)),
+    allow(unused_variables)
+)]
+fn check_precision<'a, 'b: 'a>(
+    precision: u64,
+    thermal_properties: &'a ThermalProperties<'_>,
+    layers: impl IntoIterator<Item = &'a Layer<'b>>,
+    inputs: impl IntoIterator<Item = (&'static str, &'a Float)>,
+) {
+    #[cfg(all(feature = "tracing", debug_assertions))]
+    for (input, value) in [
+        ("rho", thermal_properties.rho.as_ref()),
+        ("c", thermal_properties.c.as_ref()),
+        ("k", thermal_properties.k.as_ref()),
+    ]
+    .into_iter()
+    .chain(layers.into_iter().flat_map(|layer| {
+        [
+            ("d", layer.d.as_ref()),
+            ("z0", layer.z0.as_ref()),
+            ("mu_a", layer.mu_a.as_ref()),
+            ("e0", layer.e0.as_ref()),
+        ]
+        .into_iter()
+        .chain(layer.n.as_deref().map(|n| ("n", n)))
+    }))
+    .chain(inputs)
+    {
+        if value.prec_64() < precision {
+            tracing::warn!(
+                input,
+                input_precision = value.prec_64(),
+                precision,
+                "input precision is below the requested precision"
+            );
         }
     }
 }
 
+/// Calculates the fraction of irradiance transmitted across an interface
+/// between media with the refractive indices `n_1` and `n_2` at normal
+/// incidence, `1 - ((n_1 - n_2) / (n_1 + n_2))^2`
+fn fresnel_transmittance(n_1: &Float, n_2: &Float) -> Float {
+    let mut reflectance = Float::with_val_64(n_1.prec_64().max(n_2.prec_64()), n_1 - n_2);
+    reflectance /= Float::with_val_64(reflectance.prec_64(), n_1 + n_2);
+    reflectance.square_mut();
+    1 - reflectance
+}
+
 /// Multiple layers of tissue
 #[derive(Clone, PartialEq, Debug)]
-pub struct MultiLayer {
+pub struct MultiLayer<'a> {
     /// The layers this [`struct@MultiLayer`] is composed of
-    layers: Vec<Layer<'static>>,
+    layers: Vec<Layer<'a>>,
 }
 
-impl MultiLayer {
+impl<'a> MultiLayer<'a> {
     /// Creates a new [`struct@MultiLayer`] from multiple [`struct@Layer`]s
     ///
     /// If the input layers are not sorted in order of incidence, they are
     /// sorted. Irradiance is taken from the topmost layer and propagated
-    /// downward according to Beer's Law
+    /// downward according to Beer's Law. If two adjacent layers both have a
+    /// refractive index, only the fraction `T = 1 - R` of the irradiance is
+    /// transmitted across the interface between them, where `R = ((n_1 -
+    /// n_2) / (n_1 + n_2))^2` is the Fresnel reflectance at normal incidence.
+    /// Any gap between the layers is disregarded, and multiple reflections
+    /// are not accounted for
     ///
-    /// If the input layers overlap in any way, [`None`] is returned
-    pub fn new<'a>(input_layers: impl IntoIterator<Item = Layer<'a>>) -> Option<Self> {
-        let input_layers = input_layers.into_iter();
-        let mut layers = Vec::with_capacity(input_layers.size_hint().0);
+    /// If the input layers overlap in any way, [`MultiLayerError::Overlap`] is
+    /// returned. The layers are kept as they are given, so borrowed fields
+    /// (such as those of layers from an [`struct@crate::interner::Interner`])
+    /// stay borrowed, except for the `e0` of each layer below the topmost one
+    /// and any snapped `z0` (see [`MultiLayer::new_with_tolerance`]), which
+    /// are computed here
+    pub fn new(input_layers: impl IntoIterator<Item = Layer<'a>>) -> Result<Self, MultiLayerError> {
+        Self::new_with_tolerance(input_layers, &Float::new_64(1))
+    }
 
-        for layer in input_layers {
-            layers.push(layer.into_owned());
+    /// Creates a new [`struct@MultiLayer`] from a single [`struct@Layer`]
+    ///
+    /// This is the same as [`MultiLayer::new`] with only one layer, which can
+    /// never fail as there is nothing for it to overlap
+    pub fn single(layer: Layer<'a>) -> Self {
+        Self {
+            layers: vec![layer],
         }
+    }
+
+    /// Creates a new [`struct@MultiLayer`] from multiple [`struct@Layer`]s,
+    /// permitting overlaps of up to `tolerance` (units: cm)
+    ///
+    /// This is the same as [`MultiLayer::new`], except that a layer which
+    /// begins no more than `tolerance` before the bottom of the layer above it
+    /// is treated as touching it, and its `z0` is snapped to that bottom. This
+    /// is useful for boundaries which overlap slightly due to rounding.
+    /// Overlaps greater than `tolerance` are still rejected with
+    /// [`MultiLayerError::Overlap`]
+    ///
+    /// If the `tracing` feature is enabled, the order the layers are sorted
+    /// into, each snapped `z0`, each Fresnel transmittance applied, and each
+    /// irradiance propagated downward are emitted as `DEBUG` level events
+    pub fn new_with_tolerance(
+        input_layers: impl IntoIterator<Item = Layer<'a>>,
+        tolerance: &Float,
+    ) -> Result<Self, MultiLayerError> {
+        let mut layers = input_layers.into_iter().collect::<Vec<_>>();
 
         layers.sort_by(|a, b| a.z0.total_cmp(b.z0.as_ref()));
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            order = %layers
+                .iter()
+                .map(|layer| layer.z0.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            "sorted layers by z0",
+        );
+
         if let Some(layer) = layers.first() {
             let mut e0 = layer.e0.clone().into_owned();
+            let mut n = layer.n.clone();
 
             let mut z0 = layer.z0.clone().into_owned();
             z0 += layer.d.as_ref();
@@ -84,9 +875,43 @@ impl MultiLayer {
 
             for layer in layers.iter_mut().skip(1) {
                 if layer.z0.as_ref() < &z0 {
-                    return None;
+                    b.assign(&z0 - layer.z0.as_ref());
+
+                    if &b > tolerance {
+                        return Err(MultiLayerError::Overlap {
+                            z0: layer.z0.clone().into_owned(),
+                            bottom: z0,
+                        });
+                    }
+
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        from = %layer.z0,
+                        to = %z0,
+                        overlap = %b,
+                        "snapped layer to the bottom of the layer above",
+                    );
+
+                    layer.z0.to_mut().assign(&z0);
                 }
 
+                if let (Some(n_1), Some(n_2)) = (&n, &layer.n) {
+                    let transmittance = fresnel_transmittance(n_1, n_2);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        z0 = %layer.z0,
+                        %transmittance,
+                        "applied fresnel transmittance",
+                    );
+
+                    e0 *= transmittance;
+                }
+                n.clone_from(&layer.n);
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(z0 = %layer.z0, e0 = %e0, "propagated irradiance");
+
                 layer.e0.to_mut().assign(&e0);
 
                 z0.assign(layer.z0.as_ref());
@@ -100,37 +925,353 @@ impl MultiLayer {
             }
         }
 
-        Some(Self { layers })
+        Ok(Self { layers })
     }
 
-    //TODO: add a method for updating e0
-
-    /// Runs the given [`trait@Beam`] over the contained [`struct@Layer`]s
-    /// with the provided [`struct@ThermalProperties`]
+    /// Creates a new [`struct@MultiLayer`] approximating a region of total
+    /// thickness `d_total` beginning at `z0` with a continuously varying
+    /// absorption coefficient
     ///
-    /// Not all implementations of [`trait@Beam`] will use all parameters
-    pub fn evaluate_with(
-        &self,
+    /// The region is split into `n` sublayers of equal thickness, each with
+    /// `mu_a` sampled at its midpoint. `e0` is the irradiance incident on the
+    /// top of the region, which is propagated downward across the sublayers
+    /// as in [`MultiLayer::new`]
+    pub fn discretize(
         precision: u64,
-        beam: &impl Beam,
-        thermal_properties: &ThermalProperties<'_>,
-        z: &Float,
-        r: &Float,
-        tp: &Float,
-    ) -> Float {
-        let mut sum = Float::with_val_64(precision, Special::Zero);
+        d_total: &Float,
+        z0: &Float,
+        mu_a: impl Fn(&Float) -> Float,
+        e0: &Float,
+        n: usize,
+    ) -> Self {
+        let mut d = Float::with_val_64(precision, d_total);
+        d /= n as u64;
 
-        for layer in &self.layers {
-            sum += beam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
-        }
+        let mut half_d = d.clone();
+        half_d /= 2.0;
 
-        sum
-    }
+        let mut layers = Vec::with_capacity(n);
+        let mut top = Float::with_val_64(precision, z0);
+        let mut midpoint = Float::new_64(precision);
+
+        for _ in 0..n {
+            midpoint.assign(&top);
+            midpoint += &half_d;
+
+            layers.push(Layer {
+                d: Cow::Owned(d.clone()),
+                z0: Cow::Owned(top.clone()),
+                mu_a: Cow::Owned(mu_a(&midpoint)),
+                e0: Cow::Owned(Float::with_val_64(precision, e0)),
+                n: None,
+            });
+
+            // this is computed the same way as the bottom of each layer is in
+            // MultiLayer::new, so the sublayers never overlap due to rounding
+
+            top += &d;
+        }
+
+        Self::new(layers).expect("discretized layers never overlap")
+    }
+
+    /// Calculates the irradiance (units: W*cm^-2) reaching the depth `z`
+    /// (units: cm) according to Beer's Law
+    ///
+    /// Within a layer, the irradiance is attenuated from the `e0` at its top
+    /// by its `mu_a`. Above the stack, this is the incident irradiance, and
+    /// nothing is absorbed in gaps between layers or below the stack, so
+    /// there it is the irradiance leaving the bottom of the layer above
+    pub fn irradiance_at(&self, z: &Float, precision: u64) -> Float {
+        let mut irradiance = Float::with_val_64(precision, Special::Zero);
+        let mut depth = Float::new_64(precision);
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            if *z < *layer.z0 {
+                if i == 0 {
+                    irradiance.assign(layer.e0.as_ref());
+                }
+
+                break;
+            }
+
+            // the depth over which the layer attenuates the irradiance
+
+            depth.assign(z - layer.z0.as_ref());
+            depth.min_mut(layer.d.as_ref());
+            depth *= layer.mu_a.as_ref();
+            depth *= -1;
+            depth.exp_mut();
+
+            irradiance.assign(layer.e0.as_ref());
+            irradiance *= &depth;
+        }
+
+        irradiance
+    }
+
+    /// The irradiance (units: W*cm^-2) incident on the top of the stack, as
+    /// originally supplied
+    ///
+    /// Beer's Law (and any Fresnel transmittance) is only applied across the
+    /// layers below the topmost one, whose `e0` is the irradiance at its top
+    /// and is left as it was given, so this is that `e0` (rescaled by any
+    /// [`MultiLayer::scale_irradiance`] since). There is no attenuation above
+    /// the stack to undo. It is zero for an empty stack
+    pub fn incident_irradiance(&self, precision: u64) -> Float {
+        self.layers.first().map_or_else(
+            || Float::with_val_64(precision, Special::Zero),
+            |layer| Float::with_val_64(precision, layer.e0.as_ref()),
+        )
+    }
+
+    /// Calculates the power density absorbed at the depth `z` (units: cm),
+    /// `mu_a` times the irradiance reaching it (see
+    /// [`MultiLayer::irradiance_at`]). Units: W*cm^-3
+    ///
+    /// This is the source term of the temperature rise before any heat is
+    /// conducted away, i.e. at `tp = 0`. Each layer spans `z0..z0 + d`, so at
+    /// a boundary between two layers the lower one is used, and outside of
+    /// every layer nothing is absorbed
+    pub fn absorbed_power_density(&self, z: &Float, precision: u64) -> Float {
+        let mut density = Float::with_val_64(precision, Special::Zero);
+
+        let Some(layer) = self.layers.iter().find(|layer| {
+            let mut bottom = Float::with_val_64(precision, layer.z0.as_ref());
+            bottom += layer.d.as_ref();
+
+            *z >= *layer.z0 && *z < bottom
+        }) else {
+            return density;
+        };
+
+        density.assign(z - layer.z0.as_ref());
+        density *= layer.mu_a.as_ref();
+        density *= -1;
+        density.exp_mut();
+        density *= layer.e0.as_ref();
+        density *= layer.mu_a.as_ref();
+        density
+    }
+
+    /// Calculates the absorbed power density (see
+    /// [`MultiLayer::absorbed_power_density`]) at each of `depths`
+    pub fn absorbed_power_density_profile(&self, depths: &[Float], precision: u64) -> Vec<Float> {
+        depths
+            .iter()
+            .map(|z| self.absorbed_power_density(z, precision))
+            .collect()
+    }
+
+    /// Calculates the fraction of the irradiance incident on the top of the
+    /// stack which is absorbed across all of its layers
+    ///
+    /// Each layer absorbs `e0 * (1 - exp(-mu_a * d))` of the irradiance
+    /// reaching it, and the irradiances are already propagated downward (and
+    /// reduced by any reflection between layers), so without reflection this
+    /// is `1 - exp(-sum(mu_a * d))`. This depends only upon the layers, not
+    /// the beam or time. It is zero for an empty stack and NaN if the
+    /// incident irradiance is zero
+    pub fn absorbed_fraction(&self, precision: u64) -> Float {
+        let mut absorbed = Float::with_val_64(precision, Special::Zero);
+
+        let Some(top) = self.layers.first() else {
+            return absorbed;
+        };
+
+        let mut transmitted = Float::new_64(precision);
+
+        for layer in &self.layers {
+            // exp_m1 keeps the fraction absorbed by thin layers accurate
+
+            transmitted.assign(layer.d.as_ref() * layer.mu_a.as_ref());
+            transmitted *= -1;
+            transmitted.exp_m1_mut();
+            transmitted *= layer.e0.as_ref();
+            absorbed -= &transmitted;
+        }
+
+        absorbed /= top.e0.as_ref();
+        absorbed
+    }
+
+    /// Compares the layers of `self` with those of `other`, reporting each
+    /// of `d`, `z0`, `mu_a`, and `e0` which differ by more than `tolerance`
+    /// along with any layers only present in one of the two
+    ///
+    /// Layers are paired by their index in order of incidence, so inserting
+    /// a layer part of the way down the stack is reported as changes to each
+    /// layer below it followed by an added layer. As irradiance is propagated
+    /// downward, changing the `mu_a` or `d` of one layer also changes the
+    /// `e0` of those below it. Refractive indices are not compared
+    pub fn diff(&self, other: &MultiLayer<'_>, tolerance: &Float) -> Vec<LayerDiff> {
+        let mut differences = Vec::new();
+
+        for (index, (from, to)) in self.layers.iter().zip(&other.layers).enumerate() {
+            for (field, from, to) in [
+                ("d", &from.d, &to.d),
+                ("z0", &from.z0, &to.z0),
+                ("mu_a", &from.mu_a, &to.mu_a),
+                ("e0", &from.e0, &to.e0),
+            ] {
+                let mut difference = Float::with_val_64(
+                    from.prec_64().max(to.prec_64()),
+                    from.as_ref() - to.as_ref(),
+                );
+                difference.abs_mut();
+
+                // a NaN on either side is still a difference
+
+                if difference.is_nan() || difference > *tolerance {
+                    differences.push(LayerDiff::Changed {
+                        index,
+                        field,
+                        from: from.clone().into_owned(),
+                        to: to.clone().into_owned(),
+                    });
+                }
+            }
+        }
+
+        let common = self.layers.len().min(other.layers.len());
+
+        for (index, layer) in self.layers.iter().enumerate().skip(common) {
+            differences.push(LayerDiff::Removed {
+                index,
+                layer: layer.clone().into_owned(),
+            });
+        }
+
+        for (index, layer) in other.layers.iter().enumerate().skip(common) {
+            differences.push(LayerDiff::Added {
+                index,
+                layer: layer.clone().into_owned(),
+            });
+        }
+
+        differences
+    }
+
+    /// Creates a copy of `self` with every contained [`struct@Layer`]
+    /// re-created at the given precision (see [`Layer::with_precision`])
+    ///
+    /// The layers are copied as they are rather than being constructed anew,
+    /// so the irradiances propagated at the original precision are kept
+    pub fn with_precision(&self, precision: u64) -> Self {
+        Self {
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| layer.with_precision(precision))
+                .collect(),
+        }
+    }
+
+    /// Multiplies the irradiance of every contained [`struct@Layer`] by
+    /// `factor`, such as to model attenuation by a filter before the tissue
+    /// (see [`struct@Attenuator`])
+    ///
+    /// The ratios between the irradiances of the layers given by Beer's Law
+    /// are preserved, so nothing needs to be propagated again
+    pub fn scale_irradiance(&mut self, factor: &Float) {
+        for layer in &mut self.layers {
+            *layer.e0.to_mut() *= factor;
+        }
+    }
+
+    //TODO: add a method for updating e0
+
+    /// Runs the given [`trait@Beam`] over the contained [`struct@Layer`]s
+    /// with the provided [`struct@ThermalProperties`]
+    ///
+    /// Not all implementations of [`trait@Beam`] will use all parameters.
+    /// Layers which receive no irradiance (an `e0` of zero) contribute
+    /// nothing, so they are skipped
+    ///
+    /// The contribution of each layer is its Green's function evaluated at
+    /// `z`, wherever that lies, so a point within a layer which absorbs
+    /// nothing (a `mu_a` of zero, e.g. vitreous above the retina) still
+    /// warms as heat is conducted to it from its absorbing neighbors, even
+    /// though that layer's own contribution is zero
+    pub fn evaluate_with(
+        &self,
+        precision: u64,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+
+        for layer in self.layers.iter().filter(|layer| !layer.e0.is_zero()) {
+            sum += beam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+        }
+
+        sum
+    }
+
+    /// Runs the given [`trait@Beam`] over the contained [`struct@Layer`]s as
+    /// in [`MultiLayer::evaluate_with`], summing the contributions of each
+    /// layer with compensated (Neumaier) summation
+    ///
+    /// This only matters when the contributions span several orders of
+    /// magnitude, e.g. for stacks of dozens of thin layers deep into an
+    /// absorbing medium, where the low bits of the smaller contributions are
+    /// otherwise lost as they are added to the larger ones. For a handful of
+    /// layers, [`MultiLayer::evaluate_with`] is just as accurate
+    pub fn evaluate_with_compensated(
+        &self,
+        precision: u64,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        utilities::neumaier_sum(
+            self.layers
+                .iter()
+                .filter(|layer| !layer.e0.is_zero())
+                .map(|layer| beam.evaluate_with(precision, thermal_properties, layer, z, r, tp)),
+            precision,
+        )
+    }
+
+    /// Runs the given [`trait@Beam`] over each of the contained
+    /// [`struct@Layer`]s individually, returning the contribution of each in
+    /// order of incidence
+    ///
+    /// These are the terms summed by [`MultiLayer::evaluate_with`], so their
+    /// sum (in order) is its result. As there, layers which receive no
+    /// irradiance are skipped, contributing exactly zero
+    pub fn evaluate_breakdown(
+        &self,
+        precision: u64,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Vec<Float> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                if layer.e0.is_zero() {
+                    return Float::with_val_64(precision, Special::Zero);
+                }
+
+                beam.evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            })
+            .collect()
+    }
 
     /// Calculates the temperature rise over the interval a..b
     ///
     /// Similar to [`fn@temperature_rise`], this is really just a convenience
-    /// wrapper over `Quadrature::integrate`
+    /// wrapper over `Quadrature::integrate`, and the bounds are interpreted
+    /// and validated in the same way
+    #[allow(clippy::too_many_arguments)]
     pub fn temperature_rise(
         &self,
         precision: u64,
@@ -141,249 +1282,8221 @@ impl MultiLayer {
         r: &Float,
         epsilon: &Float,
         bounds: (&Float, &Float),
-    ) -> (Float, Float) {
-        quadrature.integrate(
+    ) -> Result<(Float, Float), BoundsError> {
+        check_thermal_properties(thermal_properties);
+        check_precision(
+            precision,
+            thermal_properties,
+            &self.layers,
+            [("z", z), ("r", r), ("start", bounds.0), ("end", bounds.1)],
+        );
+
+        integrate_bounds(
+            precision,
+            quadrature,
             |t| self.evaluate_with(precision, beam, thermal_properties, z, r, &t),
             epsilon,
             bounds,
         )
     }
-}
 
-//TODO: we could probably swap the use of [`struct@Float`] for a generic
-//      parameter that implements the operation traits in rug::ops in most
-//      (if not all) places
+    /// Calculates the temperature rise at `observation_time` resulting from
+    /// an exposure lasting from `exposure.0` to `exposure.1`
+    ///
+    /// This is the same as [`fn@temperature_rise_exposure`], and the
+    /// exposure window is interpreted and validated in the same way
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_exposure(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        exposure: (&Float, &Float),
+        observation_time: &Float,
+    ) -> Result<(Float, Float), BoundsError> {
+        let (a, b) = exposure_bounds(precision, exposure, observation_time)?;
 
-/// An abstraction over the various `*Beam` structures
-pub trait Beam {
-    /// Run the beam over a given [`struct@Layer`] with the provided
-    /// [`struct@ThermalProperties`]
+        self.temperature_rise(
+            precision,
+            quadrature,
+            beam,
+            thermal_properties,
+            z,
+            r,
+            epsilon,
+            (&a, &b),
+        )
+    }
+
+    /// Calculates the temperature rise over the interval a..b as in
+    /// [`MultiLayer::temperature_rise`], stopping early once `cancel` is set
     ///
-    /// Not all implementations of [`trait@Beam`] will use all parameters
-    fn evaluate_with<'a>(
+    /// The integral is computed with `Quadrature::try_integrate_cancellable`,
+    /// so if `cancel` is set before it completes,
+    /// [`QuadratureError::Cancelled`] is returned with the partial result
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_cancellable(
         &self,
         precision: u64,
-        thermal_properties: &ThermalProperties<'a>,
-        layer: &Layer<'a>,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        bounds: (&Float, &Float),
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), CancellableError> {
+        check_thermal_properties(thermal_properties);
+        check_precision(
+            precision,
+            thermal_properties,
+            &self.layers,
+            [("z", z), ("r", r), ("start", bounds.0), ("end", bounds.1)],
+        );
+
+        integrate_bounds_cancellable(
+            precision,
+            quadrature,
+            |t| self.evaluate_with(precision, beam, thermal_properties, z, r, &t),
+            epsilon,
+            bounds,
+            cancel,
+        )
+    }
+
+    /// Calculates the temperature rise at `observation_time` resulting from
+    /// an exposure lasting from `exposure.0` to `exposure.1` as in
+    /// [`MultiLayer::temperature_rise_exposure`], stopping early once
+    /// `cancel` is set as in [`MultiLayer::temperature_rise_cancellable`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_exposure_cancellable(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
         z: &Float,
         r: &Float,
+        epsilon: &Float,
+        exposure: (&Float, &Float),
+        observation_time: &Float,
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), CancellableError> {
+        let (a, b) = exposure_bounds(precision, exposure, observation_time)?;
+
+        self.temperature_rise_cancellable(
+            precision,
+            quadrature,
+            beam,
+            thermal_properties,
+            z,
+            r,
+            epsilon,
+            (&a, &b),
+            cancel,
+        )
+    }
+
+    /// Calculates the temperature rise over the interval a..b at every point
+    /// (z, r) of the grid formed by `depths` and `radii`
+    ///
+    /// The result has a row for each depth, each of which has a value for
+    /// each radial distance. Depths are iterated over in the outer loop, so if
+    /// `beam` is radially uniform (see `Beam::is_radially_uniform`), the
+    /// temperature rise is only calculated once per depth and reused across
+    /// the row. Invalid bounds are reported as in
+    /// [`MultiLayer::temperature_rise`]
+    ///
+    /// Nothing else is shared between the integrations. The factors which
+    /// `LargeBeam::prepare` precomputes are specific to
+    /// [`struct@LargeBeam`], while this is generic over beams. A
+    /// [`struct@TimePlan`] also needs its times before integrating, but
+    /// `quadrature` chooses them adaptively, and differently at each depth
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_field(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        depths: &[Float],
+        radii: &[Float],
+        epsilon: &Float,
+        bounds: (&Float, &Float),
+    ) -> Result<Vec<Vec<Float>>, BoundsError> {
+        let radially_uniform = beam.is_radially_uniform();
+        let mut field = Vec::with_capacity(depths.len());
+
+        for z in depths {
+            let mut row = Vec::with_capacity(radii.len());
+
+            for r in radii {
+                let value = match row.first() {
+                    Some(value) if radially_uniform => Float::with_val_64(precision, value),
+                    _ => {
+                        self.temperature_rise(
+                            precision,
+                            quadrature,
+                            beam,
+                            thermal_properties,
+                            z,
+                            r,
+                            epsilon,
+                            bounds,
+                        )?
+                        .0
+                    }
+                };
+
+                row.push(value);
+            }
+
+            field.push(row);
+        }
+
+        Ok(field)
+    }
+
+    /// Calculates the temperature rise over the interval a..b at every point
+    /// (z, r) of the grid as in [`MultiLayer::temperature_field`], spreading
+    /// the cells across threads with `rayon`
+    ///
+    /// The cells are independent of one another, and each is computed exactly
+    /// as it is by [`MultiLayer::temperature_field`], so the results are
+    /// identical and in the same order regardless of how the work is divided
+    #[cfg(feature = "rayon")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_field_parallel(
+        &self,
+        precision: u64,
+        quadrature: &(impl Quadrature<Float> + Sync),
+        beam: &(impl Beam + Sync),
+        thermal_properties: &ThermalProperties<'_>,
+        depths: &[Float],
+        radii: &[Float],
+        epsilon: &Float,
+        bounds: (&Float, &Float),
+    ) -> Result<Vec<Vec<Float>>, BoundsError> {
+        let radially_uniform = beam.is_radially_uniform();
+
+        let cell = |z: &Float, r: &Float| {
+            self.temperature_rise(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                bounds,
+            )
+            .map(|(value, _)| value)
+        };
+
+        depths
+            .par_iter()
+            .map(|z| match radii.first() {
+                Some(r) if radially_uniform => {
+                    let value = cell(z, r)?;
+                    Ok(vec![value; radii.len()])
+                }
+                _ => radii.par_iter().map(|r| cell(z, r)).collect(),
+            })
+            .collect()
+    }
+
+    /// Calculates the thermal energy deposited by the time `tp` within the
+    /// region spanning `z_bounds` in depth and `r_bounds` in radial distance,
+    /// returning it alongside the approximate error of the outermost
+    /// integration. Units: J
+    ///
+    /// This is `rho * c` times the integral of the temperature rise over
+    /// 0..tp (as in [`MultiLayer::temperature_rise`]) over the region, with
+    /// the radial integrand weighted by `2 * pi * r`. All three integrals are
+    /// computed with `quadrature`, nested such that every evaluation of the
+    /// integrand over z is an integral over r, every evaluation of which is
+    /// in turn an integral over time. The cost is therefore roughly the cube
+    /// of that of a single integration, so this is best used with modest
+    /// tolerances. If `beam` is radially uniform (see
+    /// `Beam::is_radially_uniform`), the integral over r is instead computed
+    /// analytically
+    ///
+    /// All three sets of bounds are validated as in
+    /// [`MultiLayer::temperature_rise`], with the bounds over time being
+    /// 0..tp. As radial distances are nonnegative, a negative lower bound
+    /// in `r_bounds` is also rejected, where the weight of `2 * pi * r`
+    /// would otherwise count the region below zero as negative energy
+    #[allow(clippy::too_many_arguments)]
+    pub fn integrated_energy(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
         tp: &Float,
-    ) -> Float;
-}
+        epsilon: &Float,
+        z_bounds: (&Float, &Float),
+        r_bounds: (&Float, &Float),
+    ) -> Result<(Float, Float), BoundsError> {
+        let zero = Float::with_val_64(precision, Special::Zero);
+
+        check_bounds((&zero, tp))?;
+        check_bounds(r_bounds)?;
+
+        if *r_bounds.0 < 0 {
+            return Err(BoundsError::NegativeLower {
+                a: r_bounds.0.clone(),
+            });
+        }
+
+        let temperature_rise = |z: &Float, r: &Float| {
+            self.temperature_rise(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                (&zero, tp),
+            )
+            .expect("the bounds over time were validated")
+            .0
+        };
+
+        let radially_uniform = beam.is_radially_uniform();
+
+        // 2 * pi * r integrated over r_bounds
+
+        let mut area = Float::with_val_64(precision, r_bounds.1.square_ref());
+        area -= Float::with_val_64(precision, r_bounds.0.square_ref());
+        area *= Float::with_val_64(precision, Constant::Pi);
+
+        let (mut energy, mut error) = integrate_bounds(
+            precision,
+            quadrature,
+            |z| {
+                if radially_uniform {
+                    return temperature_rise(&z, r_bounds.0) * &area;
+                }
+
+                integrate_bounds(
+                    precision,
+                    quadrature,
+                    |r| {
+                        let mut weight = Float::with_val_64(precision, Constant::Pi);
+                        weight *= 2;
+                        weight *= &r;
+
+                        temperature_rise(&z, &r) * weight
+                    },
+                    epsilon,
+                    r_bounds,
+                )
+                .expect("the radial bounds were validated")
+                .0
+            },
+            epsilon,
+            z_bounds,
+        )?;
+
+        let mut heat_capacity = Float::with_val_64(precision, thermal_properties.rho.as_ref());
+        heat_capacity *= thermal_properties.c.as_ref();
+
+        energy *= &heat_capacity;
+        error *= &heat_capacity;
+
+        Ok((energy, error))
+    }
+
+    /// Calculates the temperature rise at each of the provided times, with
+    /// integration beginning at `start`
+    ///
+    /// Similar to [`fn@temperature_rise_series`], each point is computed
+    /// incrementally from the previous one, so `times` must be sorted in
+    /// ascending order and must not precede `start`
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_series(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        start: &Float,
+        times: &[Float],
+    ) -> Vec<(Float, Float)> {
+        self.temperature_rise_series_with_progress(
+            precision,
+            quadrature,
+            beam,
+            thermal_properties,
+            z,
+            r,
+            epsilon,
+            start,
+            times,
+            |_| (),
+        )
+    }
+
+    /// Calculates the temperature rise at each of the provided times, with
+    /// integration beginning at `start`, reporting progress along the way
+    ///
+    /// This is the same as [`MultiLayer::temperature_rise_series`], except
+    /// that `progress` is called with the number of completed time points
+    /// each time one is completed
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_series_with_progress(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        start: &Float,
+        times: &[Float],
+        progress: impl FnMut(usize),
+    ) -> Vec<(Float, Float)> {
+        integrate_series(
+            precision,
+            quadrature,
+            |t| self.evaluate_with(precision, beam, thermal_properties, z, r, &t),
+            epsilon,
+            start,
+            times,
+            progress,
+        )
+    }
+
+    /// Calculates the temperature rise at each of the provided times, with
+    /// integration beginning at `start`, without collecting the results
+    ///
+    /// This is the same as [`MultiLayer::temperature_rise_series`], except
+    /// that the results are passed to `callback` as they are computed, as in
+    /// [`fn@temperature_rise_stream`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_stream(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        start: &Float,
+        times: &[Float],
+        callback: impl FnMut(usize, &Float, &Float),
+    ) {
+        integrate_stream(
+            precision,
+            quadrature,
+            |t| self.evaluate_with(precision, beam, thermal_properties, z, r, &t),
+            epsilon,
+            start,
+            times,
+            callback,
+        )
+    }
+
+    /// Calculates the temperature rise at each of the provided times as in
+    /// [`MultiLayer::temperature_rise_stream`], stopping early once `cancel`
+    /// is set
+    ///
+    /// `callback` is only called for the times completed before `cancel` was
+    /// set. The partial result returned with [`QuadratureError::Cancelled`]
+    /// is the temperature rise as of the time the integration stopped short
+    /// of, that is the sum up to the last completed time plus the partial
+    /// integral beyond it, with the largest approximate error of any interval
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature_rise_stream_cancellable(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        start: &Float,
+        times: &[Float],
+        mut callback: impl FnMut(usize, &Float, &Float),
+        cancel: &AtomicBool,
+    ) -> Result<(), CancellableError> {
+        let f =
+            check_integrand(|t| self.evaluate_with(precision, beam, thermal_properties, z, r, &t));
+
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+        let mut error = Float::with_val_64(precision, Special::Zero);
+        let mut previous = start;
+
+        for (i, time) in times.iter().enumerate() {
+            if time != previous {
+                let (integral, interval_error) = match integrate_cancellable(
+                    quadrature,
+                    &f,
+                    epsilon,
+                    (previous, time),
+                    cancel,
+                ) {
+                    Ok(result) => result,
+                    Err((integral, interval_error)) => {
+                        sum += integral;
+                        error.max_mut(&interval_error);
+
+                        return Err(CancellableError::Quadrature(QuadratureError::Cancelled {
+                            value: sum,
+                            error,
+                        }));
+                    }
+                };
+
+                sum += integral;
+                error.max_mut(&interval_error);
+            }
+
+            callback(i, &sum, &error);
+            previous = time;
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the absolute temperature over the interval a..b given a
+    /// baseline temperature
+    ///
+    /// Similar to [`fn@temperature`], this is the same as
+    /// [`MultiLayer::temperature_rise`] with `baseline` added to the
+    /// resulting temperature rise
+    #[allow(clippy::too_many_arguments)]
+    pub fn temperature(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        baseline: &Float,
+        epsilon: &Float,
+        bounds: (&Float, &Float),
+    ) -> Result<(Float, Float), BoundsError> {
+        let (mut temperature, error) = self.temperature_rise(
+            precision,
+            quadrature,
+            beam,
+            thermal_properties,
+            z,
+            r,
+            epsilon,
+            bounds,
+        )?;
+
+        temperature += baseline;
+
+        Ok((temperature, error))
+    }
+
+    /// Finds the earliest time `tp` within `bounds` at which the absolute
+    /// temperature (the temperature rise over 0..tp plus `baseline`) reaches
+    /// `threshold`. Units: s
+    ///
+    /// The temperature rise is assumed not to decrease over `bounds`, as is
+    /// the case for any beam which is continuously on, and the crossing is
+    /// found by bisection until it is known to within a relative tolerance of
+    /// `epsilon` (which is also used for each integration). Rather than
+    /// integrating over 0..tp afresh at each step, the temperature rise up to
+    /// the lower end of the bracket is kept and only the remainder is
+    /// integrated. `None` is returned if the threshold is never reached
+    /// within `bounds`
+    ///
+    /// `bounds` are validated as in [`MultiLayer::temperature_rise`], with
+    /// the additional requirements that they be nonnegative and finite, and
+    /// a [`enum@BoundsError`] is returned if they are invalid
+    #[allow(clippy::too_many_arguments)]
+    pub fn time_to_threshold(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        threshold: &Float,
+        baseline: &Float,
+        epsilon: &Float,
+        bounds: (&Float, &Float),
+    ) -> Result<Option<Float>, BoundsError> {
+        check_exposure_bounds(bounds)?;
+
+        let rise = |a: &Float, b: &Float| {
+            self.temperature_rise(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                (a, b),
+            )
+            .map(|(rise, _)| rise)
+        };
+
+        let mut remaining = Float::with_val_64(precision, threshold);
+        remaining -= baseline;
+
+        let mut lower = Float::with_val_64(precision, bounds.0);
+        let mut lower_rise = rise(&Float::with_val_64(precision, Special::Zero), &lower)?;
+
+        if lower_rise >= remaining {
+            return Ok(Some(lower));
+        }
+
+        let mut upper = Float::with_val_64(precision, bounds.1);
+
+        if Float::with_val_64(precision, &lower_rise + rise(&lower, &upper)?) < remaining {
+            return Ok(None);
+        }
+
+        // each iteration halves the bracket, so after `precision` of them it
+        // can't be narrowed any further
+
+        for _ in 0..precision {
+            let mut tolerance = Float::with_val_64(precision, &upper);
+            tolerance *= epsilon;
+
+            if Float::with_val_64(precision, &upper - &lower) <= tolerance {
+                break;
+            }
+
+            let mut middle = Float::with_val_64(precision, &lower + &upper);
+            middle /= 2;
+
+            let mut middle_rise = rise(&lower, &middle)?;
+            middle_rise += &lower_rise;
+
+            if middle_rise >= remaining {
+                upper = middle;
+            } else {
+                lower = middle;
+                lower_rise = middle_rise;
+            }
+        }
+
+        Ok(Some(upper))
+    }
+
+    /// Finds how long (units: s) the temperature rise following a pulse
+    /// lasting from a time of 0 to `pulse_width` takes to fall from its peak
+    /// to half of it, characterizing how quickly the point cools
+    ///
+    /// The temperature rise at each observation time is calculated as in
+    /// [`MultiLayer::temperature_rise_exposure`]. It can only increase while
+    /// the pulse is ongoing, so its peak is searched for between the later of
+    /// `bounds.0` and `pulse_width` and `bounds.1`, with `utilities::minimize`
+    /// starting from the middle of them. The time at which it falls to half
+    /// of the peak is then found by bisection between the peak and
+    /// `bounds.1`. Each is found to within a relative tolerance of `epsilon`
+    /// (which is also used for each integration), and the temperature rise is
+    /// assumed to rise to a single peak and then only fall, as it does after
+    /// a pulse
+    ///
+    /// `None` is returned if the temperature rise doesn't fall to half of the
+    /// peak by `bounds.1`, if the peak is not positive, or if `bounds` ends
+    /// before the pulse does. A [`enum@BoundsError`] is returned if `bounds`
+    /// is invalid (as in [`MultiLayer::time_to_threshold`]) or if
+    /// `pulse_width` is negative
+    #[allow(clippy::too_many_arguments)]
+    pub fn decay_half_time(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        r: &Float,
+        epsilon: &Float,
+        pulse_width: &Float,
+        bounds: (&Float, &Float),
+    ) -> Result<Option<Float>, BoundsError> {
+        let zero = Float::with_val_64(precision, Special::Zero);
+
+        check_exposure_bounds(bounds)?;
+        check_exposure_bounds((&zero, pulse_width))?;
+
+        if bounds.1 <= pulse_width {
+            return Ok(None);
+        }
+
+        let rise = |observation_time: &Float| {
+            self.temperature_rise_exposure(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                (&zero, pulse_width),
+                observation_time,
+            )
+            .map(|(rise, _)| rise)
+        };
+
+        let mut lower = Float::with_val_64(precision, bounds.0);
+        lower.max_mut(pulse_width);
+        let upper = Float::with_val_64(precision, bounds.1);
+
+        // the peak is the minimum of the negated temperature rise. the search
+        // may step outside of `bounds`, which is harmless as the temperature
+        // rise is defined at every time, but its result is kept within them
+
+        let mut initial = Float::with_val_64(precision, &lower + &upper);
+        initial /= 2;
+
+        let mut peak_time = utilities::minimize(precision, &initial, epsilon, |t| {
+            rise(t).map_or_else(
+                |_| Float::with_val_64(precision, Special::Nan),
+                |rise| -rise,
+            )
+        });
+        peak_time.clamp_mut(&lower, &upper);
+
+        let mut half = rise(&peak_time)?;
+
+        if half <= 0 {
+            return Ok(None);
+        }
+        half /= 2;
+
+        let mut lower = peak_time.clone();
+        let mut upper = Float::with_val_64(precision, bounds.1);
+
+        if rise(&upper)? > half {
+            return Ok(None);
+        }
+
+        // as in `MultiLayer::time_to_threshold`, each iteration halves the
+        // bracket, so after `precision` of them it can't be narrowed any
+        // further
+
+        for _ in 0..precision {
+            let mut tolerance = Float::with_val_64(precision, &upper);
+            tolerance *= epsilon;
+
+            if Float::with_val_64(precision, &upper - &lower) <= tolerance {
+                break;
+            }
+
+            let mut middle = Float::with_val_64(precision, &lower + &upper);
+            middle /= 2;
+
+            if rise(&middle)? <= half {
+                upper = middle;
+            } else {
+                lower = middle;
+            }
+        }
+
+        upper -= peak_time;
+        Ok(Some(upper))
+    }
+
+    /// Calculates the full width at half maximum of the radial profile of
+    /// the temperature rise over 0..tp at the depth `z`. Units: cm
+    ///
+    /// The maximum is taken to be on the axis, as it is for any beam whose
+    /// intensity doesn't increase away from it. A bracket around the radial
+    /// distance at which the temperature rise falls to half of that is
+    /// expanded by factors of 2 from the diffusion length `sqrt(4 * alpha *
+    /// tp)`, then narrowed by bisection until it is known to within a
+    /// relative tolerance of `epsilon` (which is also used for each
+    /// integration)
+    ///
+    /// As there is no radial falloff for a radially uniform `beam` (such as
+    /// [`struct@LargeBeam`]), positive infinity is returned for one, as it is
+    /// if the bracket can't be expanded to contain the half maximum within
+    /// `precision` expansions. NaN is returned if the temperature rise on the
+    /// axis is not positive, and a [`enum@BoundsError`] is returned if `tp`
+    /// is invalid (as in [`MultiLayer::temperature_rise`])
+    #[allow(clippy::too_many_arguments)]
+    pub fn radial_fwhm(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        tp: &Float,
+        epsilon: &Float,
+    ) -> Result<Float, BoundsError> {
+        let zero = Float::with_val_64(precision, Special::Zero);
+
+        check_bounds((&zero, tp))?;
+
+        if beam.is_radially_uniform() {
+            return Ok(Float::with_val_64(precision, Special::Infinity));
+        }
+
+        let rise = |r: &Float| {
+            self.temperature_rise(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                (&zero, tp),
+            )
+            .map(|(rise, _)| rise)
+        };
+
+        let mut half = rise(&zero)?;
+
+        if half <= 0 || half.is_nan() {
+            return Ok(Float::with_val_64(precision, Special::Nan));
+        }
+        half /= 2;
+
+        //TODO: same todo as the one in FlatTopBeam
+        let mut upper = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        upper /= thermal_properties.rho.as_ref();
+        upper /= thermal_properties.c.as_ref();
+        upper *= tp;
+        upper *= 4;
+        upper.sqrt_mut();
+
+        let mut lower = zero.clone();
+
+        for i in 0..=precision {
+            if rise(&upper).expect("the bounds were already checked") <= half {
+                break;
+            }
+
+            if i == precision {
+                return Ok(Float::with_val_64(precision, Special::Infinity));
+            }
+
+            lower.assign(&upper);
+            upper *= 2;
+        }
+
+        // as in `MultiLayer::time_to_threshold`, each iteration halves the
+        // bracket, so after `precision` of them it can't be narrowed any
+        // further
+
+        for _ in 0..precision {
+            let mut tolerance = Float::with_val_64(precision, &upper);
+            tolerance *= epsilon;
+
+            if Float::with_val_64(precision, &upper - &lower) <= tolerance {
+                break;
+            }
+
+            let mut middle = Float::with_val_64(precision, &lower + &upper);
+            middle /= 2;
+
+            if rise(&middle).expect("the bounds were already checked") <= half {
+                upper = middle;
+            } else {
+                lower = middle;
+            }
+        }
+
+        // the full width is twice the midpoint of the bracket
+
+        lower += upper;
+        Ok(lower)
+    }
+
+    /// Finds the depth at which the temperature rise over 0..tp at the
+    /// radial distance `r` falls to `1/e` of its value at the surface (the
+    /// top of the topmost layer). Units: cm
+    ///
+    /// The bottoms of the layers are checked downward through the stack until
+    /// one is found at which the temperature rise has fallen to `1/e` of the
+    /// surface value, and the crossing between it and the depth checked before
+    /// it is then found by bisection until it is known to within a relative
+    /// tolerance of `epsilon` (which is also used for each integration), as in
+    /// [`MultiLayer::time_to_threshold`]. `None` is returned if the
+    /// temperature rise doesn't fall that low within the stack, or if the
+    /// temperature rise at the surface is not positive. A
+    /// [`enum@BoundsError`] is returned if `tp` is invalid (as in
+    /// [`MultiLayer::temperature_rise`])
+    #[allow(clippy::too_many_arguments)]
+    pub fn penetration_depth_1e(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        r: &Float,
+        tp: &Float,
+        epsilon: &Float,
+    ) -> Result<Option<Float>, BoundsError> {
+        let zero = Float::with_val_64(precision, Special::Zero);
+
+        check_bounds((&zero, tp))?;
+
+        let rise = |z: &Float| {
+            self.temperature_rise(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                (&zero, tp),
+            )
+            .map(|(rise, _)| rise)
+        };
+
+        let Some(first) = self.layers.first() else {
+            return Ok(None);
+        };
+        let mut lower = Float::with_val_64(precision, first.z0.as_ref());
+
+        let mut target = rise(&lower)?;
+
+        if target <= 0 || target.is_nan() {
+            return Ok(None);
+        }
+
+        let mut e = Float::with_val_64(precision, 1);
+        e.exp_mut();
+        target /= e;
+
+        let mut upper = Float::new_64(precision);
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            upper.assign(layer.z0.as_ref());
+            upper += layer.d.as_ref();
+
+            if rise(&upper)? <= target {
+                break;
+            }
+
+            if i == self.layers.len() - 1 {
+                return Ok(None);
+            }
+
+            lower.assign(&upper);
+        }
+
+        // as in `MultiLayer::time_to_threshold`, each iteration halves the
+        // bracket, so after `precision` of them it can't be narrowed any
+        // further
+
+        for _ in 0..precision {
+            let mut tolerance = Float::with_val_64(precision, upper.abs_ref());
+            tolerance *= epsilon;
+
+            if Float::with_val_64(precision, &upper - &lower) <= tolerance {
+                break;
+            }
+
+            let mut middle = Float::with_val_64(precision, &lower + &upper);
+            middle /= 2;
+
+            if rise(&middle)? <= target {
+                upper = middle;
+            } else {
+                lower = middle;
+            }
+        }
+
+        lower += upper;
+        lower /= 2;
+        Ok(Some(lower))
+    }
+
+    /// Calculates the centroid `(z, r)` of the temperature rise over 0..tp
+    /// within a planar section through the axis, spanned by `z_bounds` and
+    /// `r_bounds`. Units: cm
+    ///
+    /// This is the centroid of a planar section, not of a volume. `r` is a
+    /// signed coordinate along a line through the axis, and negative values
+    /// lie on the other side of it (beams here are radially symmetric, so a
+    /// negative `r` is as far from the axis as its absolute value). Each
+    /// coordinate is its mean over the section weighted by the temperature
+    /// rise alone, without the `2 * pi * r` Jacobian of a volume integral,
+    /// and is found by integrating with `quadrature` (nested within the
+    /// integration over time). As such, the radial centroid is only nonzero
+    /// if the beam is off-center within `r_bounds`, which is what makes it
+    /// useful for checking the pointing of a beam
+    ///
+    /// `epsilon` is used for every integration. NaN is returned for both
+    /// coordinates if no heat is deposited within the section, and a
+    /// [`enum@BoundsError`] is returned if `tp` is invalid (as in
+    /// [`MultiLayer::temperature_rise`])
+    #[allow(clippy::too_many_arguments)]
+    pub fn thermal_centroid(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        beam: &impl Beam,
+        thermal_properties: &ThermalProperties<'_>,
+        tp: &Float,
+        epsilon: &Float,
+        z_bounds: (&Float, &Float),
+        r_bounds: (&Float, &Float),
+    ) -> Result<(Float, Float), BoundsError> {
+        let zero = Float::with_val_64(precision, Special::Zero);
+
+        check_bounds((&zero, tp))?;
+
+        let rise = |z: &Float, r: &Float| {
+            self.temperature_rise(
+                precision,
+                quadrature,
+                beam,
+                thermal_properties,
+                z,
+                r,
+                epsilon,
+                (&zero, tp),
+            )
+            .expect("the bounds were already checked")
+            .0
+        };
+
+        // the integral over the region of the temperature rise, weighted by
+        // z, r, or neither
+
+        let moment = |weight_z: bool, weight_r: bool| {
+            quadrature
+                .integrate(
+                    |z| {
+                        let mut inner = quadrature
+                            .integrate(
+                                |r| {
+                                    let mut value = rise(&z, &r);
+                                    if weight_r {
+                                        value *= r;
+                                    }
+                                    value
+                                },
+                                epsilon,
+                                r_bounds,
+                            )
+                            .0;
+
+                        if weight_z {
+                            inner *= z;
+                        }
+
+                        inner
+                    },
+                    epsilon,
+                    z_bounds,
+                )
+                .0
+        };
+
+        let total = moment(false, false);
+
+        if total.is_zero() || total.is_nan() {
+            return Ok((
+                Float::with_val_64(precision, Special::Nan),
+                Float::with_val_64(precision, Special::Nan),
+            ));
+        }
+
+        let mut z = moment(true, false);
+        z /= &total;
+
+        let mut r = moment(false, true);
+        r /= &total;
+
+        Ok((z, r))
+    }
+}
+
+impl fmt::Display for MultiLayer<'_> {
+    /// Writes each of the contained [`struct@Layer`]s on its own line, in
+    /// order of incidence
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, layer) in self.layers.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{layer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An optical element (e.g. a filter or lens) in the path of the beam before
+/// the tissue, passing a known fraction of the irradiance incident on it
+#[derive(Clone, PartialEq, Debug)]
+pub struct Attenuator<'a> {
+    /// The fraction of the irradiance transmitted, expected to be within
+    /// `0..=1`. Units: dimensionless
+    pub transmission: Cow<'a, Float>,
+}
+
+impl Attenuator<'static> {
+    /// Creates a new [`struct@Attenuator`] from an `f64`, converting it to
+    /// an owned [`struct@Float`] at the given precision
+    pub fn from_f64(precision: u64, transmission: f64) -> Self {
+        Self {
+            transmission: Cow::Owned(Float::with_val_64(precision, transmission)),
+        }
+    }
+
+    /// Combines the given attenuators, in the order the beam passes through
+    /// them, into one transmitting the product of their transmissions
+    ///
+    /// An empty stack transmits everything
+    pub fn stack<'b>(
+        precision: u64,
+        attenuators: impl IntoIterator<Item = &'b Attenuator<'b>>,
+    ) -> Self {
+        let mut transmission = Float::with_val_64(precision, 1.0);
+
+        for attenuator in attenuators {
+            transmission *= attenuator.transmission.as_ref();
+        }
+
+        Self {
+            transmission: Cow::Owned(transmission),
+        }
+    }
+}
+
+impl<'a> Attenuator<'a> {
+    /// Combines `self` with `next`, which the beam passes through after it
+    /// (see [`Attenuator::stack`])
+    pub fn then(&self, next: &Attenuator<'_>, precision: u64) -> Attenuator<'static> {
+        Attenuator::stack(precision, [self, next])
+    }
+
+    /// Calculates the irradiance (units: W*cm^-2) leaving `self` given the
+    /// `irradiance` (units: W*cm^-2) incident on it
+    pub fn attenuate(&self, irradiance: &Float, precision: u64) -> Float {
+        let mut attenuated = Float::with_val_64(precision, irradiance);
+        attenuated *= self.transmission.as_ref();
+        attenuated
+    }
+
+    /// Applies `self` to the irradiance entering the given
+    /// [`struct@MultiLayer`] (see [`MultiLayer::scale_irradiance`])
+    pub fn apply(&self, multi_layer: &mut MultiLayer) {
+        multi_layer.scale_irradiance(self.transmission.as_ref());
+    }
+}
+
+impl<'a> fmt::Display for Attenuator<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transmission = {:.6}", self.transmission)
+    }
+}
+
+//TODO: we could probably swap the use of [`struct@Float`] for a generic
+//      parameter that implements the operation traits in rug::ops in most
+//      (if not all) places
+
+/// The upper limit on intervals used by the default implementation of
+/// `Beam::steady_state`
+pub const STEADY_STATE_INTERVAL_LIMIT: u64 = 1024;
+
+/// An abstraction over the various `*Beam` structures
+pub trait Beam {
+    /// Run the beam over a given [`struct@Layer`] with the provided
+    /// [`struct@ThermalProperties`]
+    ///
+    /// Not all implementations of [`trait@Beam`] will use all parameters
+    fn evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float;
+
+    /// Run the beam as in `Beam::evaluate_with`, checking that no term of the
+    /// evaluation is infinite or NaN
+    ///
+    /// `Beam::evaluate_with` does no such checking, so degenerate inputs (such
+    /// as a zero `rho`) silently produce non-finite results there. By default,
+    /// only the thermal diffusivity and the result are checked
+    ///
+    /// In debug builds with the `tracing` feature enabled, a warning is also
+    /// emitted for each input with a precision below `precision`, as it caps
+    /// the accuracy of the result
+    fn try_evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Result<Float, EvalError> {
+        check_precision(
+            precision,
+            thermal_properties,
+            [layer],
+            [("z", z), ("r", r), ("tp", tp)],
+        );
+
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+        check_finite("alpha = k / (rho * c)", &alpha)?;
+
+        let result = self.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+        check_finite("result", &result)?;
+
+        Ok(result)
+    }
+
+    /// Whether the result of `Beam::evaluate_with` is independent of `r`,
+    /// allowing results to be reused across radial distances
+    ///
+    /// By default, this is `false`
+    fn is_radially_uniform(&self) -> bool {
+        false
+    }
+
+    /// Run the beam as in `Beam::evaluate_with` at each of the points
+    /// `(z, r, tp)`, returning the results in the same order
+    ///
+    /// By default, this simply calls `Beam::evaluate_with` for each point,
+    /// but implementations may hoist work shared between points out of the
+    /// loop. Those which do so per depth benefit most when consecutive points
+    /// share a depth
+    fn evaluate_many<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        points: &[(Float, Float, Float)],
+    ) -> Vec<Float> {
+        points
+            .iter()
+            .map(|(z, r, tp)| self.evaluate_with(precision, thermal_properties, layer, z, r, tp))
+            .collect()
+    }
+
+    /// Run the beam as in `Beam::evaluate_with` with each of `properties`,
+    /// returning the results in the same order
+    ///
+    /// By default, this simply calls `Beam::evaluate_with` for each set of
+    /// [`struct@ThermalProperties`], but implementations may hoist work which
+    /// doesn't depend upon them out of the loop. This is useful for studying
+    /// the sensitivity of the result to `rho`, `c`, and `k`
+    fn evaluate_over_properties<'a>(
+        &self,
+        precision: u64,
+        properties: &[ThermalProperties<'a>],
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Vec<Float> {
+        properties
+            .iter()
+            .map(|thermal_properties| {
+                self.evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            })
+            .collect()
+    }
+
+    /// Calculates the steady state temperature rise under a continuous
+    /// exposure, the limit of the integral of `Beam::evaluate_with` over
+    /// 0..tp as `tp` approaches infinity. Units: K
+    ///
+    /// By default, `Beam::evaluate_with` is integrated over 0..inf with
+    /// Gauss-Kronrod quadrature (see [`fn@quadrature::finite_bounds`]) to a
+    /// relative tolerance of [`fn@quadrature::default_epsilon`], subdividing
+    /// into at most [`STEADY_STATE_INTERVAL_LIMIT`] intervals.
+    /// Implementations with closed forms should override this. Where there is
+    /// no steady state, as for [`struct@LargeBeam`], the result is infinite
+    fn steady_state<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+    ) -> Float {
+        let quadrature = quadrature::GaussKronrod {
+            interval_limit: STEADY_STATE_INTERVAL_LIMIT,
+            precision,
+            rule: &quadrature::G7_K15,
+        };
+
+        quadrature
+            .integrate(
+                |tp| self.evaluate_with(precision, thermal_properties, layer, z, r, &tp),
+                quadrature::default_epsilon(precision),
+                (
+                    Float::with_val_64(precision, Special::Zero),
+                    Float::with_val_64(precision, Special::Infinity),
+                ),
+            )
+            .0
+    }
+
+    /// The factor of `Beam::evaluate_with` depending upon `r`, by which the
+    /// result of `LargeBeam::evaluate_with` (the factor depending upon the
+    /// depth) is multiplied. This is 1 for [`struct@LargeBeam`] itself
+    ///
+    /// By default, this is the ratio of the two at the top of a semi-infinite
+    /// layer, which is exact for any beam whose result is separable as such
+    /// (as all of those in this module other than [`struct@UniformBeam`]
+    /// are). Implementations with a closed form should override this
+    fn radial_factor<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let layer = Layer::from_f64(precision, f64::INFINITY, 0.0, 1.0, 1.0);
+        let z = Float::with_val_64(precision, Special::Zero);
+
+        let mut factor = self.evaluate_with(precision, thermal_properties, &layer, &z, r, tp);
+        factor /= LargeBeam.evaluate_with(precision, thermal_properties, &layer, &z, r, tp);
+        factor
+    }
+}
+
+/// Calculates the steady state of a beam whose integrand approaches a
+/// nonzero limit or decays too slowly to be integrable, given its value at
+/// any time at which it is nonzero wherever it ever will be
+///
+/// This is infinite with the sign of `rise`, or `rise` itself if it is zero
+/// or NaN
+fn unbounded_steady_state(precision: u64, rise: Float) -> Float {
+    if rise.is_zero() || rise.is_nan() {
+        return rise;
+    }
+
+    let mut steady_state = Float::with_val_64(precision, Special::Infinity);
+    if rise.is_sign_negative() {
+        steady_state *= -1;
+    }
+
+    steady_state
+}
+
+/// A beam much wider than the region of interest, such that heat is only
+/// conducted in depth
+///
+/// As conduction is one-dimensional, there is no steady state. The
+/// temperature rise under a continuous exposure (the integral of
+/// `LargeBeam::evaluate_with` over 0..tp) grows without bound, eventually in
+/// proportion to `sqrt(tp)`, so its integral over all time diverges rather
+/// than having a closed form, and a finite upper bound must be given to
+/// [`fn@temperature_rise`]
+#[derive(Clone, PartialEq, Debug)]
+pub struct LargeBeam;
+
+impl Beam for LargeBeam {
+    fn is_radially_uniform(&self) -> bool {
+        true
+    }
+
+    //TODO: it (might?) be worthwhile to have a specialized method that
+    //      doesn't need to take r. however, this could also be addressed with
+    //      the genericization of this method at the trait level. see above
+    //      for more details
+    fn evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        _r: &Float,
+        tp: &Float,
+    ) -> Float {
+        self.prepare(precision, thermal_properties, layer, z)
+            .evaluate_at_time(tp)
+    }
+
+    fn try_evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Result<Float, EvalError> {
+        check_precision(
+            precision,
+            thermal_properties,
+            [layer],
+            [("z", z), ("r", r), ("tp", tp)],
+        );
+
+        self.prepare(precision, thermal_properties, layer, z)
+            .try_evaluate_at_time(tp)
+    }
+
+    fn evaluate_many<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        points: &[(Float, Float, Float)],
+    ) -> Vec<Float> {
+        self.map_prepared(
+            precision,
+            thermal_properties,
+            layer,
+            points,
+            |beam, _, tp| beam.evaluate_at_time(tp),
+        )
+    }
+
+    /// The factors of the result which depend only upon the layer and the
+    /// depth are computed once and shared across `properties`
+    fn evaluate_over_properties<'a>(
+        &self,
+        precision: u64,
+        properties: &[ThermalProperties<'a>],
+        layer: &Layer<'a>,
+        z: &Float,
+        _r: &Float,
+        tp: &Float,
+    ) -> Vec<Float> {
+        let factors = DepthFactors::new(precision, layer, z);
+
+        properties
+            .iter()
+            .map(|thermal_properties| {
+                factors
+                    .prepare(precision, thermal_properties)
+                    .evaluate_at_time(tp)
+            })
+            .collect()
+    }
+
+    /// There is no steady state (see [`struct@LargeBeam`]), so this is
+    /// infinite wherever heat is ever conducted to, which is everywhere
+    /// unless the layer deposits nothing or there is no conduction
+    fn steady_state<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+    ) -> Float {
+        // any positive time will do, as the integrand is nonzero at every
+        // depth once any time has passed (and constant without conduction)
+
+        let rise = self.evaluate_with(
+            precision,
+            thermal_properties,
+            layer,
+            z,
+            r,
+            &Float::with_val_64(precision, 1),
+        );
+
+        unbounded_steady_state(precision, rise)
+    }
+
+    fn radial_factor<'a>(
+        &self,
+        precision: u64,
+        _thermal_properties: &ThermalProperties<'a>,
+        _r: &Float,
+        _tp: &Float,
+    ) -> Float {
+        Float::with_val_64(precision, 1)
+    }
+}
+
+/// A [`struct@LargeBeam`] run over a fixed [`struct@Layer`] with fixed
+/// [`struct@ThermalProperties`] at a fixed depth, with all factors
+/// independent of time precomputed
+///
+/// This is useful when integrating over time, as the integrand may capture a
+/// [`struct@PreparedBeam`] rather than calling `LargeBeam::evaluate_with`
+/// afresh at every time
+#[derive(Clone, PartialEq, Debug)]
+pub struct PreparedBeam {
+    /// Floating point precision (in bits) for MPFR floats
+    precision: u64,
+
+    /// Thermal diffusivity. Units: cm^2*s^-1
+    alpha: Float,
+
+    /// Units: cm^-1
+    mu_a: Float,
+
+    /// The square of `mu_a`. Units: cm^-2
+    mu_a_squared: Float,
+
+    /// The product of the terms independent of time. Units: K*s^-1
+    coefficient: Float,
+
+    /// The distance from the point to the bottom of the layer. Units: cm
+    bottom: Float,
+
+    /// The distance from the point to the top of the layer. Units: cm
+    top: Float,
+
+    /// Whether the layer receives any irradiance (a nonzero `e0`)
+    deposits: bool,
+}
+
+impl PreparedBeam {
+    /// Calculates the result of `LargeBeam::evaluate_with` at the time `tp`
+    pub fn evaluate_at_time(&self, tp: &Float) -> Float {
+        // a layer which receives no irradiance deposits nothing, and skipping
+        // the rest also avoids 0 * inf for otherwise degenerate layers
+
+        if !self.deposits {
+            return Float::with_val_64(self.precision, Special::Zero);
+        }
+
+        if *tp == 0 || self.alpha == 0 {
+            return self.initial();
+        }
+
+        self.evaluate_with_factors(&TimeFactors::new(
+            self.precision,
+            &self.alpha,
+            &self.mu_a,
+            &self.mu_a_squared,
+            tp,
+        ))
+    }
+
+    /// Calculates the result of `LargeBeam::try_evaluate_with` at the time
+    /// `tp`, reporting the first term which is infinite or NaN
+    pub fn try_evaluate_at_time(&self, tp: &Float) -> Result<Float, EvalError> {
+        if !self.deposits {
+            return Ok(Float::with_val_64(self.precision, Special::Zero));
+        }
+
+        check_finite("alpha = k / (rho * c)", &self.alpha)?;
+        check_finite(
+            "mu_a * e0 / (2 * rho * c) * exp(-mu_a * (z - z0))",
+            &self.coefficient,
+        )?;
+
+        if *tp == 0 || self.alpha == 0 {
+            return Ok(self.initial());
+        }
+
+        let (term_3, term_4) = self.terms_at_time(tp);
+        check_finite("exp(mu_a^2 * alpha * tp)", &term_3)?;
+        check_finite("the difference of the error functions", &term_4)?;
+
+        let result = Float::with_val_64(self.precision, &self.coefficient) * term_3 * term_4;
+        check_finite("result", &result)?;
+
+        Ok(result)
+    }
+
+    /// Calculates the result at a time of 0, at which heat has not yet
+    /// diffused anywhere
+    ///
+    /// This is the limit of the result as `tp` approaches 0, which is zero
+    /// outside of the layer and halved on its boundaries. Without conduction
+    /// (a zero `alpha`), heat never diffuses, so this is also the result at
+    /// every other time, where the general form would divide by zero
+    fn initial(&self) -> Float {
+        let mut result = Float::with_val_64(self.precision, &self.coefficient);
+        result *= sign(&self.bottom) - sign(&self.top);
+        result
+    }
+
+    /// Calculates the two factors of the result which depend upon the time
+    /// `tp`, which must be nonzero
+    fn terms_at_time(&self, tp: &Float) -> (Float, Float) {
+        let factors = TimeFactors::new(
+            self.precision,
+            &self.alpha,
+            &self.mu_a,
+            &self.mu_a_squared,
+            tp,
+        );
+        let term_4 = self.erf_term(&factors);
+
+        (factors.term_3, term_4)
+    }
+
+    /// Calculates the difference of the error functions, the only factor of
+    /// the result which depends upon both the depth and the time
+    fn erf_term(&self, factors: &TimeFactors) -> Float {
+        let mut argument_1 = Float::with_val_64(self.precision, &self.bottom);
+        argument_1 *= &factors.reciprocal_sqrt;
+        argument_1 += &factors.sqrt_mu_a;
+
+        let mut argument_2 = Float::with_val_64(self.precision, &self.top);
+        argument_2 *= &factors.reciprocal_sqrt;
+        argument_2 += &factors.sqrt_mu_a;
+
+        erf_difference(self.precision, &argument_1, &argument_2)
+    }
+
+    /// Calculates the result at a nonzero time given the factors
+    /// precomputed for it
+    fn evaluate_with_factors(&self, factors: &TimeFactors) -> Float {
+        let term_4 = self.erf_term(factors);
+
+        Float::with_val_64(self.precision, &self.coefficient) * &factors.term_3 * term_4
+    }
+}
+
+/// The factors of `LargeBeam::evaluate_with` which depend only upon the time
+/// and the [`struct@Layer`] (and [`struct@ThermalProperties`]), and not upon
+/// the depth
+#[derive(Clone, PartialEq, Debug)]
+struct TimeFactors {
+    /// `exp(mu_a^2 * alpha * tp)`
+    term_3: Float,
+
+    /// `1 / sqrt(4 * alpha * tp)`. Units: cm^-1
+    reciprocal_sqrt: Float,
+
+    /// `mu_a * sqrt(alpha * tp)`
+    sqrt_mu_a: Float,
+}
+
+impl TimeFactors {
+    fn new(precision: u64, alpha: &Float, mu_a: &Float, mu_a_squared: &Float, tp: &Float) -> Self {
+        let mut term_3 = Float::with_val_64(precision, mu_a_squared);
+        term_3 *= tp;
+        term_3 *= alpha;
+        term_3.exp_mut();
+
+        let mut reciprocal_sqrt = Float::with_val_64(precision, alpha);
+        reciprocal_sqrt *= tp;
+        reciprocal_sqrt *= 4.0;
+        reciprocal_sqrt.sqrt_mut();
+        reciprocal_sqrt.recip_mut();
+
+        let mut sqrt_mu_a = Float::with_val_64(precision, alpha);
+        sqrt_mu_a *= tp;
+        sqrt_mu_a.sqrt_mut();
+        sqrt_mu_a *= mu_a;
+
+        Self {
+            term_3,
+            reciprocal_sqrt,
+            sqrt_mu_a,
+        }
+    }
+}
+
+/// A plan for evaluating a [`struct@LargeBeam`] over a fixed
+/// [`struct@Layer`] with fixed [`struct@ThermalProperties`] at a fixed set of
+/// times, such as the nodes of a quadrature rule, at many depths
+///
+/// Of the factors of `LargeBeam::evaluate_with`, the exponential and the
+/// scaling of the error functions' arguments depend only upon the time and
+/// the layer, so they are computed once per time when the plan is made
+/// rather than once per point. This trades three floats per time (at the
+/// plan's precision) of memory for skipping an `exp` and two `sqrt`s at
+/// every evaluation, which pays off when the number of depths evaluated at
+/// is large relative to the number of times. Only the error functions
+/// remain to be computed for each point
+#[derive(Clone, PartialEq, Debug)]
+pub struct TimePlan {
+    /// Floating point precision (in bits) for MPFR floats
+    precision: u64,
+
+    /// Thermal diffusivity. Units: cm^2*s^-1
+    alpha: Float,
+
+    /// Units: cm^-1
+    mu_a: Float,
+
+    /// The times planned for. Units: s
+    times: Vec<Float>,
+
+    /// The factors precomputed for each of `times`
+    factors: Vec<TimeFactors>,
+}
+
+impl TimePlan {
+    /// The times planned for. Units: s
+    pub fn times(&self) -> &[Float] {
+        &self.times
+    }
+
+    /// Calculates the result of `PreparedBeam::evaluate_at_time` at each of
+    /// the planned times, in order
+    ///
+    /// `beam` must have been prepared with the same precision,
+    /// [`struct@Layer`], and [`struct@ThermalProperties`] as the plan
+    pub fn evaluate(&self, beam: &PreparedBeam) -> Vec<Float> {
+        debug_assert_eq!(self.precision, beam.precision);
+
+        if !beam.deposits {
+            return vec![Float::with_val_64(self.precision, Special::Zero); self.times.len()];
+        }
+
+        debug_assert_eq!(self.alpha, beam.alpha);
+        debug_assert_eq!(self.mu_a, beam.mu_a);
+
+        self.times
+            .iter()
+            .zip(&self.factors)
+            .map(|(tp, factors)| {
+                if *tp == 0 || beam.alpha == 0 {
+                    beam.initial()
+                } else {
+                    beam.evaluate_with_factors(factors)
+                }
+            })
+            .collect()
+    }
+}
+
+/// The sign of `x` as an integer, where the sign of zero (and NaN) is 0
+fn sign(x: &Float) -> i32 {
+    match x.cmp0() {
+        Some(Ordering::Greater) => 1,
+        Some(Ordering::Less) => -1,
+        _ => 0,
+    }
+}
+
+/// Calculates `erf(a) - erf(b)`, where `a >= b`
+///
+/// When both arguments are far from zero, their error functions are very
+/// close to 1 or -1 and the difference loses most (or all) of its precision,
+/// so the complementary error function is used in those cases instead. This
+/// occurs for points above the layer and once `mu_a^2 * alpha * tp` is large
+fn erf_difference(precision: u64, a: &Float, b: &Float) -> Float {
+    if b.is_sign_positive() {
+        // erf(a) - erf(b) = erfc(b) - erfc(a)
+
+        let mut difference = Float::with_val_64(precision, b.erfc_ref());
+        difference -= Float::with_val_64(precision, a.erfc_ref());
+        difference
+    } else if a.is_sign_negative() {
+        // erf(a) - erf(b) = erfc(-a) - erfc(-b)
+
+        let mut difference = Float::with_val_64(precision, -a);
+        difference.erfc_mut();
+        difference -= Float::with_val_64(precision, -b).erfc();
+        difference
+    } else {
+        let mut difference = Float::with_val_64(precision, a.erf_ref());
+        difference -= Float::with_val_64(precision, b.erf_ref());
+        difference
+    }
+}
+
+/// The factors of `LargeBeam::evaluate_with` which depend only upon the
+/// [`struct@Layer`] and the depth, and not upon the time or the
+/// [`struct@ThermalProperties`]
+#[derive(Clone, PartialEq, Debug)]
+struct DepthFactors {
+    /// Units: cm^-1
+    mu_a: Float,
+
+    /// The square of `mu_a`. Units: cm^-2
+    mu_a_squared: Float,
+
+    /// `mu_a * e0`. Units: W*cm^-3
+    mu_a_e0: Float,
+
+    /// `exp(-mu_a * (z - z0))`
+    term_2: Float,
+
+    /// The distance from the point to the bottom of the layer. Units: cm
+    bottom: Float,
+
+    /// The distance from the point to the top of the layer. Units: cm
+    top: Float,
+
+    /// Whether the layer receives any irradiance (a nonzero `e0`)
+    deposits: bool,
+}
+
+impl DepthFactors {
+    fn new(precision: u64, layer: &Layer<'_>, z: &Float) -> Self {
+        let mut mu_a_e0 = Float::with_val_64(precision, layer.mu_a.as_ref());
+        mu_a_e0 *= layer.e0.as_ref();
+
+        let mut term_2 = Float::with_val_64(precision, z);
+        term_2 -= layer.z0.as_ref();
+        term_2 *= layer.mu_a.as_ref();
+        term_2 *= -1;
+        term_2.exp_mut();
+
+        let mut bottom = Float::with_val_64(precision, layer.z0.as_ref());
+        bottom += layer.d.as_ref();
+        bottom -= z;
+
+        let mut top = Float::with_val_64(precision, layer.z0.as_ref());
+        top -= z;
+
+        Self {
+            mu_a: Float::with_val_64(precision, layer.mu_a.as_ref()),
+            mu_a_squared: Float::with_val_64(precision, layer.mu_a.square_ref()),
+            mu_a_e0,
+            term_2,
+            bottom,
+            top,
+            deposits: !layer.e0.is_zero(),
+        }
+    }
+
+    /// Completes the factors independent of time with the given
+    /// [`struct@ThermalProperties`]
+    fn prepare(&self, precision: u64, thermal_properties: &ThermalProperties<'_>) -> PreparedBeam {
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        let mut term_1 = Float::with_val_64(precision, &self.mu_a_e0);
+        term_1 /= thermal_properties.rho.as_ref();
+        term_1 /= thermal_properties.c.as_ref();
+        term_1 /= 2.0;
+
+        PreparedBeam {
+            precision,
+            alpha,
+            mu_a: self.mu_a.clone(),
+            mu_a_squared: self.mu_a_squared.clone(),
+            coefficient: term_1 * &self.term_2,
+            bottom: self.bottom.clone(),
+            top: self.top.clone(),
+            deposits: self.deposits,
+        }
+    }
+}
+
+impl LargeBeam {
+    /// Precomputes the factors of `LargeBeam::evaluate_with` which are
+    /// independent of time for the given [`struct@Layer`],
+    /// [`struct@ThermalProperties`], and depth
+    pub fn prepare(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'_>,
+        layer: &Layer<'_>,
+        z: &Float,
+    ) -> PreparedBeam {
+        DepthFactors::new(precision, layer, z).prepare(precision, thermal_properties)
+    }
+
+    /// Plans the evaluation of the beam over the given [`struct@Layer`] with
+    /// the given [`struct@ThermalProperties`] at each of `times` (see
+    /// [`struct@TimePlan`])
+    pub fn plan(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'_>,
+        layer: &Layer<'_>,
+        times: &[Float],
+    ) -> TimePlan {
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        let mu_a = Float::with_val_64(precision, layer.mu_a.as_ref());
+        let mu_a_squared = Float::with_val_64(precision, layer.mu_a.square_ref());
+
+        TimePlan {
+            precision,
+            factors: times
+                .iter()
+                .map(|tp| TimeFactors::new(precision, &alpha, &mu_a, &mu_a_squared, tp))
+                .collect(),
+            times: times
+                .iter()
+                .map(|tp| Float::with_val_64(precision, tp))
+                .collect(),
+            alpha,
+            mu_a,
+        }
+    }
+
+    /// Maps each of the points `(z, r, tp)` with `f`, which is given a
+    /// [`struct@PreparedBeam`] for the point's depth along with `r` and `tp`
+    ///
+    /// A new [`struct@PreparedBeam`] is only prepared when the depth differs
+    /// from that of the previous point
+    fn map_prepared<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        points: &[(Float, Float, Float)],
+        mut f: impl FnMut(&PreparedBeam, &Float, &Float) -> Float,
+    ) -> Vec<Float> {
+        let mut prepared: Option<(&Float, PreparedBeam)> = None;
+
+        points
+            .iter()
+            .map(|(z, r, tp)| {
+                let beam = match &mut prepared {
+                    Some((depth, beam)) if *depth == z => beam,
+                    prepared => {
+                        &prepared
+                            .insert((z, self.prepare(precision, thermal_properties, layer, z)))
+                            .1
+                    }
+                };
+
+                f(beam, r, tp)
+            })
+            .collect()
+    }
+}
+
+impl LargeBeam {
+    /// Calculates the partial derivative of the result of
+    /// `LargeBeam::evaluate_with` with respect to the `mu_a` of `layer`
+    ///
+    /// This is computed analytically, so no precision is lost as it would be
+    /// with finite differences
+    pub fn evaluate_d_mu_a_with(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'_>,
+        layer: &Layer<'_>,
+        z: &Float,
+        tp: &Float,
+    ) -> Float {
+        // the integrand is a product of the four terms in
+        // LargeBeam::evaluate_with, so the product rule is applied
+
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        let mut d_term_1 = Float::with_val_64(precision, layer.e0.as_ref());
+        d_term_1 /= thermal_properties.rho.as_ref();
+        d_term_1 /= thermal_properties.c.as_ref();
+        d_term_1 /= 2.0;
+
+        let mut term_1 = Float::with_val_64(precision, &d_term_1);
+        term_1 *= layer.mu_a.as_ref();
+
+        let mut depth = Float::with_val_64(precision, z);
+        depth -= layer.z0.as_ref();
+
+        let mut term_2 = Float::with_val_64(precision, &depth);
+        term_2 *= layer.mu_a.as_ref();
+        term_2 *= -1;
+        term_2.exp_mut();
+
+        if *tp == 0 {
+            // d/dmu_a (term_1 * term_2) = term_2 * (d_term_1 - term_1 * depth),
+            // limited to the layer as in PreparedBeam::initial
+
+            let mut bottom = Float::with_val_64(precision, layer.d.as_ref());
+            bottom -= &depth;
+
+            term_1 *= depth;
+            d_term_1 -= term_1;
+            d_term_1 *= sign(&bottom) - sign(&Float::with_val_64(precision, layer.z0.as_ref() - z));
+            return d_term_1 * term_2;
+        }
+
+        let mut term_3 = Float::with_val_64(precision, layer.mu_a.as_ref());
+        term_3.square_mut();
+        term_3 *= tp;
+        term_3 *= &alpha;
+        term_3.exp_mut();
+
+        let mut reciprocal_sqrt = Float::with_val_64(precision, &alpha);
+        reciprocal_sqrt *= tp;
+        reciprocal_sqrt *= 4.0;
+        reciprocal_sqrt.sqrt_mut();
+        reciprocal_sqrt.recip_mut();
+
+        let mut sqrt_alpha_tp = Float::with_val_64(precision, &alpha);
+        sqrt_alpha_tp *= tp;
+        sqrt_alpha_tp.sqrt_mut();
+
+        let mut sqrt_mu_a = Float::with_val_64(precision, &sqrt_alpha_tp);
+        sqrt_mu_a *= layer.mu_a.as_ref();
+
+        let mut argument_1 = Float::with_val_64(precision, layer.z0.as_ref());
+        argument_1 += layer.d.as_ref();
+        argument_1 -= z;
+        argument_1 *= &reciprocal_sqrt;
+        argument_1 += &sqrt_mu_a;
+
+        let mut argument_2 = Float::with_val_64(precision, layer.z0.as_ref());
+        argument_2 -= z;
+        argument_2 *= &reciprocal_sqrt;
+        argument_2 += &sqrt_mu_a;
+
+        let term_4 = erf_difference(precision, &argument_1, &argument_2);
+
+        // d/dx erf(x) = 2 / sqrt(pi) * e^(-x^2), and each argument has a
+        // derivative of sqrt(alpha * tp) with respect to mu_a
+
+        argument_1.square_mut();
+        argument_1 *= -1;
+        argument_1.exp_mut();
+
+        argument_2.square_mut();
+        argument_2 *= -1;
+        argument_2.exp_mut();
+
+        let mut d_term_4 = argument_1;
+        d_term_4 -= argument_2;
+        d_term_4 *= sqrt_alpha_tp;
+        d_term_4 *= 2.0;
+        d_term_4 /= Float::with_val_64(precision, Constant::Pi).sqrt();
+
+        // the logarithmic derivatives of term_2 and term_3 are -depth and
+        // 2 * mu_a * alpha * tp, respectively
+
+        let mut d_log_terms_2_3 = alpha;
+        d_log_terms_2_3 *= tp;
+        d_log_terms_2_3 *= layer.mu_a.as_ref();
+        d_log_terms_2_3 *= 2.0;
+        d_log_terms_2_3 -= depth;
+
+        let mut derivative = d_term_1;
+        derivative *= &term_4;
+
+        let mut middle = Float::with_val_64(precision, &term_1);
+        middle *= term_4;
+        middle *= d_log_terms_2_3;
+        derivative += middle;
+
+        term_1 *= d_term_4;
+        derivative += term_1;
+
+        derivative * term_2 * term_3
+    }
+}
+
+//TODO: same todo as above
+#[derive(Clone, PartialEq, Debug)]
+pub struct FlatTopBeam<'a> {
+    /// Units: cm
+    radius: Cow<'a, Float>,
+}
+
+impl FlatTopBeam<'static> {
+    /// Constructs a [`struct@FlatTopBeam`], returning an error if `radius` is
+    /// not positive
+    pub fn new(radius: &Float) -> Result<Self, BeamError> {
+        Ok(Self {
+            radius: check_radius("radius", radius)?,
+        })
+    }
+}
+
+impl<'a> FlatTopBeam<'a> {
+    /// Units: cm
+    pub fn radius(&self) -> &Float {
+        &self.radius
+    }
+
+    /// Finds the time (units: s) at which `Beam::evaluate_with` peaks on the
+    /// axis of the beam at the depth `z`, returning it alongside the value
+    /// there
+    ///
+    /// Within the layer (including at its boundaries), the value can only
+    /// fall as heat diffuses away, so the peak is at a time of 0. Outside of
+    /// it, setting the derivative with respect to `tp` to zero doesn't give a
+    /// closed form, so the peak is found with `utilities::minimize`, starting
+    /// from `distance^2 / (2 * alpha)` (the time at which a point source at
+    /// that distance would peak in one dimension) and narrowing until the
+    /// time is known to within a relative tolerance of `2^(-precision / 2)`
+    ///
+    /// Without conduction (a zero `k`), heat never reaches a point outside of
+    /// the layer, so the value there stays at its initial value of zero and
+    /// the time is infinite. If the starting time is otherwise infinite, NaN,
+    /// or not positive (e.g. for a zero `rho`), there is nothing to search
+    /// from and both are NaN
+    pub fn peak_on_axis(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'_>,
+        layer: &Layer<'_>,
+        z: &Float,
+    ) -> (Float, Float) {
+        let zero = Float::with_val_64(precision, Special::Zero);
+        let evaluate =
+            |tp: &Float| self.evaluate_with(precision, thermal_properties, layer, z, &zero, tp);
+
+        let mut bottom = Float::with_val_64(precision, layer.z0.as_ref());
+        bottom += layer.d.as_ref();
+
+        let mut initial = if z < layer.z0.as_ref() {
+            Float::with_val_64(precision, layer.z0.as_ref() - z)
+        } else if *z > bottom {
+            bottom -= z;
+            bottom *= -1;
+            bottom
+        } else {
+            let peak = evaluate(&zero);
+            return (zero, peak);
+        };
+
+        if thermal_properties.k.is_zero() {
+            let peak = evaluate(&zero);
+            return (Float::with_val_64(precision, Special::Infinity), peak);
+        }
+
+        //TODO: same todo as the one in FlatTopBeam
+        initial.square_mut();
+        initial /= thermal_properties.k.as_ref();
+        initial *= thermal_properties.rho.as_ref();
+        initial *= thermal_properties.c.as_ref();
+        initial /= 2.0;
+
+        if !initial.is_finite() || initial <= 0 {
+            let nan = Float::with_val_64(precision, Special::Nan);
+            return (nan.clone(), nan);
+        }
+
+        let mut epsilon = Float::with_val_64(precision, 1);
+        epsilon >>= (precision / 2) as u32;
+
+        let tp = utilities::minimize(precision, &initial, &epsilon, |tp| -evaluate(tp));
+        let peak = evaluate(&tp);
+
+        (tp, peak)
+    }
+}
+
+impl<'a> FlatTopBeam<'a> {
+    /// Calculates the factor of the result depending upon `r` given the
+    /// thermal diffusivity `alpha`, where `tp` is nonzero
+    fn radial_factor_with(&self, precision: u64, alpha: &Float, r: &Float, tp: &Float) -> Float {
+        let radius = self.radius.as_ref();
+
+        if *r == 0 {
+            let mut r_factor = Float::with_val_64(precision, radius);
+            r_factor.square_mut();
+            r_factor /= -4.0;
+            r_factor /= alpha;
+            r_factor /= tp;
+            r_factor.exp_mut();
+            r_factor = 1 - r_factor;
+            r_factor
+        } else {
+            //TODO: this is not accurate at all. fix the marcum-q function
+            //      implementation
+
+            let mut a = Float::with_val_64(precision, 2.0);
+            a *= alpha;
+            a *= tp;
+            a.recip_mut();
+
+            let mut b = a.clone();
+            b *= radius;
+            a *= r;
+
+            let mut r_factor = utilities::marcum_q(1, &a, &b, precision);
+            r_factor = 1 - r_factor;
+            r_factor
+        }
+    }
+}
+
+impl<'a> Beam for FlatTopBeam<'a> {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        // as in LargeBeam, a layer which receives no irradiance deposits
+        // nothing, which is all the more worth skipping off of the axis
+
+        if layer.e0.is_zero() {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        // the beam is radially symmetric, so a negative r is just as far from
+        // the axis as its absolute value
+
+        let r = &*r.as_abs();
+
+        //TODO: don't duplicate this between the code in LargeBeam and this
+        //      function
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        // as in LargeBeam, heat never diffuses without conduction, so the
+        // result at a time of 0 holds at every time
+
+        let undiffused = *tp == 0 || alpha == 0;
+
+        if undiffused && r > self.radius.as_ref() {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        let z_factor = LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+
+        if undiffused {
+            return z_factor;
+        }
+
+        z_factor * self.radial_factor_with(precision, &alpha, r, tp)
+    }
+
+    fn evaluate_many<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        points: &[(Float, Float, Float)],
+    ) -> Vec<Float> {
+        // the thermal diffusivity is taken from the prepared beam rather than
+        // being recomputed for each point
+
+        LargeBeam.map_prepared(
+            precision,
+            thermal_properties,
+            layer,
+            points,
+            |beam, r, tp| {
+                let r = &*r.as_abs();
+                let undiffused = *tp == 0 || beam.alpha == 0;
+
+                if undiffused && r > self.radius.as_ref() {
+                    return Float::with_val_64(precision, Special::Zero);
+                }
+
+                let z_factor = beam.evaluate_at_time(tp);
+
+                if undiffused {
+                    return z_factor;
+                }
+
+                z_factor * self.radial_factor_with(precision, &beam.alpha, r, tp)
+            },
+        )
+    }
+
+    /// This is `1 - Q_1(r / (2 * alpha * tp), radius / (2 * alpha * tp))`,
+    /// where `Q_1` is the Marcum-Q function, which on the axis reduces to
+    /// `1 - exp(-radius^2 / (4 * alpha * tp))`. Before heat has diffused, it
+    /// is 1 within `radius` and 0 beyond it
+    fn radial_factor<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let r = &*r.as_abs();
+
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        if *tp == 0 || alpha == 0 {
+            let inside = r <= self.radius.as_ref();
+            return Float::with_val_64(precision, u32::from(inside));
+        }
+
+        self.radial_factor_with(precision, &alpha, r, tp)
+    }
+}
+
+/// A Laguerre-Gaussian beam of mode `(p, l)`, e.g. the "donut" LG01 mode
+///
+/// The intensity profile is `(2r^2/w^2)^|l| * L_p^|l|(2r^2/w^2)^2 *
+/// exp(-2r^2/w^2)`, normalized such that the LG00 mode has a peak of 1. As
+/// there is no convenient closed form for the radial factor, the intensity is
+/// convolved with the radial heat kernel numerically using `quadrature`
+#[derive(Clone, PartialEq, Debug)]
+pub struct LaguerreGaussianBeam<'a, Q> {
+    /// The beam waist. Units: cm
+    radius: Cow<'a, Float>,
+
+    /// The radial index
+    pub p: u32,
+
+    /// The azimuthal index. Only its magnitude affects the intensity
+    pub l: i32,
+
+    /// The [`trait@Quadrature`] implementation used to compute the radial
+    /// factor
+    pub quadrature: Q,
+
+    /// Tolerance passed to `quadrature`
+    pub epsilon: Cow<'a, Float>,
+}
+
+impl<Q> LaguerreGaussianBeam<'static, Q> {
+    /// Constructs a [`struct@LaguerreGaussianBeam`], returning an error if
+    /// `radius` is not positive
+    pub fn new(
+        radius: &Float,
+        p: u32,
+        l: i32,
+        quadrature: Q,
+        epsilon: &Float,
+    ) -> Result<Self, BeamError> {
+        Ok(Self {
+            radius: check_radius("radius", radius)?,
+            p,
+            l,
+            quadrature,
+            epsilon: Cow::Owned(epsilon.clone()),
+        })
+    }
+}
+
+impl<'a, Q> LaguerreGaussianBeam<'a, Q> {
+    /// The beam waist. Units: cm
+    pub fn radius(&self) -> &Float {
+        &self.radius
+    }
+
+    /// Calculates the intensity of the beam at the radial distance `r`
+    pub fn intensity(&self, precision: u64, r: &Float) -> Float {
+        let l = self.l.unsigned_abs();
+
+        let mut x = Float::with_val_64(precision, r);
+        x /= self.radius.as_ref();
+        x.square_mut();
+        x *= 2.0;
+
+        let mut intensity = utilities::laguerre(self.p, l, &x, precision);
+        intensity.square_mut();
+        intensity *= x.clone().pow(l);
+
+        x *= -1;
+        x.exp_mut();
+        intensity *= x;
+
+        intensity
+    }
+}
+
+impl<'a, Q: Quadrature<Float>> Beam for LaguerreGaussianBeam<'a, Q> {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        // as in FlatTopBeam, a layer which receives no irradiance is skipped
+        // before integrating over the profile, and a negative r is taken to
+        // be its absolute value
+
+        if layer.e0.is_zero() {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        let r = &*r.as_abs();
+        let z_factor = LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+
+        if *tp == 0 {
+            return z_factor * self.intensity(precision, r);
+        }
+
+        //TODO: same todo as the one in FlatTopBeam
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        let mut four_alpha_tp = alpha;
+        four_alpha_tp *= tp;
+        four_alpha_tp *= 4.0;
+
+        let radius = self.radius.as_ref();
+        let zero = Float::with_val_64(precision, Special::Zero);
+        let one = Float::with_val_64(precision, 1);
+
+        // the source radius r' over 0..inf is substituted with w * u / (1 - u)
+        // over 0..1 so that the integral may be computed over finite bounds
+
+        let (r_factor, _) = self.quadrature.integrate(
+            |u| {
+                if u >= 1 {
+                    return Float::with_val_64(precision, Special::Zero);
+                }
+
+                let mut jacobian = Float::with_val_64(precision, 1);
+                jacobian -= &u;
+                jacobian.recip_mut();
+
+                let mut source = u;
+                source *= &jacobian;
+                source *= radius;
+
+                jacobian.square_mut();
+                jacobian *= radius;
+
+                let mut kernel = Float::with_val_64(precision, r);
+                kernel.square_mut();
+                kernel += Float::with_val_64(precision, source.square_ref());
+                kernel /= &four_alpha_tp;
+                kernel *= -1;
+                kernel.exp_mut();
+
+                // the bessel function is 1 on the axis, so it is skipped there
+
+                if *r != 0 {
+                    let mut argument = Float::with_val_64(precision, &source);
+                    argument *= r;
+                    argument *= 2.0;
+                    argument /= &four_alpha_tp;
+
+                    kernel *= bessel::i_n(&zero, &argument, precision);
+                }
+
+                kernel *= 2.0;
+                kernel /= &four_alpha_tp;
+                kernel *= &source;
+                kernel *= self.intensity(precision, &source);
+                kernel *= jacobian;
+                kernel
+            },
+            self.epsilon.as_ref(),
+            (&zero, &one),
+        );
+
+        z_factor * r_factor
+    }
+}
+
+/// A Bessel beam, such as that produced by an axicon, with the intensity
+/// profile `J0(k_r * r)^2`, normalized to a peak of 1 on the axis
+///
+/// The intensity is convolved with the radial heat kernel numerically using
+/// `quadrature`. As the intensity oscillates indefinitely, the integral is
+/// only taken over the region in which the heat kernel is not negligible
+/// relative to `epsilon`, split into subintervals half a period of the
+/// oscillation (`pi / k_r`) wide such that each is integrated separately
+#[derive(Clone, PartialEq, Debug)]
+pub struct BesselBeam<'a, Q> {
+    /// The radial wavenumber. Only its magnitude affects the intensity.
+    /// Units: cm^-1
+    pub k_r: Cow<'a, Float>,
+
+    /// The [`trait@Quadrature`] implementation used to compute the radial
+    /// factor
+    pub quadrature: Q,
+
+    /// Tolerance passed to `quadrature`
+    pub epsilon: Cow<'a, Float>,
+}
+
+impl<'a, Q> BesselBeam<'a, Q> {
+    /// Calculates the intensity of the beam at the radial distance `r`
+    pub fn intensity(&self, precision: u64, r: &Float) -> Float {
+        let mut intensity = Float::with_val_64(precision, r);
+        intensity *= self.k_r.as_ref();
+        intensity.j0_mut();
+        intensity.square_mut();
+        intensity
+    }
+}
+
+impl<'a, Q: Quadrature<Float>> Beam for BesselBeam<'a, Q> {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        // as in FlatTopBeam, a layer which receives no irradiance is skipped
+        // before integrating over the profile, and a negative r is taken to
+        // be its absolute value
+
+        if layer.e0.is_zero() {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        let r = &*r.as_abs();
+        let z_factor = LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+
+        if *tp == 0 {
+            return z_factor * self.intensity(precision, r);
+        }
+
+        //TODO: same todo as the one in FlatTopBeam
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        let mut four_alpha_tp = alpha;
+        four_alpha_tp *= tp;
+        four_alpha_tp *= 4.0;
+
+        let zero = Float::with_val_64(precision, Special::Zero);
+
+        // the heat kernel falls off as exp(-(r - r')^2 / (4 * alpha * tp)), so
+        // its tails beyond sqrt(-ln(epsilon)) + 1 diffusion lengths of r are
+        // negligible
+
+        let mut half_width = Float::with_val_64(precision, self.epsilon.as_ref());
+        half_width.ln_mut();
+        half_width *= -1;
+        half_width.sqrt_mut();
+        half_width += 1;
+        half_width *= Float::with_val_64(precision, four_alpha_tp.sqrt_ref());
+
+        let mut lower = Float::with_val_64(precision, r - &half_width);
+        lower.max_mut(&zero);
+        let upper = Float::with_val_64(precision, r + &half_width);
+
+        // J0 is even, so only the magnitude of k_r matters here, and a
+        // negative step would never advance
+
+        let mut step = Float::with_val_64(precision, Constant::Pi);
+        step /= Float::with_val_64(precision, self.k_r.abs_ref());
+
+        let kernel = |source: Float| {
+            let mut kernel = Float::with_val_64(precision, r);
+            kernel.square_mut();
+            kernel += Float::with_val_64(precision, source.square_ref());
+            kernel /= &four_alpha_tp;
+            kernel *= -1;
+            kernel.exp_mut();
+
+            // the bessel function is 1 on the axis, so it is skipped there
+
+            if *r != 0 {
+                let mut argument = Float::with_val_64(precision, &source);
+                argument *= r;
+                argument *= 2.0;
+                argument /= &four_alpha_tp;
+
+                kernel *= bessel::i_n(&zero, &argument, precision);
+            }
+
+            kernel *= 2.0;
+            kernel /= &four_alpha_tp;
+            kernel *= self.intensity(precision, &source);
+            kernel *= source;
+            kernel
+        };
+
+        let mut r_factor = Float::with_val_64(precision, Special::Zero);
+        let mut end = Float::new_64(precision);
+
+        while lower < upper {
+            // an infinite step (for a k_r of 0) covers everything at once
+
+            end.assign(&lower + &step);
+            end.min_mut(&upper);
+
+            r_factor += self
+                .quadrature
+                .integrate(&kernel, self.epsilon.as_ref(), (&lower, &end))
+                .0;
+
+            lower.assign(&end);
+        }
+
+        z_factor * r_factor
+    }
+}
+
+/// A flat-top beam with an elliptical profile, such as that produced by a
+/// cylindrical lens, with its axes aligned with the x and y axes
+///
+/// As there is no radial symmetry, the point at which the beam is evaluated
+/// is given by `r` along with an azimuth (see
+/// [`EllipticalFlatTopBeam::evaluate_with_azimuth`]), and `Beam::evaluate_with`
+/// evaluates it along the x axis. The aperture is convolved with the heat
+/// kernel numerically using `quadrature`. The convolution along the y axis has
+/// a closed form, so only that along the x axis is numerical. If `radius_x`
+/// and `radius_y` are equal, this is equivalent to [`struct@FlatTopBeam`] at
+/// any azimuth
+#[derive(Clone, PartialEq, Debug)]
+pub struct EllipticalFlatTopBeam<'a, Q> {
+    /// The semi-axis along the x axis. Units: cm
+    radius_x: Cow<'a, Float>,
+
+    /// The semi-axis along the y axis. Units: cm
+    radius_y: Cow<'a, Float>,
+
+    /// The [`trait@Quadrature`] implementation used to compute the radial
+    /// factor
+    pub quadrature: Q,
+
+    /// Tolerance passed to `quadrature`
+    pub epsilon: Cow<'a, Float>,
+}
+
+impl<Q> EllipticalFlatTopBeam<'static, Q> {
+    /// Constructs an [`struct@EllipticalFlatTopBeam`], returning an error if
+    /// either `radius_x` or `radius_y` is not positive
+    pub fn new(
+        radius_x: &Float,
+        radius_y: &Float,
+        quadrature: Q,
+        epsilon: &Float,
+    ) -> Result<Self, BeamError> {
+        Ok(Self {
+            radius_x: check_radius("radius_x", radius_x)?,
+            radius_y: check_radius("radius_y", radius_y)?,
+            quadrature,
+            epsilon: Cow::Owned(epsilon.clone()),
+        })
+    }
+}
+
+impl<'a, Q> EllipticalFlatTopBeam<'a, Q> {
+    /// The semi-axis along the x axis. Units: cm
+    pub fn radius_x(&self) -> &Float {
+        &self.radius_x
+    }
+
+    /// The semi-axis along the y axis. Units: cm
+    pub fn radius_y(&self) -> &Float {
+        &self.radius_y
+    }
+}
+
+impl<'a, Q: Quadrature<Float>> EllipticalFlatTopBeam<'a, Q> {
+    /// Runs the beam as in `Beam::evaluate_with` at the point a distance `r`
+    /// from the axis at the angle `azimuth` (units: rad), measured from the x
+    /// axis
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate_with_azimuth<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        azimuth: &Float,
+        tp: &Float,
+    ) -> Float {
+        // as in FlatTopBeam, a layer which receives no irradiance is skipped
+        // before integrating over the profile
+
+        if layer.e0.is_zero() {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        let radius_x = self.radius_x.as_ref();
+        let radius_y = self.radius_y.as_ref();
+
+        let (mut y, mut x) =
+            Float::with_val_64(precision, azimuth).sin_cos(Float::new_64(precision));
+        x *= r;
+        y *= r;
+
+        if *tp == 0 {
+            let mut normalized_x = Float::with_val_64(precision, &x / radius_x);
+            normalized_x.square_mut();
+            let mut normalized_y = Float::with_val_64(precision, &y / radius_y);
+            normalized_y.square_mut();
+            normalized_x += normalized_y;
+
+            if normalized_x > 1 {
+                return Float::with_val_64(precision, Special::Zero);
+            }
+        }
+
+        let z_factor = LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+
+        if *tp == 0 {
+            return z_factor;
+        }
+
+        //TODO: same todo as the one in FlatTopBeam
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        let mut width = alpha;
+        width *= tp;
+        width *= 4.0;
+        width.sqrt_mut();
+
+        let mut normalization = Float::with_val_64(precision, Constant::Pi);
+        normalization.sqrt_mut();
+        normalization *= &width;
+
+        let mut half_pi = Float::with_val_64(precision, Constant::Pi);
+        half_pi /= 2.0;
+        let lower = Float::with_val_64(precision, -&half_pi);
+
+        // the source x' over -radius_x..radius_x is substituted with
+        // radius_x * sin(phi) over -pi/2..pi/2, which removes the singularity
+        // in the derivative of the half-height of the ellipse at its ends
+
+        let (r_factor, _) = self.quadrature.integrate(
+            |phi| {
+                let (sin, cos) = phi.sin_cos(Float::new_64(precision));
+
+                // the half-height of the ellipse at x'
+
+                let mut height = cos.clone();
+                height *= radius_y;
+
+                let mut argument_1 = Float::with_val_64(precision, &height + &y);
+                argument_1 /= &width;
+                argument_1.erf_mut();
+
+                let mut argument_2 = height;
+                argument_2 -= &y;
+                argument_2 /= &width;
+                argument_2.erf_mut();
+
+                let mut y_factor = argument_1;
+                y_factor += argument_2;
+                y_factor /= 2.0;
+
+                let mut kernel = sin;
+                kernel *= radius_x;
+                kernel -= &x;
+                kernel /= &width;
+                kernel.square_mut();
+                kernel *= -1;
+                kernel.exp_mut();
+                kernel /= &normalization;
+
+                kernel *= y_factor;
+                kernel *= cos;
+                kernel *= radius_x;
+                kernel
+            },
+            self.epsilon.as_ref(),
+            (&lower, &half_pi),
+        );
+
+        z_factor * r_factor
+    }
+}
+
+impl<'a, Q: Quadrature<Float>> Beam for EllipticalFlatTopBeam<'a, Q> {
+    /// Evaluates the beam along the x axis, where `r` is the distance along
+    /// it (see [`EllipticalFlatTopBeam::evaluate_with_azimuth`])
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let azimuth = Float::with_val_64(precision, Special::Zero);
+        self.evaluate_with_azimuth(precision, thermal_properties, layer, z, r, &azimuth, tp)
+    }
+}
+
+/// A [`trait@Beam`] whose power varies over the course of the exposure, such
+/// as a pulse with a Gaussian temporal profile (see [`fn@gaussian_profile`])
+///
+/// By Duhamel's principle, the temperature rise at `observation_time` is the
+/// integral of `profile(observation_time - tp)` times the response of `inner`
+/// at `tp` over tp in 0..`observation_time`, where `profile` is a function of
+/// the time elapsed since exposure began (units: s) and is 1 at full power.
+/// As such, this should be integrated over exactly those bounds, e.g. with
+/// [`fn@temperature_rise`]
+///
+/// Each evaluation costs one evaluation of `profile` on top of that of
+/// `inner`, so no nested integration is needed. However, as the integrand
+/// depends upon `observation_time`, a series of times cannot be accumulated
+/// incrementally as in [`fn@temperature_rise_series`], and each time instead
+/// requires its own [`struct@TemporalProfileBeam`] and a full integral from 0
+pub struct TemporalProfileBeam<'a, B, F> {
+    /// The beam at full power
+    pub inner: B,
+
+    /// The relative power of the beam over time
+    pub profile: F,
+
+    /// The time at which the temperature rise is observed. Units: s
+    pub observation_time: Cow<'a, Float>,
+}
+
+impl<'a, B: Beam, F: Fn(&Float) -> Float> Beam for TemporalProfileBeam<'a, B, F> {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let mut emitted = Float::with_val_64(precision, self.observation_time.as_ref());
+        emitted -= tp;
+
+        let mut result = (self.profile)(&emitted);
+        result *= self
+            .inner
+            .evaluate_with(precision, thermal_properties, layer, z, r, tp);
+        result
+    }
+}
+
+/// A [`trait@Beam`] which is taken to have no effect beyond `max_radius`
+/// (units: cm) of its axis
+///
+/// Beyond `max_radius`, zero is returned without evaluating `inner` at all,
+/// which saves evaluating the Marcum-Q function or a numerical convolution
+/// for points that are known to be negligibly heated. This is an
+/// approximation, and the error it introduces at a point is the entirety of
+/// the contribution of `inner` there, which heat diffusing outward from the
+/// beam will eventually make significant. `max_radius` should be chosen
+/// well beyond both the radius of the beam and the distance heat diffuses
+/// over the times of interest, `sqrt(4 * alpha * tp)`
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoundedBeam<'a, B> {
+    /// The beam being bounded
+    pub inner: B,
+
+    /// Units: cm
+    pub max_radius: Cow<'a, Float>,
+}
+
+impl<'a, B: Beam> Beam for BoundedBeam<'a, B> {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        if r > self.max_radius.as_ref() {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        self.inner
+            .evaluate_with(precision, thermal_properties, layer, z, r, tp)
+    }
+
+    fn try_evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Result<Float, EvalError> {
+        if r > self.max_radius.as_ref() {
+            return Ok(Float::with_val_64(precision, Special::Zero));
+        }
+
+        self.inner
+            .try_evaluate_with(precision, thermal_properties, layer, z, r, tp)
+    }
+}
+
+/// A [`trait@Beam`] which is taken to have no effect beyond `multiple`
+/// diffusion lengths, `sqrt(4 * alpha * tp)`, outside of `radius` (units:
+/// cm) of its axis
+///
+/// This is like [`struct@BoundedBeam`], except that the cutoff grows with
+/// `tp` alongside the distance heat has diffused, so it remains valid at
+/// every time rather than only over those it was chosen for. It is useful
+/// when computing a wide radial field for a small beam, where most points
+/// are far enough away that `inner` is negligible there. `radius` should be
+/// the radius beyond which `inner` deposits no (or negligible) power, such as
+/// that of a flat-top beam, or a few times that of a gaussian one
+///
+/// The heat kernel falls off as `exp(-rho^2 / (4 * alpha * tp))` with the
+/// distance `rho` from a source, so the contribution ignored beyond the
+/// cutoff is at most about `exp(-multiple^2)` times that on the axis, e.g.
+/// `1.2e-4` for a multiple of 3, `1.4e-11` for 5, and `2.3e-16` for 6
+#[derive(Clone, PartialEq, Debug)]
+pub struct CutoffBeam<'a, B> {
+    /// The beam being cut off
+    pub inner: B,
+
+    /// Units: cm
+    pub radius: Cow<'a, Float>,
+
+    /// The number of diffusion lengths beyond `radius` at which `inner` is
+    /// cut off
+    pub multiple: Cow<'a, Float>,
+}
+
+impl<'a, B> CutoffBeam<'a, B> {
+    /// Whether a point at a radial distance of `r` is beyond the cutoff at
+    /// the time `tp`
+    fn is_cut_off(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'_>,
+        r: &Float,
+        tp: &Float,
+    ) -> bool {
+        let mut cutoff = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        cutoff /= thermal_properties.rho.as_ref();
+        cutoff /= thermal_properties.c.as_ref();
+        cutoff *= tp;
+        cutoff *= 4;
+        cutoff.sqrt_mut();
+        cutoff *= self.multiple.as_ref();
+        cutoff += self.radius.as_ref();
+
+        *r.as_abs() > cutoff
+    }
+}
+
+impl<'a, B: Beam> Beam for CutoffBeam<'a, B> {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        if self.is_cut_off(precision, thermal_properties, r, tp) {
+            return Float::with_val_64(precision, Special::Zero);
+        }
+
+        self.inner
+            .evaluate_with(precision, thermal_properties, layer, z, r, tp)
+    }
+
+    fn try_evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Result<Float, EvalError> {
+        if self.is_cut_off(precision, thermal_properties, r, tp) {
+            return Ok(Float::with_val_64(precision, Special::Zero));
+        }
+
+        self.inner
+            .try_evaluate_with(precision, thermal_properties, layer, z, r, tp)
+    }
+}
+
+/// A [`trait@Beam`] depositing power uniformly throughout all of space,
+/// rather than absorbing irradiance within a [`struct@Layer`]
+///
+/// This isn't a physical beam, but is useful as a reference for testing and
+/// cross-checking, as with nothing to conduct heat away from any point, the
+/// temperature rise everywhere is the energy deposited per unit volume over
+/// `rho * c`. The integrand is the constant `power_density / (rho * c)`, so
+/// the temperature rise over 0..tp is exactly `power_density * tp / (rho *
+/// c)`, growing linearly in time. The layer, `z`, `r`, and `tp` are all
+/// disregarded
+#[derive(Clone, PartialEq, Debug)]
+pub struct UniformBeam<'a> {
+    /// Units: W*cm^-3
+    pub power_density: Cow<'a, Float>,
+}
+
+impl<'a> Beam for UniformBeam<'a> {
+    fn is_radially_uniform(&self) -> bool {
+        true
+    }
+
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        _layer: &Layer<'b>,
+        _z: &Float,
+        _r: &Float,
+        _tp: &Float,
+    ) -> Float {
+        let mut result = Float::with_val_64(precision, self.power_density.as_ref());
+        result /= thermal_properties.rho.as_ref();
+        result /= thermal_properties.c.as_ref();
+        result
+    }
+
+    /// With nothing to conduct heat away, the temperature rise grows without
+    /// bound, so this is infinite unless `power_density` is zero
+    fn steady_state<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+    ) -> Float {
+        let rise = self.evaluate_with(
+            precision,
+            thermal_properties,
+            layer,
+            z,
+            r,
+            &Float::with_val_64(precision, Special::Zero),
+        );
+
+        unbounded_steady_state(precision, rise)
+    }
+}
+
+/// Creates a Gaussian temporal profile, `exp(-(t - t0)^2 / (2 * sigma^2))`,
+/// for use with [`struct@TemporalProfileBeam`]
+///
+/// `t0` is the time of the peak of the pulse and `sigma` is its standard
+/// deviation. Units: s
+pub fn gaussian_profile(precision: u64, t0: Float, sigma: Float) -> impl Fn(&Float) -> Float {
+    move |t| {
+        let mut exponent = Float::with_val_64(precision, t);
+        exponent -= &t0;
+        exponent /= &sigma;
+        exponent.square_mut();
+        exponent /= -2.0;
+        exponent.exp_mut();
+        exponent
+    }
+}
+
+/// A superposition of multiple [`trait@Beam`]s incident on the tissue at the
+/// same time
+///
+/// As the heat equation is linear, the contributions of each of the
+/// constituent beams are simply summed
+pub struct CompositeBeam {
+    /// The beams this [`struct@CompositeBeam`] is composed of
+    pub beams: Vec<Box<dyn Beam>>,
+}
+
+impl Beam for CompositeBeam {
+    fn evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+
+        for beam in &self.beams {
+            sum += beam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+        }
+
+        sum
+    }
+
+    fn try_evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Result<Float, EvalError> {
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+
+        for beam in &self.beams {
+            sum += beam.try_evaluate_with(precision, thermal_properties, layer, z, r, tp)?;
+        }
+
+        check_finite("result", &sum)?;
+
+        Ok(sum)
+    }
+
+    fn steady_state<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+    ) -> Float {
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+
+        for beam in &self.beams {
+            sum += beam.steady_state(precision, thermal_properties, layer, z, r);
+        }
+
+        sum
+    }
+}
+
+/// A superposition of multiple [`trait@Beam`]s incident on the tissue at the
+/// same time at different transverse positions, such as the spots of a
+/// multi-emitter array or of multiple reflections
+///
+/// Each beam is paired with the radial coordinate of its axis (units: cm).
+/// All of the axes and the point at which the beams are evaluated lie on one
+/// line through the origin, so each beam is evaluated at a radial distance of
+/// `|r - offset|` from its axis. As with [`struct@CompositeBeam`], the
+/// contributions of each beam are summed
+pub struct OffsetCompositeBeam {
+    /// The beams this [`struct@OffsetCompositeBeam`] is composed of, paired
+    /// with their offsets
+    pub beams: Vec<(Box<dyn Beam>, Float)>,
+}
+
+impl Beam for OffsetCompositeBeam {
+    fn evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+        let mut distance = Float::new_64(precision);
+
+        for (beam, offset) in &self.beams {
+            distance.assign(r - offset);
+            distance.abs_mut();
+
+            sum += beam.evaluate_with(precision, thermal_properties, layer, z, &distance, tp);
+        }
+
+        sum
+    }
+
+    fn try_evaluate_with<'a>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'a>,
+        layer: &Layer<'a>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Result<Float, EvalError> {
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+        let mut distance = Float::new_64(precision);
+
+        for (beam, offset) in &self.beams {
+            distance.assign(r - offset);
+            distance.abs_mut();
+
+            sum +=
+                beam.try_evaluate_with(precision, thermal_properties, layer, z, &distance, tp)?;
+        }
+
+        check_finite("result", &sum)?;
+
+        Ok(sum)
+    }
+}
+
+/// A source of heat given directly as the absorbed power density at a set of
+/// depths, such as one tabulated by an optical Monte Carlo simulation, in
+/// place of the Beer's Law model of a [`struct@Layer`]
+///
+/// The power density is linearly interpolated between the samples and is
+/// zero outside of them. As with [`struct@LargeBeam`], the source is taken to
+/// be much wider than the region of interest, so heat is only conducted in
+/// depth
+#[derive(Clone, PartialEq, Debug)]
+pub struct TabulatedSource {
+    /// Units: cm
+    depths: Vec<Float>,
+
+    /// Units: W*cm^-3
+    power_density: Vec<Float>,
+}
+
+impl TabulatedSource {
+    /// Creates a new [`struct@TabulatedSource`] from the absorbed power
+    /// density (units: W*cm^-3) at each of the given depths (units: cm)
+    ///
+    /// There must be as many depths as power densities, and at least two of
+    /// each. Every sample must be finite, and the depths must be strictly
+    /// increasing. Otherwise, a [`enum@TabulatedSourceError`] is returned
+    pub fn new(
+        depths: Vec<Float>,
+        power_density: Vec<Float>,
+    ) -> Result<Self, TabulatedSourceError> {
+        if depths.len() != power_density.len() {
+            return Err(TabulatedSourceError::LengthMismatch {
+                depths: depths.len(),
+                power_density: power_density.len(),
+            });
+        }
+
+        if depths.len() < 2 {
+            return Err(TabulatedSourceError::TooFewSamples(depths.len()));
+        }
+
+        for (i, (depth, power_density)) in depths.iter().zip(&power_density).enumerate() {
+            if !depth.is_finite() || !power_density.is_finite() {
+                return Err(TabulatedSourceError::NonFinite(i));
+            }
+
+            if i != 0 && *depth <= depths[i - 1] {
+                return Err(TabulatedSourceError::NotIncreasing(i));
+            }
+        }
+
+        Ok(Self {
+            depths,
+            power_density,
+        })
+    }
+
+    /// The depths of the samples. Units: cm
+    pub fn depths(&self) -> &[Float] {
+        &self.depths
+    }
+
+    /// The absorbed power density of the samples. Units: W*cm^-3
+    pub fn power_density(&self) -> &[Float] {
+        &self.power_density
+    }
+
+    /// Calculates the absorbed power density (units: W*cm^-3) at the depth
+    /// `z` (units: cm), linearly interpolating between the samples
+    ///
+    /// This is zero outside of the samples, and, as with the limit of
+    /// [`TabulatedSource::evaluate_with`] as `tp` approaches 0, halved at the
+    /// first and last of them
+    pub fn power_density_at(&self, z: &Float, precision: u64) -> Float {
+        let mut result = Float::with_val_64(precision, Special::Zero);
+
+        let (first, last) = (&self.depths[0], &self.depths[self.depths.len() - 1]);
+        if *z < *first || *z > *last {
+            return result;
+        }
+
+        for (depths, power_density) in self.depths.windows(2).zip(self.power_density.windows(2)) {
+            if *z > depths[1] {
+                continue;
+            }
+
+            result.assign(z - &depths[0]);
+            result /= Float::with_val_64(precision, &depths[1] - &depths[0]);
+            result *= Float::with_val_64(precision, &power_density[1] - &power_density[0]);
+            result += &power_density[0];
+            break;
+        }
+
+        if *z == *first || *z == *last {
+            result /= 2;
+        }
+
+        result
+    }
+
+    /// Calculates the rate of temperature rise (units: K*s^-1) at the depth
+    /// `z` (units: cm) due to the energy deposited `tp` (units: s) before,
+    /// the analog of `LargeBeam::evaluate_with`
+    ///
+    /// The interpolated power density is convolved with the one-dimensional
+    /// heat kernel, `exp(-(z - z')^2 / w^2) / (sqrt(pi) * w)` where `w =
+    /// sqrt(4 * alpha * tp)`, and divided by `rho * c`. Over each interval
+    /// between samples, the power density is linear, so the convolution has a
+    /// closed form in terms of error functions and exponentials. At a `tp` of
+    /// 0, or without conduction, this is [`TabulatedSource::power_density_at`]
+    /// divided by `rho * c`
+    pub fn evaluate_with(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        tp: &Float,
+    ) -> Float {
+        let mut rho_c = Float::with_val_64(precision, thermal_properties.rho.as_ref());
+        rho_c *= thermal_properties.c.as_ref();
+
+        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        alpha /= &rho_c;
+
+        if *tp == 0 || alpha == 0 {
+            let mut result = self.power_density_at(z, precision);
+            result /= &rho_c;
+            return result;
+        }
+
+        // the width of the kernel, sqrt(4 * alpha * tp)
+
+        let mut width = alpha;
+        width *= tp;
+        width *= 4;
+        width.sqrt_mut();
+
+        let mut sqrt_pi = Float::with_val_64(precision, Constant::Pi);
+        sqrt_pi.sqrt_mut();
+
+        let mut sum = Float::with_val_64(precision, Special::Zero);
+        let mut upper = Float::new_64(precision);
+        let mut lower = Float::new_64(precision);
+        let mut slope = Float::new_64(precision);
+        let mut term = Float::new_64(precision);
+
+        for (depths, power_density) in self.depths.windows(2).zip(self.power_density.windows(2)) {
+            // over depths[0]..depths[1], the power density is
+            // power_density[0] + slope * (z - depths[0]) + slope * (z' - z)
+
+            upper.assign(&depths[1] - z);
+            upper /= &width;
+            lower.assign(&depths[0] - z);
+            lower /= &width;
+
+            slope.assign(&power_density[1] - &power_density[0]);
+            slope /= Float::with_val_64(precision, &depths[1] - &depths[0]);
+
+            term.assign(z - &depths[0]);
+            term *= &slope;
+            term += &power_density[0];
+            term *= erf_difference(precision, &upper, &lower);
+            term /= 2;
+            sum += &term;
+
+            // the integral of (z' - z) times the kernel
+
+            term.assign(lower.square_ref());
+            term *= -1;
+            term.exp_mut();
+            upper.square_mut();
+            upper *= -1;
+            upper.exp_mut();
+            term -= &upper;
+            term *= &width;
+            term /= &sqrt_pi;
+            term /= 2;
+            term *= &slope;
+            sum += &term;
+        }
+
+        sum /= &rho_c;
+        sum
+    }
+
+    /// Calculates the temperature rise at the depth `z` (units: cm) over the
+    /// interval a..b, as in [`fn@temperature_rise`], returning it alongside
+    /// its approximate error
+    ///
+    /// See [`fn@temperature_rise`] for the meaning of and the conditions upon
+    /// the bounds
+    pub fn temperature_rise(
+        &self,
+        precision: u64,
+        quadrature: &impl Quadrature<Float>,
+        thermal_properties: &ThermalProperties<'_>,
+        z: &Float,
+        epsilon: &Float,
+        bounds: (&Float, &Float),
+    ) -> Result<(Float, Float), BoundsError> {
+        check_thermal_properties(thermal_properties);
+
+        integrate_bounds(
+            precision,
+            quadrature,
+            |t| self.evaluate_with(precision, thermal_properties, z, &t),
+            epsilon,
+            bounds,
+        )
+    }
+}
+
+/// Calculates the temperature rise over the interval a..b
+///
+/// This is really just a convenience wrapper around `Quadrature::integrate`
+///
+/// The integration variable is the time elapsed between the deposition of
+/// energy and the observation, not an absolute time, so the bounds a..b
+/// describe an exposure which began `b` and ended `a` before the moment the
+/// temperature is observed. A lower bound of 0 is therefore an exposure
+/// which is still ongoing, and a positive one is an exposure which ended `a`
+/// ago. To give the exposure window and the observation time separately, use
+/// [`fn@temperature_rise_exposure`]
+///
+/// The lower bound must be finite and no greater than the upper bound, which
+/// may be positive infinity to approach the steady state. Otherwise, a
+/// [`enum@BoundsError`] is returned rather than integrating backwards. If the
+/// bounds are equal, the temperature rise is zero
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, Float), BoundsError> {
+    check_thermal_properties(thermal_properties);
+    check_precision(
+        precision,
+        thermal_properties,
+        [layer],
+        [("z", z), ("r", r), ("start", bounds.0), ("end", bounds.1)],
+    );
+
+    integrate_bounds(
+        precision,
+        quadrature,
+        |t| beam.evaluate_with(precision, thermal_properties, layer, z, r, &t),
+        epsilon,
+        bounds,
+    )
+}
+
+/// Calculates the temperature rise at each of the provided times, with
+/// integration beginning at `start`
+///
+/// Rather than integrating over start..time for every point, only the
+/// interval between consecutive times is integrated and the results are
+/// accumulated. As such, `times` must be sorted in ascending order and must
+/// not precede `start`. The error estimate reported for each point is the
+/// largest estimate of the intervals accumulated into it
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_series(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    start: &Float,
+    times: &[Float],
+) -> Vec<(Float, Float)> {
+    integrate_series(
+        precision,
+        quadrature,
+        |t| beam.evaluate_with(precision, thermal_properties, layer, z, r, &t),
+        epsilon,
+        start,
+        times,
+        |_| (),
+    )
+}
+
+/// Calculates the temperature rise at each of the provided times, with
+/// integration beginning at `start`, integrating over the logarithm of time
+///
+/// This is the same as [`fn@temperature_rise_series`], except that each
+/// interval a..b is integrated after substituting `tp = exp(-w)` over
+/// `-ln(b)..-ln(a)`, with the Jacobian `exp(-w)`. Equal widths in `w` cover
+/// equal ratios of time, so the early times over which the integrand changes
+/// most rapidly are spread across as much of the interval as the late times
+/// over which it hardly changes, which better suits responses spanning many
+/// decades. If `start` is 0, the first interval extends to infinity in `w`
+/// and is mapped onto finite bounds as in [`fn@temperature_rise`]
+///
+/// `start` and `times` must not be negative or infinite, and `times` must be
+/// sorted in ascending order and must not precede `start`. Otherwise, a
+/// [`enum@BoundsError`] is returned for the first interval which breaks this.
+/// As in [`fn@temperature_rise_series`], a time equal to the one before it
+/// adds nothing to the sum
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_series_log_time(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    start: &Float,
+    times: &[Float],
+) -> Result<Vec<(Float, Float)>, BoundsError> {
+    let f = |t: Float| beam.evaluate_with(precision, thermal_properties, layer, z, r, &t);
+
+    let mut series = Vec::with_capacity(times.len());
+    let mut sum = Float::with_val_64(precision, Special::Zero);
+    let mut error = Float::with_val_64(precision, Special::Zero);
+    let mut previous = start;
+
+    for time in times {
+        check_exposure_bounds((previous, time))?;
+
+        // a time after a nonnegative previous one is positive, so -ln(time)
+        // is finite, while a time equal to the previous one (as when both
+        // are 0) is skipped rather than integrated over an infinite interval
+
+        if time != previous {
+            let mut lower = Float::with_val_64(precision, time.ln_ref());
+            lower *= -1;
+            let mut upper = Float::with_val_64(precision, previous.ln_ref());
+            upper *= -1;
+
+            let (integral, interval_error) = integrate_bounds(
+                precision,
+                quadrature,
+                |mut t| {
+                    t *= -1;
+                    t.exp_mut();
+                    let jacobian = t.clone();
+                    f(t) * jacobian
+                },
+                epsilon,
+                (&lower, &upper),
+            )?;
+            sum += integral;
+            error.max_mut(&interval_error);
+        }
+
+        series.push((sum.clone(), error.clone()));
+        previous = time;
+    }
+
+    Ok(series)
+}
+
+/// Calculates the temperature rise at each of the provided times, with
+/// integration beginning at `start`, without collecting the results
+///
+/// This is the same as [`fn@temperature_rise_series`], except that rather
+/// than returning the results, `callback` is called with the index of each
+/// time point, its temperature rise, and its approximate error as soon as it
+/// is computed. This allows the results for a very large number of times to
+/// be written out incrementally rather than held in memory
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_stream(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    start: &Float,
+    times: &[Float],
+    callback: impl FnMut(usize, &Float, &Float),
+) {
+    integrate_stream(
+        precision,
+        quadrature,
+        |t| beam.evaluate_with(precision, thermal_properties, layer, z, r, &t),
+        epsilon,
+        start,
+        times,
+        callback,
+    )
+}
+
+/// Calculates the temperature rise at `observation_time` resulting from an
+/// exposure lasting from a time of 0 to `pulse_width`
+///
+/// By superposition, this is the temperature rise of a continuous exposure
+/// at `observation_time` minus that of a continuous exposure beginning at
+/// `pulse_width`, which is the same as integrating only over the last
+/// `pulse_width` before `observation_time`. This is done directly rather than
+/// differencing two integrals. If `observation_time` is less than
+/// `pulse_width`, the exposure is still ongoing and the result is the same as
+/// that of [`fn@temperature_rise`] over 0..`observation_time`
+///
+/// Negative pulse widths and observation times are rejected with a
+/// [`enum@BoundsError`]
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_pulse(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    pulse_width: &Float,
+    observation_time: &Float,
+) -> Result<(Float, Float), BoundsError> {
+    let mut a = Float::with_val_64(precision, observation_time - pulse_width);
+
+    if a.is_sign_negative() && pulse_width.is_sign_positive() {
+        a.assign(Special::Zero);
+    }
+
+    temperature_rise(
+        precision,
+        quadrature,
+        beam,
+        thermal_properties,
+        layer,
+        z,
+        r,
+        epsilon,
+        (&a, observation_time),
+    )
+}
+
+/// The number of thermal relaxation times after the end of a pulse at which
+/// [`fn@auto_time_bound`] places the end of the observation window
+pub const AUTO_TIME_BOUND_RELAXATION_TIMES: u32 = 10;
+
+/// Recommends a time (units: s) by which to stop observing the temperature
+/// rise from a pulse of `pulse_width` over `layer`, for when no observation
+/// window is given
+///
+/// This is `pulse_width` plus [`AUTO_TIME_BOUND_RELAXATION_TIMES`] thermal
+/// relaxation times of the layer (see [`Layer::thermal_relaxation_time`]),
+/// by which the heat deposited has long since diffused out of the optical
+/// penetration depth and the layer is well into cooling. It is infinite for
+/// a layer which absorbs nothing
+pub fn auto_time_bound(
+    layer: &Layer<'_>,
+    thermal_properties: &ThermalProperties<'_>,
+    pulse_width: &Float,
+    precision: u64,
+) -> Float {
+    let mut bound = layer.thermal_relaxation_time(thermal_properties, precision);
+    bound *= AUTO_TIME_BOUND_RELAXATION_TIMES;
+    bound += pulse_width;
+    bound
+}
+
+/// Calculates the temperature rise at `observation_time` resulting from an
+/// exposure lasting from `exposure.0` to `exposure.1`, both given as absolute
+/// times
+///
+/// Energy deposited at the time `t` has been diffusing for
+/// `observation_time - t` by the time of the observation, so this is
+/// [`fn@temperature_rise`] over `observation_time - exposure.1` to
+/// `observation_time - exposure.0`, with the upper end of the exposure
+/// clamped to `observation_time`. If the exposure has not yet begun, the
+/// temperature rise is zero, while if it has ended, only cooling is
+/// observed. [`fn@temperature_rise_pulse`] is the special case of an
+/// exposure beginning at a time of 0
+///
+/// The exposure window is validated as in [`fn@temperature_rise`], such that
+/// its end may be positive infinity for an exposure which never ends
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_exposure(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    exposure: (&Float, &Float),
+    observation_time: &Float,
+) -> Result<(Float, Float), BoundsError> {
+    let (a, b) = exposure_bounds(precision, exposure, observation_time)?;
+
+    temperature_rise(
+        precision,
+        quadrature,
+        beam,
+        thermal_properties,
+        layer,
+        z,
+        r,
+        epsilon,
+        (&a, &b),
+    )
+}
+
+/// Converts an exposure window in absolute times into the bounds of the
+/// integral over the time elapsed since deposition at `observation_time` (see
+/// [`fn@temperature_rise_exposure`])
+///
+/// If the exposure begins after `observation_time`, the bounds are both 0
+fn exposure_bounds(
+    precision: u64,
+    (start, end): (&Float, &Float),
+    observation_time: &Float,
+) -> Result<(Float, Float), BoundsError> {
+    check_bounds((start, end))?;
+
+    let mut a = Float::with_val_64(precision, end);
+    a.min_mut(observation_time);
+    a -= observation_time;
+    a *= -1;
+
+    let mut b = Float::with_val_64(precision, observation_time - start);
+
+    if b.is_sign_negative() {
+        a.assign(Special::Zero);
+        b.assign(Special::Zero);
+    }
+
+    Ok((a, b))
+}
+
+/// Calculates the temperature rise over the interval a..b to `target_digits`
+/// significant decimal digits, returning it alongside the precision it was
+/// calculated at
+///
+/// The temperature rise is calculated as in [`fn@temperature_rise`], first
+/// at a precision a little beyond `target_digits`, then at double the
+/// previous precision until two successive results agree to `target_digits`.
+/// `quadrature` is called with each precision to construct the
+/// [`trait@Quadrature`] implementation used at it, and is passed an `epsilon`
+/// of `2^(-precision / 2)` so that the integration is refined alongside the
+/// arithmetic. Any quadrature internal to `beam` is left as is
+///
+/// If the results have not converged by `max_precision`,
+/// [`EscalationError::NotConverged`] is returned. Invalid bounds are
+/// reported as in [`fn@temperature_rise`]
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_to_digits<Q: Quadrature<Float>>(
+    target_digits: u32,
+    max_precision: u64,
+    quadrature: impl Fn(u64) -> Q,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, u64), EscalationError> {
+    // log2(10) bits are needed per decimal digit, plus some guard bits
+
+    let mut precision = (f64::from(target_digits) * std::f64::consts::LOG2_10).ceil() as u64 + 16;
+    let mut previous: Option<Float> = None;
+
+    while precision <= max_precision {
+        let mut epsilon = Float::with_val_64(precision, 1);
+        epsilon >>= (precision / 2) as u32;
+
+        let (result, _) = temperature_rise(
+            precision,
+            &quadrature(precision),
+            beam,
+            thermal_properties,
+            layer,
+            z,
+            r,
+            &epsilon,
+            bounds,
+        )?;
+
+        if let Some(previous) = previous {
+            let mut tolerance = Float::with_val_64(precision, 10);
+            tolerance.pow_assign(target_digits);
+            tolerance.recip_mut();
+            tolerance *= Float::with_val_64(precision, result.abs_ref());
+
+            let mut difference = previous;
+            difference -= &result;
+            difference.abs_mut();
+
+            if difference <= tolerance {
+                return Ok((result, precision));
+            }
+        }
+
+        previous = Some(result);
+        precision *= 2;
+    }
+
+    Err(EscalationError::NotConverged {
+        precision: precision / 2,
+    })
+}
+
+/// Calculates the temperature rise over the interval a..b at both
+/// `precision_low` and `precision_high` (on separate threads), returning the
+/// latter alongside the number of significant decimal digits in which the
+/// two agree
+///
+/// Each is calculated as in [`fn@temperature_rise`], with `quadrature`
+/// called with its precision to construct the [`trait@Quadrature`]
+/// implementation used at it. Both are given the same `epsilon`, so any
+/// disagreement is down to the arithmetic rather than the integration, and
+/// few agreeing digits suggest that `precision_low` (and perhaps
+/// `precision_high`) is insufficient. The digits are counted relative to the
+/// high-precision result and capped at the number `precision_low` can
+/// represent, which is also reported when the two agree exactly
+///
+/// Invalid bounds are reported as in [`fn@temperature_rise`]
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_cross_check<Q: Quadrature<Float>>(
+    precision_low: u64,
+    precision_high: u64,
+    quadrature: impl Fn(u64) -> Q + Sync,
+    beam: &(impl Beam + Sync),
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, u32), BoundsError> {
+    let rise = |precision| {
+        temperature_rise(
+            precision,
+            &quadrature(precision),
+            beam,
+            thermal_properties,
+            layer,
+            z,
+            r,
+            epsilon,
+            bounds,
+        )
+        .map(|(value, _)| value)
+    };
+
+    let (low, high) = std::thread::scope(|scope| {
+        let low = scope.spawn(|| rise(precision_low));
+        let high = rise(precision_high);
+
+        (
+            low.join()
+                .unwrap_or_else(|payload| std::panic::resume_unwind(payload)),
+            high,
+        )
+    });
+    let (low, high) = (low?, high?);
+
+    // log10(2) decimal digits are represented per bit
+
+    let max_digits = (precision_low as f64 * std::f64::consts::LOG10_2).floor() as u32;
+
+    let mut error = Float::with_val_64(precision_high, &low - &high);
+    if error.is_zero() {
+        return Ok((high, max_digits));
+    }
+
+    error /= &high;
+    error.abs_mut();
+    error.log10_mut();
+
+    let digits = if error.is_finite() {
+        (-error.to_f64()).floor().clamp(0.0, f64::from(max_digits)) as u32
+    } else {
+        0
+    };
+
+    Ok((high, digits))
+}
+
+/// The number of bits [`fn@estimate_precision`] adds to its recommendation
+/// beyond those it estimates are needed, to allow for the error in that
+/// estimate
+pub const ESTIMATE_PRECISION_GUARD_BITS: u64 = 8;
+
+/// The highest precision (in bits) [`fn@estimate_precision`] evaluates at
+/// while searching for one at which the result has any correct bits
+const ESTIMATE_PRECISION_LIMIT: u64 = 1 << 16;
+
+/// Recommends a precision (in bits) at which `Beam::evaluate_with` attains a
+/// relative error of at most `target_relative_error`
+///
+/// The rounding error of an evaluation at `p` bits is roughly `2^(lost -
+/// p)`, where `lost` is the number of bits lost to cancellation and the
+/// like. The beam is evaluated at 64 bits and at double that, and the
+/// difference between the two (relative to the latter) is taken as the error
+/// of the former, from which `lost` is extrapolated. If not even one bit of
+/// the former is correct, both precisions are doubled until it is. The
+/// recommendation is `lost`, plus the bits of `target_relative_error`, plus
+/// [`ESTIMATE_PRECISION_GUARD_BITS`]
+///
+/// Only the arithmetic of a single evaluation is accounted for, so the
+/// tolerance of an integration over time should be chosen separately (see
+/// [`fn@quadrature::default_epsilon`])
+///
+/// `None` is returned if `target_relative_error` is not within `0..1`
+/// (exclusive), if the beam evaluates to an infinite or NaN result, which
+/// no precision can make accurate, or if the recommendation exceeds the
+/// highest precision supported by [`struct@Float`]
+pub fn estimate_precision(
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    tp: &Float,
+    target_relative_error: &Float,
+) -> Option<u64> {
+    if !(*target_relative_error > 0 && *target_relative_error < 1) {
+        return None;
+    }
+
+    let mut low = 64;
+
+    let lost = loop {
+        let high = low * 2;
+        let low_result = beam.evaluate_with(low, thermal_properties, layer, z, r, tp);
+        let high_result = beam.evaluate_with(high, thermal_properties, layer, z, r, tp);
+
+        if !low_result.is_finite() || !high_result.is_finite() {
+            return None;
+        }
+
+        // results which agree entirely (such as zeros) lost nothing
+
+        let mut error = Float::with_val_64(high, &low_result - &high_result);
+        if error.is_zero() {
+            break 0;
+        }
+
+        error /= &high_result;
+        error.abs_mut();
+
+        if error < 0.5 || high >= ESTIMATE_PRECISION_LIMIT {
+            if !error.is_finite() {
+                break low;
+            }
+
+            error.log2_mut();
+            break (low as f64 + error.to_f64()).ceil().max(0.0) as u64;
+        }
+
+        low = high;
+    };
+
+    let mut target_bits = Float::with_val_64(64, target_relative_error);
+    target_bits.log2_mut();
+
+    target_bits *= -1;
+    target_bits.ceil_mut();
+
+    // the bits of the target alone may exceed any supported precision, so
+    // they're checked before being converted
+
+    let precision_max = u64::from(float::prec_max());
+    if target_bits > precision_max {
+        return None;
+    }
+
+    Some(target_bits.to_f64() as u64 + lost + ESTIMATE_PRECISION_GUARD_BITS)
+        .filter(|precision| *precision <= precision_max)
+}
+
+/// Validates the bounds a..b before integrating over them with `quadrature`
+///
+/// See [`fn@temperature_rise`] for the conditions the bounds must satisfy
+fn integrate_bounds(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    (a, b): (&Float, &Float),
+) -> Result<(Float, Float), BoundsError> {
+    let f = check_integrand(f);
+
+    check_bounds((a, b))?;
+
+    if a == b {
+        return Ok((
+            Float::with_val_64(precision, Special::Zero),
+            Float::with_val_64(precision, Special::Zero),
+        ));
+    }
+
+    // an infinite upper bound is mapped onto finite bounds here, rather than
+    // leaving it to `quadrature`, so that it is supported by any quadrature
+
+    let (f, (a, b)) = quadrature::finite_bounds(f, (a, b), precision);
+
+    Ok(quadrature.integrate(f, epsilon, (&a, &b)))
+}
+
+/// Validates the bounds a..b before integrating over them with `quadrature`
+/// as in [`fn@integrate_bounds`], stopping early once `cancel` is set (see
+/// [`fn@integrate_cancellable`])
+fn integrate_bounds_cancellable(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    (a, b): (&Float, &Float),
+    cancel: &AtomicBool,
+) -> Result<(Float, Float), CancellableError> {
+    let f = check_integrand(f);
+
+    check_bounds((a, b))?;
+
+    if a == b {
+        return Ok((
+            Float::with_val_64(precision, Special::Zero),
+            Float::with_val_64(precision, Special::Zero),
+        ));
+    }
+
+    let (f, (a, b)) = quadrature::finite_bounds(f, (a, b), precision);
+
+    integrate_cancellable(quadrature, f, epsilon, (&a, &b), cancel).map_err(|(value, error)| {
+        CancellableError::Quadrature(QuadratureError::Cancelled { value, error })
+    })
+}
+
+/// Integrates over a..b with `Quadrature::try_integrate_cancellable`,
+/// returning the partial result as an error if it was cancelled
+///
+/// Integrals which don't meet the tolerance are returned as they are by
+/// `Quadrature::integrate`, so that cancellation is the only difference
+fn integrate_cancellable(
+    quadrature: &impl Quadrature<Float>,
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    (a, b): (&Float, &Float),
+    cancel: &AtomicBool,
+) -> Result<(Float, Float), (Float, Float)> {
+    match quadrature.try_integrate_cancellable(f, epsilon, (a, b), cancel) {
+        Ok(result) => Ok(result),
+        Err(
+            QuadratureError::NotConverged { value, error, .. }
+            | QuadratureError::Divergent { value, error },
+        ) => Ok((value, error)),
+        Err(QuadratureError::Cancelled { value, error }) => Err((value, error)),
+    }
+}
+
+/// Returns a [`enum@BoundsError`] if the bounds a..b cannot be integrated
+/// over
+fn check_bounds((a, b): (&Float, &Float)) -> Result<(), BoundsError> {
+    if !a.is_finite() {
+        return Err(BoundsError::NonFiniteLower { a: a.clone() });
+    }
+
+    if b.is_nan() {
+        return Err(BoundsError::NanUpper);
+    }
+
+    if a > b {
+        return Err(BoundsError::Reversed {
+            a: a.clone(),
+            b: b.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns a [`enum@BoundsError`] if the bounds a..b are not a valid span of
+/// times since the beginning of an exposure, which must additionally be
+/// nonnegative and finite
+pub(crate) fn check_exposure_bounds((a, b): (&Float, &Float)) -> Result<(), BoundsError> {
+    check_bounds((a, b))?;
+
+    if *a < 0 {
+        return Err(BoundsError::NegativeLower { a: a.clone() });
+    }
+
+    if b.is_infinite() {
+        return Err(BoundsError::InfiniteUpper);
+    }
+
+    Ok(())
+}
+
+/// Wraps an integrand such that the first non-finite value it evaluates to
+/// is warned of, rather than silently poisoning the integral
+///
+/// As with [`fn@check_precision`], this is only done in debug builds with the
+/// `tracing` feature enabled, and otherwise returns `f` as is. Use
+/// `Beam::try_evaluate_with` to find which term is responsible
+fn check_integrand(f: impl Fn(Float) -> Float) -> impl Fn(Float) -> Float {
+    #[cfg(all(feature = "tracing", debug_assertions))]
+    let warned = std::sync::atomic::AtomicBool::new(false);
+
+    move |t| {
+        #[cfg(all(feature = "tracing", debug_assertions))]
+        let time = t.clone();
+
+        let value = f(t);
+
+        #[cfg(all(feature = "tracing", debug_assertions))]
+        if !value.is_finite() && !warned.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            tracing::warn!(t = %time, %value, "the integrand is not finite");
+        }
+
+        value
+    }
+}
+
+fn integrate_series(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    start: &Float,
+    times: &[Float],
+    mut progress: impl FnMut(usize),
+) -> Vec<(Float, Float)> {
+    let mut series = Vec::with_capacity(times.len());
+
+    integrate_stream(
+        precision,
+        quadrature,
+        f,
+        epsilon,
+        start,
+        times,
+        |_, sum, error| {
+            series.push((sum.clone(), error.clone()));
+            progress(series.len());
+        },
+    );
+
+    series
+}
+
+fn integrate_stream(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    start: &Float,
+    times: &[Float],
+    mut callback: impl FnMut(usize, &Float, &Float),
+) {
+    let f = check_integrand(f);
+
+    let mut sum = Float::with_val_64(precision, Special::Zero);
+    let mut error = Float::with_val_64(precision, Special::Zero);
+    let mut previous = start;
+
+    for (i, time) in times.iter().enumerate() {
+        if time != previous {
+            let (integral, interval_error) = quadrature.integrate(&f, epsilon, (previous, time));
+            sum += integral;
+            error.max_mut(&interval_error);
+        }
+
+        callback(i, &sum, &error);
+        previous = time;
+    }
+}
+
+/// Calculates the partial derivative of the temperature rise resulting from a
+/// [`struct@LargeBeam`] over the interval a..b with respect to the `mu_a` of
+/// `layer`
+///
+/// The integrand of [`fn@temperature_rise`] is differentiated analytically
+/// (see [`LargeBeam::evaluate_d_mu_a_with`]) before being integrated, and the
+/// integral and approximate error are returned as in [`fn@temperature_rise`]
+#[inline]
+pub fn temperature_rise_d_mu_a(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, Float), BoundsError> {
+    integrate_bounds(
+        precision,
+        quadrature,
+        |t| LargeBeam.evaluate_d_mu_a_with(precision, thermal_properties, layer, z, &t),
+        epsilon,
+        bounds,
+    )
+}
+
+/// Calculates the absolute temperature over the interval a..b given a
+/// baseline temperature
+///
+/// This is the same as [`fn@temperature_rise`], with `baseline` added to the
+/// resulting temperature rise. The error estimate is left untouched, and the
+/// bounds are validated in the same way
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn temperature(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    baseline: &Float,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, Float), BoundsError> {
+    let (mut temperature, error) = temperature_rise(
+        precision,
+        quadrature,
+        beam,
+        thermal_properties,
+        layer,
+        z,
+        r,
+        epsilon,
+        bounds,
+    )?;
+
+    temperature += baseline;
+
+    Ok((temperature, error))
+}
+
+/// Calculates the temperature rise over the interval a..b, flagging results
+/// which are not physically meaningful
+///
+/// This is the same as [`fn@temperature_rise`], except that if the absolute
+/// temperature reached (`baseline` plus the temperature rise) exceeds
+/// `ceiling`, such as the temperature at which tissue vaporizes,
+/// [`CeilingError::Exceeded`] is returned. The model is linear in the
+/// irradiance and assumes the thermal properties are constant, which no
+/// longer holds past such a point, but the result is still carried by the
+/// error for callers which want it regardless. Nothing is clamped
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise_checked(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    baseline: &Float,
+    ceiling: &Float,
+    epsilon: &Float,
+    bounds: (&Float, &Float),
+) -> Result<(Float, Float), CeilingError> {
+    let (temperature_rise, error) = temperature_rise(
+        precision,
+        quadrature,
+        beam,
+        thermal_properties,
+        layer,
+        z,
+        r,
+        epsilon,
+        bounds,
+    )?;
+
+    let temperature = Float::with_val_64(precision, &temperature_rise + baseline);
+
+    if temperature > *ceiling {
+        return Err(CeilingError::Exceeded {
+            temperature_rise,
+            error,
+            temperature,
+        });
+    }
+
+    Ok((temperature_rise, error))
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic;
+
+    use crate::{
+        quadrature::{GaussKronrod, G7_K15},
+        test_beams::GaussianBeam,
+    };
+
+    #[ctor::ctor]
+    static ZERO: Float = Float::with_val_64(64, Special::Zero);
+
+    #[ctor::ctor]
+    static ONE: Float = Float::with_val_64(64, 1.0);
+
+    #[ctor::ctor]
+    static EPSILON: Float = Float::with_val_64(64, 1e-16);
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(
+            ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063),
+            ThermalProperties {
+                rho: Cow::Owned(Float::with_val_64(64, 1.0)),
+                c: Cow::Owned(Float::with_val_64(64, 4.178)),
+                k: Cow::Owned(Float::with_val_64(64, 0.0063)),
+            }
+        );
+
+        assert_eq!(
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+            Layer {
+                d: Cow::Owned(Float::with_val_64(64, 0.001)),
+                z0: Cow::Owned(Float::with_val_64(64, 0.0)),
+                mu_a: Cow::Owned(Float::with_val_64(64, 1000.0)),
+                e0: Cow::Owned(Float::with_val_64(64, 1.0)),
+                n: None,
+            }
+        );
+
+        assert_eq!(
+            Layer::from_f64(128, 0.001, 0.0, 1000.0, 1.0).d.prec_64(),
+            128
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063).to_string(),
+            "rho = 1.00000 g*cm^3, c = 4.17800 J*g^-1*K^-1, k = 6.30000e-3 W*cm^-1*K^-1"
+        );
+
+        let layers = MultiLayer::new([
+            Layer::from_f64(64, 0.01, 0.001, 10.0, 0.0),
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+
+        assert_eq!(
+            layers.to_string(),
+            "z0 = 0 cm, d = 1.00000e-3 cm, mu_a = 1000.00 cm^-1, e0 = 1.00000 W*cm^-2\n\
+             z0 = 1.00000e-3 cm, d = 1.00000e-2 cm, mu_a = 10.0000 cm^-1, e0 = 3.67879e-1 W*cm^-2"
+        );
+    }
+
+    #[test]
+    fn large_beam_sanity() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+
+        assert_eq!(
+            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &ZERO),
+            5e-1
+        );
+
+        let mut result =
+            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ZERO);
+        // reference result: 0.5 * e^-1
+        result -= 1.8393972058572116080e-1;
+        result.abs_mut();
+        assert!(result < *EPSILON);
+
+        let mut result =
+            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
+        // reference result: 0.5 * e^-1 * e^1 * (erf(1) - erf(-1/sqrt(4) + 1))
+        result -= 1.6110045756833416583e-1;
+        result.abs_mut();
+        println!("{}", result);
+        assert!(result < *EPSILON);
+    }
+
+    #[test]
+    fn prepared_beam() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0);
+        let z = Float::with_val_64(64, 0.0005);
+        let prepared = LargeBeam.prepare(64, &thermal_properties, &layer, &z);
+
+        for tp in [0.0, 1e-6, 1e-3, 1e-1, 1.0] {
+            let tp = Float::with_val_64(64, tp);
+
+            assert_eq!(
+                prepared.evaluate_at_time(&tp),
+                LargeBeam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &tp)
+            );
+        }
+    }
+
+    #[test]
+    fn time_plan() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0);
+
+        // the nodes of a Gauss-Legendre rule over 0..0.01, alongside 0
+        let mut times = vec![Float::with_val_64(64, Special::Zero)];
+        times.extend(
+            crate::quadrature::gauss_legendre_rule(16, 64)
+                .iter()
+                .map(|(x, _)| {
+                    let mut tp = Float::with_val_64(64, x + 1);
+                    tp *= 0.005;
+                    tp
+                }),
+        );
+
+        let plan = LargeBeam.plan(64, &thermal_properties, &layer, &times);
+        assert_eq!(plan.times(), times);
+
+        for z in [-0.001, 0.0, 0.0005, 0.001, 0.002] {
+            let z = Float::with_val_64(64, z);
+            let planned = plan.evaluate(&LargeBeam.prepare(64, &thermal_properties, &layer, &z));
+
+            // the planned computation performs the same operations in the
+            // same order, so nothing is lost to rounding
+
+            assert_eq!(
+                planned,
+                times
+                    .iter()
+                    .map(|tp| LargeBeam.evaluate_with(
+                        64,
+                        &thermal_properties,
+                        &layer,
+                        &z,
+                        &ZERO,
+                        tp
+                    ))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn flat_top_beam_sanity() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let beam = FlatTopBeam {
+            radius: Cow::Borrowed(&ONE),
+        };
+
+        assert_eq!(
+            beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &ZERO),
+            5e-1
+        );
+
+        let mut result = beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ZERO);
+        // reference result: 0.5 * e^-1 * (1 - 0)
+        result -= 1.8393972058572116080e-1;
+        result.abs_mut();
+        assert!(result < *EPSILON);
+
+        let mut result = beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
+        // reference result: 0.5 * e^-1 * e^1 * (erf(1) - erf(-1/sqrt(4) + 1))
+        //                       * (1 - e^(-1/4))
+        result -= 3.5635295060953884529e-2;
+        result.abs_mut();
+        println!("{}", result);
+        assert!(result < *EPSILON);
+    }
+
+    #[test]
+    fn radial_factor() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let radius = Float::with_val_64(64, 0.01);
+        let beam = FlatTopBeam::new(&radius).expect("Invalid beam");
+        let z = Float::with_val_64(64, 0.005);
+
+        // the result separates into the axial factor of LargeBeam and the
+        // radial factor, which is 1 for LargeBeam itself
+
+        for r in [0.0, 0.005, 0.02] {
+            let r = Float::with_val_64(64, r);
+
+            for tp in [0.0, 1e-3, 1e-1] {
+                let tp = Float::with_val_64(64, tp);
+
+                let axial = LargeBeam.evaluate_with(64, &thermal_properties, &layer, &z, &r, &tp);
+                assert_eq!(LargeBeam.radial_factor(64, &thermal_properties, &r, &tp), 1);
+                assert_eq!(
+                    beam.evaluate_with(64, &thermal_properties, &layer, &z, &r, &tp),
+                    axial * beam.radial_factor(64, &thermal_properties, &r, &tp)
+                );
+            }
+        }
+
+        let outside = Float::with_val_64(64, 0.02);
+        assert_eq!(
+            beam.radial_factor(64, &thermal_properties, &outside, &ZERO),
+            0
+        );
+
+        // by default, the factor is recovered from the result itself
+
+        let gaussian = GaussianBeam {
+            radius: radius.clone(),
+        };
+        let r = Float::with_val_64(64, 0.005);
+        let tp = Float::with_val_64(64, 1e-2);
+
+        let mut spread = Float::with_val_64(64, 0.0063 / 4.178 * 8.0 * 1e-2);
+        spread += Float::with_val_64(64, radius.square_ref());
+
+        let mut expected = Float::with_val_64(64, r.square_ref());
+        expected *= -2.0;
+        expected /= &spread;
+        expected.exp_mut();
+        expected *= Float::with_val_64(64, radius.square_ref());
+        expected /= spread;
+
+        let mut error = gaussian.radial_factor(64, &thermal_properties, &r, &tp);
+        error -= &expected;
+        error /= expected;
+        error.abs_mut();
+        assert!(error < 1e-15);
+    }
+
+    #[test]
+    fn multi_layer_sanity() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let layers = MultiLayer::single(layer.clone());
+
+        let mut result =
+            layers.evaluate_with(64, &LargeBeam, &thermal_properties, &ONE, &ZERO, &ONE);
+        result -= LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
+        assert!(result < *EPSILON);
+
+        let layers = MultiLayer::new([
+            Layer {
+                d: Cow::Borrowed(&ONE),
+                z0: Cow::Borrowed(&ZERO),
+                mu_a: Cow::Borrowed(&ONE),
+                e0: Cow::Borrowed(&ONE),
+                n: None,
+            },
+            Layer {
+                d: Cow::Borrowed(&ONE),
+                z0: Cow::Borrowed(&ONE),
+                mu_a: Cow::Borrowed(&ONE),
+                e0: Cow::Borrowed(&ZERO),
+                n: None,
+            },
+        ])
+        .expect("Unable to construct a MultiLayer");
+
+        let layer = Layer {
+            d: Cow::Owned(Float::with_val_64(64, 2)),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+
+        let beam = FlatTopBeam {
+            radius: Cow::Borrowed(&ONE),
+        };
+
+        let small = Float::with_val_64(64, 1e-6);
+
+        let mut result = layers.evaluate_with(64, &beam, &thermal_properties, &ZERO, &ZERO, &small);
+        result -= beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &small);
+        assert!(result < *EPSILON);
+
+        // a layer receiving no irradiance is skipped, which is the same as
+        // evaluating it in full
+
+        let shadowed = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Owned(Float::with_val_64(64, 2)),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ZERO),
+            n: None,
+        };
+        let full = LargeBeam
+            .prepare(64, &thermal_properties, &shadowed, &ONE)
+            .evaluate_at_time(&ONE);
+        assert_eq!(full, 0);
+        assert_eq!(
+            LargeBeam.evaluate_with(64, &thermal_properties, &shadowed, &ONE, &ZERO, &ONE),
+            full
+        );
+
+        let mut with_shadowed = layers.clone();
+        with_shadowed.layers.push(shadowed.clone().into_owned());
+
+        assert_eq!(
+            with_shadowed.evaluate_with(64, &LargeBeam, &thermal_properties, &ONE, &ZERO, &ONE),
+            layers.evaluate_with(64, &LargeBeam, &thermal_properties, &ONE, &ZERO, &ONE)
+        );
+        assert_eq!(
+            with_shadowed.evaluate_with_compensated(
+                64,
+                &LargeBeam,
+                &thermal_properties,
+                &ONE,
+                &ZERO,
+                &ONE
+            ),
+            layers.evaluate_with_compensated(
+                64,
+                &LargeBeam,
+                &thermal_properties,
+                &ONE,
+                &ZERO,
+                &ONE
+            )
+        );
+
+        // nor does it produce NaN where its other factors are infinite
+
+        let degenerate = Layer {
+            mu_a: Cow::Owned(Float::with_val_64(64, Special::Infinity)),
+            ..shadowed
+        };
+        let prepared = LargeBeam.prepare(64, &thermal_properties, &degenerate, &ONE);
+        assert_eq!(prepared.evaluate_at_time(&ONE), 0);
+        assert_eq!(
+            prepared
+                .try_evaluate_at_time(&ONE)
+                .expect("A layer without irradiance was rejected"),
+            0
+        );
+        assert!(LargeBeam
+            .plan(
+                64,
+                &thermal_properties,
+                &degenerate,
+                &[ZERO.clone(), ONE.clone()]
+            )
+            .evaluate(&prepared)
+            .iter()
+            .all(|result| *result == 0));
+        assert!(LargeBeam
+            .evaluate_many(
+                64,
+                &thermal_properties,
+                &degenerate,
+                &[(ONE.clone(), ZERO.clone(), ONE.clone())],
+            )
+            .iter()
+            .all(|result| *result == 0));
+        assert_eq!(
+            LargeBeam.evaluate_with(64, &thermal_properties, &degenerate, &ONE, &ZERO, &ONE),
+            0
+        );
+        assert_eq!(
+            LargeBeam
+                .try_evaluate_with(64, &thermal_properties, &degenerate, &ONE, &ZERO, &ONE)
+                .expect("A layer without irradiance was rejected"),
+            0
+        );
+        assert_eq!(
+            beam.evaluate_with(64, &thermal_properties, &degenerate, &ONE, &ZERO, &small),
+            0
+        );
+
+        // as do the beams which integrate over their profiles
+
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let laguerre_gaussian = LaguerreGaussianBeam::new(&ONE, 1, 1, quadrature, &EPSILON)
+            .expect("Unable to construct a LaguerreGaussianBeam");
+        let bessel = BesselBeam {
+            k_r: Cow::Borrowed(&ONE),
+            quadrature,
+            epsilon: Cow::Borrowed(&EPSILON),
+        };
+        let elliptical = EllipticalFlatTopBeam::new(&ONE, &small, quadrature, &EPSILON)
+            .expect("Unable to construct an EllipticalFlatTopBeam");
+
+        for r in [&*ZERO, &*ONE] {
+            assert_eq!(
+                laguerre_gaussian.evaluate_with(
+                    64,
+                    &thermal_properties,
+                    &degenerate,
+                    &ONE,
+                    r,
+                    &ONE
+                ),
+                0
+            );
+            assert_eq!(
+                bessel.evaluate_with(64, &thermal_properties, &degenerate, &ONE, r, &ONE),
+                0
+            );
+            assert_eq!(
+                elliptical.evaluate_with(64, &thermal_properties, &degenerate, &ONE, r, &ONE),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn evaluate_breakdown() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let mut multi_layer = MultiLayer::new([
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+            Layer::from_f64(64, 0.002, 0.001, 100.0, 0.0),
+            Layer::from_f64(64, 0.01, 0.003, 10.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+
+        // the bottom layer is shadowed entirely
+
+        multi_layer.layers[2].e0 = Cow::Owned(Float::with_val_64(64, Special::Zero));
+
+        let z = Float::with_val_64(64, 0.0015);
+        let tp = Float::with_val_64(64, 1e-3);
+
+        let breakdown =
+            multi_layer.evaluate_breakdown(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp);
+        assert_eq!(breakdown.len(), 3);
+        assert!(breakdown[..2].iter().all(|contribution| *contribution > 0));
+        assert!(breakdown[2].is_zero());
+
+        let mut sum = Float::with_val_64(64, Special::Zero);
+        for contribution in &breakdown {
+            sum += contribution;
+        }
+        sum -= multi_layer.evaluate_with(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp);
+        assert!(sum.abs() <= *EPSILON);
+    }
+
+    #[test]
+    fn discretize_constant() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let single = MultiLayer::single(layer);
+        let discretized = MultiLayer::discretize(64, &ONE, &ZERO, |_| ONE.clone(), &ONE, 8);
+
+        assert_eq!(discretized.layers.len(), 8);
+
+        for z in [0.0, 0.3, 1.0, 1.5] {
+            let z = Float::with_val_64(64, z);
+
+            for tp in [1e-3, 1.0] {
+                let tp = Float::with_val_64(64, tp);
+
+                let mut result =
+                    discretized.evaluate_with(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp);
+                result -= single.evaluate_with(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp);
+                result.abs_mut();
+                assert!(result < *EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn composite_beam_sanity() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let beam = FlatTopBeam {
+            radius: Cow::Borrowed(&ONE),
+        };
+        let composite = CompositeBeam {
+            beams: vec![Box::new(beam.clone())],
+        };
+
+        assert_eq!(
+            composite.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE),
+            beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE)
+        );
+
+        let layers = MultiLayer::single(layer.clone());
+
+        assert_eq!(
+            layers.evaluate_with(64, &composite, &thermal_properties, &ONE, &ZERO, &ONE),
+            layers.evaluate_with(64, &beam, &thermal_properties, &ONE, &ZERO, &ONE)
+        );
+
+        let wide_beam = FlatTopBeam {
+            radius: Cow::Owned(Float::with_val_64(64, 2)),
+        };
+        let composite = CompositeBeam {
+            beams: vec![Box::new(beam.clone()), Box::new(wide_beam.clone())],
+        };
+
+        let mut result =
+            composite.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
+        result -= beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
+        result -= wide_beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
+        result.abs_mut();
+        assert!(result < *EPSILON);
+    }
+
+    #[test]
+    fn laguerre_gaussian_beam_on_axis() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let beam = LaguerreGaussianBeam {
+            radius: Cow::Borrowed(&ONE),
+            p: 0,
+            l: 1,
+            quadrature: GaussKronrod {
+                interval_limit: 1024,
+                precision: 64,
+                rule: &G7_K15,
+            },
+            epsilon: Cow::Owned(Float::with_val_64(64, 1e-12)),
+        };
+
+        // the donut has no intensity on its axis, so nothing is absorbed there
+        // initially
+
+        assert_eq!(
+            beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ZERO),
+            0
+        );
+
+        let mut previous = Float::with_val_64(64, Special::Zero);
+
+        for tp in [1e-4, 1e-3, 1e-2] {
+            let tp = Float::with_val_64(64, tp);
+            let result = beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &tp);
+
+            // reference result: z_factor * 2w^2 * 4tp / (2 * 4tp + w^2)^2
+            let mut reference = Float::with_val_64(64, &tp * 8.0);
+            reference += 1;
+            reference.square_mut();
+            reference.recip_mut();
+            reference *= &tp;
+            reference *= 8.0;
+            reference *= LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &tp);
+
+            let mut error = Float::with_val_64(64, &result - &reference);
+            error.abs_mut();
+            assert!(error < 1e-10);
+
+            assert!(result > previous);
+            previous = result;
+        }
+    }
+
+    #[test]
+    fn d_mu_a_matches_finite_difference() {
+        let thermal_properties = ThermalProperties::from_f64(128, 1.0, 1.0, 1.0);
+        let layer = Layer::from_f64(128, 1.0, 0.0, 1.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 128,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(128, 1e-24);
+        let zero = Float::with_val_64(128, Special::Zero);
+        let end = Float::with_val_64(128, 1.0);
+        let step = Float::with_val_64(128, 1e-12);
+
+        for z in [0.0, 0.5, 1.0] {
+            let z = Float::with_val_64(128, z);
+
+            let (derivative, _) = temperature_rise_d_mu_a(
+                128,
+                &quadrature,
+                &thermal_properties,
+                &layer,
+                &z,
+                &epsilon,
+                (&zero, &end),
+            )
+            .expect("Invalid bounds");
+
+            let rise_at = |offset: &Float| {
+                let layer = Layer {
+                    mu_a: Cow::Owned(Float::with_val_64(128, layer.mu_a.as_ref() + offset)),
+                    ..layer.clone()
+                };
+
+                temperature_rise(
+                    128,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &layer,
+                    &z,
+                    &zero,
+                    &epsilon,
+                    (&zero, &end),
+                )
+                .expect("Invalid bounds")
+                .0
+            };
+
+            let mut finite_difference = rise_at(&step);
+            finite_difference -= rise_at(&Float::with_val_64(128, -&step));
+            finite_difference /= 2.0;
+            finite_difference /= &step;
+
+            let mut result = derivative;
+            result -= finite_difference;
+            result.abs_mut();
+            assert!(result < 1e-18);
+        }
+
+        // the derivative of the integrand is handled separately when tp = 0
+
+        let mut result =
+            LargeBeam.evaluate_d_mu_a_with(128, &thermal_properties, &layer, &end, &zero);
+        // reference result: 0.5 * e^-1 * (1 - 1)
+        result.abs_mut();
+        assert!(result < 1e-30);
+    }
+
+    #[test]
+    fn bounds_validation() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let nan = Float::with_val_64(64, Special::Nan);
+        let infinity = Float::with_val_64(64, Special::Infinity);
+
+        let rise = |bounds| {
+            temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ONE,
+                &ZERO,
+                &EPSILON,
+                bounds,
+            )
+        };
+
+        assert!(matches!(
+            rise((&ONE, &ZERO)),
+            Err(BoundsError::Reversed { .. })
+        ));
+        assert!(matches!(
+            rise((&nan, &ONE)),
+            Err(BoundsError::NonFiniteLower { .. })
+        ));
+        assert!(matches!(
+            rise((&infinity, &infinity)),
+            Err(BoundsError::NonFiniteLower { .. })
+        ));
+        assert!(matches!(rise((&ZERO, &nan)), Err(BoundsError::NanUpper)));
+
+        let (result, error) = rise((&ONE, &ONE)).expect("Equal bounds were rejected");
+        assert_eq!(result, 0);
+        assert_eq!(error, 0);
+
+        // an infinite upper bound is permitted, and approaches the steady state
+        // for a beam of finite radius
+
+        let beam = FlatTopBeam {
+            radius: Cow::Borrowed(&ONE),
+        };
+        let (result, _) = temperature_rise(
+            64,
+            &quadrature,
+            &beam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            &EPSILON,
+            (&ZERO, &infinity),
+        )
+        .expect("An infinite upper bound was rejected");
+        assert!(result.is_finite());
+        assert!(result > 0);
+    }
+
+    #[test]
+    fn tabulated_source() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.02, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-9);
+
+        // mu_a * e0 * exp(-mu_a * z) sampled across the layer, which linear
+        // interpolation overestimates by at most (h * mu_a)^2 / 8 = 1.25e-5 of it
+
+        let depths: Vec<Float> = (0..=200)
+            .map(|i| Float::with_val_64(64, 0.02 * f64::from(i) / 200.0))
+            .collect();
+        let power_density = depths
+            .iter()
+            .map(|z| {
+                let mut power_density = Float::with_val_64(64, z * -100.0);
+                power_density.exp_mut();
+                power_density *= 100.0;
+                power_density
+            })
+            .collect();
+        let source = TabulatedSource::new(depths, power_density)
+            .expect("Unable to construct a TabulatedSource");
+
+        let assert_close = |tabulated: Float, analytic: Float| {
+            if tabulated == analytic {
+                return;
+            }
+
+            let mut error = Float::with_val_64(64, &tabulated - &analytic);
+            error /= &analytic;
+            error.abs_mut();
+            assert!(error < 2e-5, "{tabulated} != {analytic}");
+        };
+
+        for z in [0.0, 0.005, 0.02, 0.03] {
+            let z = Float::with_val_64(64, z);
+
+            for tp in [0.0, 1e-3, 1e-1] {
+                let tp = Float::with_val_64(64, tp);
+
+                assert_close(
+                    source.evaluate_with(64, &thermal_properties, &z, &tp),
+                    LargeBeam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &tp),
+                );
+            }
+        }
+
+        let z = Float::with_val_64(64, 0.005);
+        let end = Float::with_val_64(64, 0.01);
+        assert_close(
+            source
+                .temperature_rise(
+                    64,
+                    &quadrature,
+                    &thermal_properties,
+                    &z,
+                    &epsilon,
+                    (&ZERO, &end),
+                )
+                .expect("Invalid bounds")
+                .0,
+            temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &epsilon,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds")
+            .0,
+        );
+
+        let samples = |depths: &[f64], power_density: &[f64]| {
+            TabulatedSource::new(
+                depths.iter().map(|&z| Float::with_val_64(64, z)).collect(),
+                power_density
+                    .iter()
+                    .map(|&p| Float::with_val_64(64, p))
+                    .collect(),
+            )
+        };
+
+        assert_eq!(
+            samples(&[0.0, 1.0], &[1.0]),
+            Err(TabulatedSourceError::LengthMismatch {
+                depths: 2,
+                power_density: 1
+            })
+        );
+        assert_eq!(
+            samples(&[0.0], &[1.0]),
+            Err(TabulatedSourceError::TooFewSamples(1))
+        );
+        assert_eq!(
+            samples(&[0.0, 1.0], &[1.0, f64::NAN]),
+            Err(TabulatedSourceError::NonFinite(1))
+        );
+        assert_eq!(
+            samples(&[0.0, 1.0, 1.0], &[1.0, 1.0, 1.0]),
+            Err(TabulatedSourceError::NotIncreasing(2))
+        );
+    }
+
+    #[test]
+    fn large_beam_has_no_steady_state() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+
+        let mut alpha = Float::with_val_64(64, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        // once heat has spread far beyond the layer, all of the absorbed
+        // power, e0 * (1 - e^(-mu_a * d)), is conducted as if from a plane,
+        // giving a temperature rise of that over rho * c times
+        // sqrt(tp / (pi * alpha))
+        let mut power = Float::with_val_64(64, -1);
+        power.exp_mut();
+        power = 1 - power;
+        power /= thermal_properties.rho.as_ref();
+        power /= thermal_properties.c.as_ref();
+
+        let mut previous_error = Float::with_val_64(64, Special::Infinity);
+
+        for end in [1e2, 1e3, 1e4] {
+            let end = Float::with_val_64(64, end);
+
+            let (rise, _) = temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds");
+
+            let mut asymptote = Float::with_val_64(64, &end / &alpha);
+            asymptote /= Float::with_val_64(64, Constant::Pi);
+            asymptote.sqrt_mut();
+            asymptote *= &power;
+
+            let mut error = rise;
+            error -= &asymptote;
+            error /= &asymptote;
+            error.abs_mut();
+
+            assert!(error < previous_error);
+            previous_error = error;
+        }
+
+        assert!(previous_error < 1e-2);
+    }
+
+    #[test]
+    fn steady_state() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let beam = GaussianBeam {
+            radius: Float::with_val_64(64, 0.01),
+        };
+        let z = Float::with_val_64(64, 0.005);
+
+        let steady_state = beam.steady_state(64, &thermal_properties, &layer, &z, &ZERO);
+        assert!(steady_state.is_finite());
+
+        // the temperature rise under a long exposure approaches the steady
+        // state from below, the remainder decaying as 1 / sqrt(tp). the
+        // integrand is concentrated at early times, so the exposure is
+        // integrated a decade at a time
+
+        let mut rise = Float::with_val_64(64, Special::Zero);
+        let mut start = Float::with_val_64(64, Special::Zero);
+        let mut previous_error = Float::with_val_64(64, Special::Infinity);
+
+        for exponent in -4..=6 {
+            let end = Float::with_val_64(64, 10.0f64.powi(exponent));
+            rise += temperature_rise(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &EPSILON,
+                (&start, &end),
+            )
+            .expect("Invalid bounds")
+            .0;
+            start = end;
+
+            assert!(rise < steady_state);
+
+            let mut error = Float::with_val_64(64, &steady_state - &rise);
+            error /= &steady_state;
+            assert!(error < previous_error);
+            previous_error = error;
+        }
+
+        assert!(previous_error < 1e-3);
+
+        // a beam with no steady state has an infinite one, except where there
+        // is nothing to heat
+
+        assert_eq!(
+            LargeBeam.steady_state(64, &thermal_properties, &layer, &z, &ZERO),
+            f64::INFINITY
+        );
+        assert_eq!(
+            LargeBeam.steady_state(
+                64,
+                &thermal_properties,
+                &Layer::from_f64(64, 0.01, 0.0, 100.0, 0.0),
+                &z,
+                &ZERO
+            ),
+            0
+        );
+        assert_eq!(
+            UniformBeam {
+                power_density: Cow::Borrowed(&ONE),
+            }
+            .steady_state(64, &thermal_properties, &layer, &z, &ZERO),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn estimate_precision() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let z = Float::with_val_64(64, 0.005);
+        let tp = Float::with_val_64(64, 0.01);
+        let target = Float::with_val_64(64, 1e-30);
+
+        // 1e-30 takes about 100 bits, and little is lost to cancellation
+        // inside of the layer
+
+        let precision = super::estimate_precision(
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &z,
+            &ZERO,
+            &tp,
+            &target,
+        )
+        .expect("Invalid target");
+        assert!((100..=140).contains(&precision));
+
+        let result =
+            LargeBeam.evaluate_with(precision, &thermal_properties, &layer, &z, &ZERO, &tp);
+        let exact = LargeBeam.evaluate_with(1024, &thermal_properties, &layer, &z, &ZERO, &tp);
+
+        let mut error = Float::with_val_64(1024, &result - &exact);
+        error /= &exact;
+        error.abs_mut();
+        assert!(error <= target);
+
+        // a looser target needs fewer bits
+
+        assert!(
+            super::estimate_precision(
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &tp,
+                &Float::with_val_64(64, 1e-6),
+            )
+            .expect("Invalid target")
+                < precision
+        );
+
+        // targets outside of 0..1 and results which are NaN are rejected
+
+        for target in [0.0, -1e-6, 1.0, f64::NAN] {
+            assert_eq!(
+                super::estimate_precision(
+                    &LargeBeam,
+                    &thermal_properties,
+                    &layer,
+                    &z,
+                    &ZERO,
+                    &tp,
+                    &Float::with_val_64(64, target),
+                ),
+                None
+            );
+        }
+
+        // the smallest positive target needs as many bits as its exponent,
+        // which is still within the supported precisions
+
+        let mut smallest = Float::with_val_64(64, Special::Zero);
+        smallest.next_up();
+        let precision = super::estimate_precision(
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &z,
+            &ZERO,
+            &tp,
+            &smallest,
+        )
+        .expect("Invalid target");
+        let exponent = smallest.get_exp().expect("Zero target").unsigned_abs();
+        assert!(precision > u64::from(exponent));
+        assert!(precision <= u64::from(float::prec_max()));
+
+        let massless = ThermalProperties::from_f64(64, 0.0, 4.178, 0.0063);
+        assert_eq!(
+            super::estimate_precision(
+                &LargeBeam,
+                &massless,
+                &layer,
+                &z,
+                &ZERO,
+                &tp,
+                &Float::with_val_64(64, 1e-6),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn temperature_rise_ceiling() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let z = Float::with_val_64(64, 0.0005);
+        let end = Float::with_val_64(64, 0.01);
+
+        // body temperature, and the boiling point of water
+
+        let baseline = Float::with_val_64(64, 310.15);
+        let ceiling = Float::with_val_64(64, 373.15);
+
+        let rise = |e0: f64, bounds: (&Float, &Float)| {
+            let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, e0);
+            let unchecked = temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &EPSILON,
+                bounds,
+            );
+            let checked = temperature_rise_checked(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &baseline,
+                &ceiling,
+                &EPSILON,
+                bounds,
+            );
+
+            (unchecked, checked)
+        };
+
+        let (unchecked, checked) = rise(1.0, (&ZERO, &end));
+        assert_eq!(checked, Ok(unchecked.expect("Invalid bounds")));
+
+        // the same exposure at ten thousand times the irradiance would boil
+        // the tissue, but the result is still reported
+
+        let (unchecked, checked) = rise(1e4, (&ZERO, &end));
+        let (unchecked, unchecked_error) = unchecked.expect("Invalid bounds");
+
+        let Err(CeilingError::Exceeded {
+            temperature_rise,
+            error,
+            temperature,
+        }) = checked
+        else {
+            panic!("The ceiling was not flagged");
+        };
+        assert_eq!(temperature_rise, unchecked);
+        assert_eq!(error, unchecked_error);
+        assert_eq!(temperature, Float::with_val_64(64, &unchecked + &baseline));
+        assert!(temperature > ceiling);
+
+        assert!(matches!(
+            rise(1.0, (&end, &ZERO)).1,
+            Err(CeilingError::Bounds(BoundsError::Reversed { .. }))
+        ));
+    }
+
+    #[test]
+    fn temperature_baseline() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let layers = MultiLayer::single(layer.clone());
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let baseline = Float::with_val_64(64, 37);
+
+        for end in [1e-3, 1e-1, 1.0] {
+            let end = Float::with_val_64(64, end);
+
+            let (rise, rise_error) = temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ONE,
+                &ZERO,
+                &EPSILON,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds");
+            let (absolute, absolute_error) = temperature(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ONE,
+                &ZERO,
+                &baseline,
+                &EPSILON,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds");
+
+            assert_eq!(absolute, Float::with_val_64(64, &rise + &baseline));
+            assert_eq!(absolute_error, rise_error);
+
+            let (absolute, absolute_error) = layers
+                .temperature(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &ONE,
+                    &ZERO,
+                    &baseline,
+                    &EPSILON,
+                    (&ZERO, &end),
+                )
+                .expect("Invalid bounds");
+
+            assert_eq!(absolute, Float::with_val_64(64, &rise + &baseline));
+            assert_eq!(absolute_error, rise_error);
+        }
+    }
+
+    #[test]
+    fn try_evaluate_with_zero_rho() {
+        let thermal_properties = ThermalProperties::from_f64(64, 0.0, 1.0, 1.0);
+        let layer = Layer::from_f64(64, 1.0, 0.0, 1.0, 1.0);
+
+        for tp in [&*ZERO, &*ONE] {
+            assert!(matches!(
+                LargeBeam.try_evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, tp),
+                Err(EvalError::NonFinite {
+                    term: "alpha = k / (rho * c)",
+                    ..
+                })
+            ));
+        }
+
+        // the default implementation reports the same term
+
+        let beam = FlatTopBeam {
+            radius: Cow::Borrowed(&ONE),
+        };
+        assert!(matches!(
+            beam.try_evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE),
+            Err(EvalError::NonFinite {
+                term: "alpha = k / (rho * c)",
+                ..
+            })
+        ));
+
+        // finite inputs are evaluated identically to the unchecked path
+
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 1.0, 1.0);
+        assert_eq!(
+            LargeBeam
+                .try_evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE)
+                .expect("Finite inputs were rejected"),
+            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE)
+        );
+    }
+
+    #[test]
+    fn validate_thermal_properties() {
+        assert_eq!(
+            ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063).validate(),
+            Ok(())
+        );
+
+        assert!(matches!(
+            ThermalProperties::from_f64(64, 0.0, 4.178, 0.0063).validate(),
+            Err(ThermalError::NonPositive { name: "rho", .. })
+        ));
+        assert!(matches!(
+            ThermalProperties::from_f64(64, 1.0, 4.178, -0.0063).validate(),
+            Err(ThermalError::NonPositive { name: "k", .. })
+        ));
+        assert!(matches!(
+            ThermalProperties::from_f64(64, 1.0, f64::NAN, 0.0063).validate(),
+            Err(ThermalError::NonFinite { name: "c", .. })
+        ));
+        assert!(matches!(
+            ThermalProperties::from_f64(64, f64::INFINITY, 4.178, 0.0063).validate(),
+            Err(ThermalError::NonFinite { name: "rho", .. })
+        ));
+    }
+
+    #[test]
+    fn elliptical_flat_top_beam_circular() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let beam = EllipticalFlatTopBeam {
+            radius_x: Cow::Borrowed(&ONE),
+            radius_y: Cow::Borrowed(&ONE),
+            quadrature: GaussKronrod {
+                interval_limit: 1024,
+                precision: 64,
+                rule: &G7_K15,
+            },
+            epsilon: Cow::Borrowed(&epsilon),
+        };
+        let flat_top = FlatTopBeam {
+            radius: Cow::Borrowed(&ONE),
+        };
+
+        // the beams agree on the axis and at a time of 0. off of the axis,
+        // FlatTopBeam depends upon the (inaccurate) marcum-q function, so
+        // the radial factor is instead the convolution of the circular
+        // aperture with the heat kernel in polar coordinates, which with an
+        // alpha of 1 is `exp(-(r^2 + rho^2 - 2 * r * rho * cos(theta)) / (4 *
+        // tp)) / (4 * pi * tp)` over the unit disk
+
+        let mut two_pi = Float::with_val_64(64, Constant::Pi);
+        two_pi *= 2.0;
+
+        let radial_factor = |r: &Float, tp: &Float| {
+            let (mut factor, _) = beam.quadrature.integrate(
+                |rho| {
+                    let (mut integral, _) = beam.quadrature.integrate(
+                        |theta| {
+                            let mut exponent = theta.cos();
+                            exponent *= r;
+                            exponent *= &rho;
+                            exponent *= -2.0;
+                            exponent += Float::with_val_64(64, r.square_ref());
+                            exponent += Float::with_val_64(64, rho.square_ref());
+                            exponent /= tp;
+                            exponent /= -4.0;
+                            exponent.exp_mut();
+                            exponent
+                        },
+                        &epsilon,
+                        (&*ZERO, &two_pi),
+                    );
+                    integral *= &rho;
+                    integral
+                },
+                &epsilon,
+                (&*ZERO, &*ONE),
+            );
+            factor /= Float::with_val_64(64, Constant::Pi);
+            factor /= tp;
+            factor /= 4.0;
+            factor
+        };
+
+        let azimuths = [0.0, 1.0, 2.5].map(|azimuth| Float::with_val_64(64, azimuth));
+
+        for tp in [0.0, 1e-2, 1e-1, 1.0] {
+            let tp = Float::with_val_64(64, tp);
+
+            for r in [0.0, 0.5, 1.5] {
+                let r = Float::with_val_64(64, r);
+                let expected = if r == 0 || tp == 0 {
+                    flat_top.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp)
+                } else {
+                    LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp)
+                        * radial_factor(&r, &tp)
+                };
+
+                for azimuth in &azimuths {
+                    let mut error = beam.evaluate_with_azimuth(
+                        64,
+                        &thermal_properties,
+                        &layer,
+                        &ONE,
+                        &r,
+                        azimuth,
+                        &tp,
+                    );
+                    error -= &expected;
+                    error.abs_mut();
+                    assert!(error < 1e-10);
+                }
+            }
+        }
+
+        // an elliptical beam is wider along its major axis
+
+        let r = Float::with_val_64(64, 0.9);
+        let tp = Float::with_val_64(64, 1e-2);
+        let half = Float::with_val_64(64, 0.5);
+        let elliptical = EllipticalFlatTopBeam {
+            radius_y: Cow::Borrowed(&half),
+            ..beam.clone()
+        };
+        let along_minor = elliptical.evaluate_with_azimuth(
+            64,
+            &thermal_properties,
+            &layer,
+            &ONE,
+            &r,
+            &azimuths[1],
+            &tp,
+        );
+        assert!(
+            elliptical.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp) > along_minor
+        );
+    }
+
+    #[test]
+    fn beam_non_positive_radius() {
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+
+        for radius in [0.0, -1.0] {
+            let radius = Float::with_val_64(64, radius);
+
+            assert_eq!(
+                FlatTopBeam::new(&radius),
+                Err(BeamError::NonPositiveRadius {
+                    name: "radius",
+                    value: radius.clone(),
+                })
+            );
+            assert!(LaguerreGaussianBeam::new(&radius, 0, 0, quadrature, &EPSILON).is_err());
+            assert!(EllipticalFlatTopBeam::new(&ONE, &radius, quadrature, &EPSILON).is_err());
+        }
+
+        assert_eq!(
+            FlatTopBeam::new(&ONE).map(|beam| beam.radius().clone()),
+            Ok(ONE.clone())
+        );
+    }
+
+    #[test]
+    fn temperature_rise_converges_to_digits() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = |precision| GaussKronrod {
+            interval_limit: 1024,
+            precision,
+            rule: &G7_K15,
+        };
+        let end = Float::with_val_64(64, 0.01);
+
+        let (result, precision) = temperature_rise_to_digits(
+            8,
+            1024,
+            quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            (&ZERO, &end),
+        )
+        .expect("Did not converge");
+        assert!(precision <= 256);
+
+        let (reference, _) = temperature_rise(
+            256,
+            &quadrature(256),
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            &Float::with_val_64(256, 1e-20),
+            (&ZERO, &end),
+        )
+        .expect("Invalid bounds");
+
+        let mut error = Float::with_val_64(256, &result - &reference);
+        error /= &reference;
+        error.abs_mut();
+        assert!(error < 1e-8);
+    }
+
+    #[test]
+    fn temperature_rise_cross_check() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = |precision| GaussKronrod {
+            interval_limit: 1024,
+            precision,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-10);
+        let end = Float::with_val_64(64, 0.01);
+
+        let cross_check = |precision_low| {
+            super::temperature_rise_cross_check(
+                precision_low,
+                128,
+                quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds")
+        };
+
+        // 53 bits represent 15 decimal digits, nearly all of which should
+        // agree in such a well-conditioned case
+
+        let (result, digits) = cross_check(53);
+        assert!((12..=15).contains(&digits), "{digits} digits agree");
+
+        let (reference, _) = temperature_rise(
+            128,
+            &quadrature(128),
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            (&ZERO, &end),
+        )
+        .expect("Invalid bounds");
+        assert_eq!(result, reference);
+
+        // too few bits should be distrusted
+
+        let (_, digits) = cross_check(12);
+        assert!(digits <= 3, "{digits} digits agree");
+    }
+
+    #[test]
+    fn pulse_matches_difference() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let pulse_width = Float::with_val_64(64, 0.01);
+
+        let rise = |bounds: (&Float, &Float)| {
+            temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                bounds,
+            )
+            .expect("Invalid bounds")
+            .0
+        };
+        let pulse = |pulse_width: &Float, observation_time: &Float| {
+            temperature_rise_pulse(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                pulse_width,
+                observation_time,
+            )
+        };
+
+        for observation_time in [0.01, 0.02, 0.1] {
+            let observation_time = Float::with_val_64(64, observation_time);
+            let off = Float::with_val_64(64, &observation_time - &pulse_width);
+
+            let mut error = pulse(&pulse_width, &observation_time)
+                .expect("Invalid bounds")
+                .0;
+            error -= rise((&ZERO, &observation_time));
+            error += rise((&ZERO, &off));
+            error.abs_mut();
+            assert!(error < 1e-10);
+        }
+
+        // during the pulse, the exposure is simply continuous
+
+        let observation_time = Float::with_val_64(64, 0.005);
+        assert_eq!(
+            pulse(&pulse_width, &observation_time)
+                .expect("Invalid bounds")
+                .0,
+            rise((&ZERO, &observation_time))
+        );
+
+        let negative = Float::with_val_64(64, -0.01);
+        assert!(matches!(
+            pulse(&negative, &observation_time),
+            Err(BoundsError::Reversed { .. })
+        ));
+        assert!(matches!(
+            pulse(&pulse_width, &negative),
+            Err(BoundsError::Reversed { .. })
+        ));
+    }
+
+    #[test]
+    fn exposure_window() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let multi_layer = MultiLayer::single(layer.clone());
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let float = |value| Float::with_val_64(64, value);
+
+        let exposure = |start: f64, end: f64, observation_time: f64| {
+            temperature_rise_exposure(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&float(start), &float(end)),
+                &float(observation_time),
+            )
+            .expect("Invalid bounds")
+            .0
+        };
+        let close = |value: Float, expected: Float| {
+            let mut error = value;
+            error -= &expected;
+            error /= &expected;
+            error.abs_mut();
+            error < 1e-10
+        };
+
+        // an exposure beginning at 0 is a pulse
+
+        let pulse_width = float(0.01);
+        for observation_time in [0.005, 0.01, 0.02, 0.1] {
+            assert_eq!(
+                exposure(0.0, 0.01, observation_time),
+                temperature_rise_pulse(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &layer,
+                    &ZERO,
+                    &ZERO,
+                    &epsilon,
+                    &pulse_width,
+                    &float(observation_time),
+                )
+                .expect("Invalid bounds")
+                .0
+            );
+        }
+
+        // only the time elapsed since the exposure matters, not when it
+        // occurred
+
+        assert!(close(exposure(0.5, 0.51, 0.6), exposure(0.0, 0.01, 0.1)));
+        assert!(close(
+            exposure(0.5, 0.51, 0.505),
+            exposure(0.0, 0.01, 0.005)
+        ));
+
+        // the same exposure observed later has cooled, and an exposure which
+        // is observed before it begins has no effect
+
+        assert!(exposure(0.5, 0.51, 0.6) < exposure(0.5, 0.51, 0.52));
+        assert_eq!(exposure(0.5, 0.51, 0.4), 0);
+        assert_eq!(exposure(0.5, 0.51, 0.5), 0);
+
+        // an exposure which never ends is continuous from its start
+
+        assert!(close(
+            exposure(0.5, f64::INFINITY, 0.6),
+            exposure(0.0, 0.1, 0.1)
+        ));
+
+        assert_eq!(
+            multi_layer
+                .temperature_rise_exposure(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &ZERO,
+                    &ZERO,
+                    &epsilon,
+                    (&float(0.5), &float(0.51)),
+                    &float(0.6),
+                )
+                .expect("Invalid bounds")
+                .0,
+            exposure(0.5, 0.51, 0.6)
+        );
+
+        assert!(matches!(
+            temperature_rise_exposure(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&float(0.51), &float(0.5)),
+                &float(0.6),
+            ),
+            Err(BoundsError::Reversed { .. })
+        ));
+    }
+
+    #[test]
+    fn temporal_profile_constant() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+
+        for observation_time in [0.01, 0.1] {
+            let observation_time = Float::with_val_64(64, observation_time);
+            let beam = TemporalProfileBeam {
+                inner: LargeBeam,
+                profile: |_: &Float| Float::with_val_64(64, 1),
+                observation_time: Cow::Borrowed(&observation_time),
+            };
+
+            let (mut error, _) = temperature_rise(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&ZERO, &observation_time),
+            )
+            .expect("Invalid bounds");
+            error -= temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&ZERO, &observation_time),
+            )
+            .expect("Invalid bounds")
+            .0;
+            error.abs_mut();
+            assert!(error < 1e-12);
+        }
+
+        // a pulse peaking long after the observation time has barely begun
+
+        let observation_time = Float::with_val_64(64, 0.01);
+        let epsilon = Float::with_val_64(64, 1e-6);
+        let beam = TemporalProfileBeam {
+            inner: LargeBeam,
+            profile: gaussian_profile(
+                64,
+                Float::with_val_64(64, 1.0),
+                Float::with_val_64(64, 0.01),
+            ),
+            observation_time: Cow::Borrowed(&observation_time),
+        };
+        let (result, _) = temperature_rise(
+            64,
+            &quadrature,
+            &beam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            (&ZERO, &observation_time),
+        )
+        .expect("Invalid bounds");
+        assert!(result < 1e-100);
+        assert!(result > 0);
+    }
+
+    #[test]
+    fn multi_layer_tolerance() {
+        let top = Layer::from_f64(64, 0.1, 0.0, 1.0, 1.0);
+        let bottom = Layer::from_f64(64, 0.1, 0.1 - 1e-13, 1.0, 1.0);
+        let tolerance = Float::with_val_64(64, 1e-12);
+
+        assert!(matches!(
+            MultiLayer::new([top.clone(), bottom.clone()]),
+            Err(MultiLayerError::Overlap { .. })
+        ));
+
+        let multi_layer = MultiLayer::new_with_tolerance([bottom.clone(), top.clone()], &tolerance)
+            .expect("A sub-tolerance overlap was rejected");
+        assert_eq!(
+            *multi_layer.layers[1].z0,
+            Float::with_val_64(64, &*top.z0 + &*top.d)
+        );
+
+        let bottom = Layer::from_f64(64, 0.1, 0.1 - 1e-11, 1.0, 1.0);
+        assert!(matches!(
+            MultiLayer::new_with_tolerance([top, bottom], &tolerance),
+            Err(MultiLayerError::Overlap { .. })
+        ));
+    }
+
+    #[test]
+    fn auto_time_bound() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let pulse_width = Float::with_val_64(64, 1e-3);
+
+        let bound = |mu_a: f64| {
+            let layer = Layer::from_f64(64, 0.01, 0.0, mu_a, 1.0);
+            let mut bound = super::auto_time_bound(&layer, &thermal_properties, &pulse_width, 64);
+
+            let mut expected = layer.thermal_relaxation_time(&thermal_properties, 64);
+            expected *= AUTO_TIME_BOUND_RELAXATION_TIMES;
+            expected += &pulse_width;
+            assert_eq!(bound, expected);
+
+            bound -= &pulse_width;
+            bound
+        };
+
+        // doubling mu_a quarters the relaxation time, and so the time allowed
+        // after the pulse
+
+        let mut ratio = bound(100.0);
+        ratio /= bound(200.0);
+        ratio -= 4;
+        assert!(ratio.abs() < 1e-15);
+
+        assert!(bound(0.0).is_infinite());
+    }
+
+    #[test]
+    fn thermal_relaxation_time() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layer = Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        };
+
+        // alpha = 1 cm^2*s^-1, so tau = 1 / 4 s
+
+        assert_eq!(layer.thermal_relaxation_time(&thermal_properties, 64), 0.25);
+        assert_eq!(
+            thermal_properties.radial_thermal_relaxation_time(&Float::with_val_64(64, 2.0), 64),
+            1.0
+        );
+
+        // with water-like properties, alpha = 0.0063 / 4.178 cm^2*s^-1
+
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+
+        let mut error = layer.thermal_relaxation_time(&thermal_properties, 64);
+        error -= 4.178 / (4.0 * 0.0063 * 100.0 * 100.0);
+        error.abs_mut();
+        assert!(error < 1e-15);
+    }
+
+    #[test]
+    fn offset_composite_beam() {
+        let thermal_properties = ThermalProperties {
+            rho: Cow::Borrowed(&ONE),
+            c: Cow::Borrowed(&ONE),
+            k: Cow::Borrowed(&ONE),
+        };
+        let layers = MultiLayer::single(Layer {
+            d: Cow::Borrowed(&ONE),
+            z0: Cow::Borrowed(&ZERO),
+            mu_a: Cow::Borrowed(&ONE),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        });
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let beam = || FlatTopBeam {
+            radius: Cow::Owned(Float::with_val_64(64, 0.5)),
+        };
+        let offset = Float::with_val_64(64, 2.0);
+
+        let rise = |beam: &OffsetCompositeBeam, r: &Float| {
+            layers
+                .temperature_rise(
+                    64,
+                    &quadrature,
+                    beam,
+                    &thermal_properties,
+                    &ONE,
+                    r,
+                    &epsilon,
+                    (&*ZERO, &*ONE),
+                )
+                .expect("Invalid bounds")
+                .0
+        };
+
+        // two coincident beams deposit twice the heat of one
+
+        let single = OffsetCompositeBeam {
+            beams: vec![(Box::new(beam()), ZERO.clone())],
+        };
+        let coincident = OffsetCompositeBeam {
+            beams: vec![
+                (Box::new(beam()), ZERO.clone()),
+                (Box::new(beam()), ZERO.clone()),
+            ],
+        };
+
+        let mut error = rise(&single, &ZERO);
+        error *= 2.0;
+        error -= rise(&coincident, &ZERO);
+        error.abs_mut();
+        assert!(error < 1e-12);
+
+        // a beam is centered on its offset
+
+        let shifted = OffsetCompositeBeam {
+            beams: vec![(Box::new(beam()), offset.clone())],
+        };
+        assert_eq!(rise(&shifted, &offset), rise(&single, &ZERO));
+    }
+
+    #[test]
+    fn multi_layer_cancellable() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layers = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-9);
+        let tp = Float::with_val_64(64, 0.01);
+        let times = [0.0, 0.01, 0.02, 0.05]
+            .map(|time| Float::with_val_64(64, time))
+            .to_vec();
+
+        // without cancellation, the results are the same as those of the
+        // methods they mirror
+
+        let cancel = AtomicBool::new(false);
+        let temperature_rise = |cancel: &AtomicBool| {
+            layers.temperature_rise_cancellable(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&ZERO, &tp),
+                cancel,
+            )
+        };
+        assert_eq!(
+            temperature_rise(&cancel),
+            Ok(layers
+                .temperature_rise(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &ZERO,
+                    &ZERO,
+                    &epsilon,
+                    (&ZERO, &tp),
+                )
+                .expect("Invalid bounds"))
+        );
+
+        let series = layers.temperature_rise_series(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            &ZERO,
+            &times,
+        );
+        let mut streamed = Vec::new();
+        layers
+            .temperature_rise_stream_cancellable(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                &ZERO,
+                &times,
+                |_, temperature_rise, error| {
+                    streamed.push((temperature_rise.clone(), error.clone()));
+                },
+                &cancel,
+            )
+            .expect("The series was cancelled");
+        assert_eq!(streamed, series);
+
+        // once the flag is set, the integral in progress stops with its
+        // partial result, and no later times are reported
+
+        cancel.store(true, atomic::Ordering::Relaxed);
+        assert!(matches!(
+            temperature_rise(&cancel),
+            Err(CancellableError::Quadrature(
+                QuadratureError::Cancelled { .. }
+            ))
+        ));
+
+        let cancel = AtomicBool::new(false);
+        let mut streamed = Vec::new();
+        let result = layers.temperature_rise_stream_cancellable(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            &ZERO,
+            &times,
+            |i, temperature_rise, _| {
+                streamed.push(temperature_rise.clone());
+                if i == 1 {
+                    cancel.store(true, atomic::Ordering::Relaxed);
+                }
+            },
+            &cancel,
+        );
+        assert!(matches!(
+            result,
+            Err(CancellableError::Quadrature(
+                QuadratureError::Cancelled { .. }
+            ))
+        ));
+        assert_eq!(streamed.len(), 2);
+
+        // invalid bounds are still rejected before integrating
+
+        assert_eq!(
+            layers.temperature_rise_cancellable(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                (&tp, &ZERO),
+                &cancel,
+            ),
+            Err(CancellableError::Bounds(BoundsError::Reversed {
+                a: tp.clone(),
+                b: ZERO.clone(),
+            }))
+        );
+    }
+
+    #[test]
+    fn stream_matches_series() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let layers = MultiLayer::single(layer.clone());
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let times = [0.0, 0.01, 0.01, 0.02, 0.05]
+            .map(|time| Float::with_val_64(64, time))
+            .to_vec();
+
+        let series = temperature_rise_series(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            &ZERO,
+            &times,
+        );
+
+        let mut streamed = Vec::new();
+        temperature_rise_stream(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            &ZERO,
+            &times,
+            |i, temperature_rise, error| {
+                assert_eq!(i, streamed.len());
+                streamed.push((temperature_rise.clone(), error.clone()));
+            },
+        );
+        assert_eq!(streamed, series);
+
+        let mut streamed = Vec::new();
+        layers.temperature_rise_stream(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &ZERO,
+            &ZERO,
+            &epsilon,
+            &ZERO,
+            &times,
+            |_, temperature_rise, error| {
+                streamed.push((temperature_rise.clone(), error.clone()));
+            },
+        );
+        assert_eq!(
+            streamed,
+            layers.temperature_rise_series(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+                &ZERO,
+                &times,
+            )
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn trace_layer_construction() {
+        let tolerance = Float::with_val_64(64, 1e-6);
+
+        // given out of order, with the third layer overlapping the second
+        // within the tolerance
+
+        let (multi_layer, events) = crate::test_tracing::capture(|| {
+            MultiLayer::new_with_tolerance(
+                [
+                    Layer::from_f64(64, 0.01, 0.0015, 10.0, 0.0),
+                    Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+                    Layer::from_f64(64, 0.001, 0.0115 - 1e-7, 100.0, 0.0),
+                ],
+                &tolerance,
+            )
+        });
+        multi_layer.expect("Unable to construct a MultiLayer");
+
+        let count = |message: &str| events.iter().filter(|event| *event == message).count();
+        assert_eq!(count("sorted layers by z0"), 1);
+        assert_eq!(count("snapped layer to the bottom of the layer above"), 1);
+        assert_eq!(count("applied fresnel transmittance"), 0);
+        assert_eq!(count("propagated irradiance"), 2);
+    }
+
+    #[cfg(all(feature = "tracing", debug_assertions))]
+    #[test]
+    fn trace_low_precision() {
+        let thermal_properties = ThermalProperties::from_f64(256, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(256, 0.01, 0.0, 100.0, 1.0);
+        let z = Float::with_val_64(32, 0.001);
+        let r = Float::with_val_64(256, Special::Zero);
+        let tp = Float::with_val_64(256, 0.01);
+
+        let message = "input precision is below the requested precision";
+        let count = |events: Vec<String>| events.iter().filter(|event| *event == message).count();
+
+        let (_, events) = crate::test_tracing::capture(|| {
+            LargeBeam.try_evaluate_with(256, &thermal_properties, &layer, &z, &r, &tp)
+        });
+        assert_eq!(count(events), 1);
+
+        let (_, events) = crate::test_tracing::capture(|| {
+            LargeBeam.try_evaluate_with(32, &thermal_properties, &layer, &z, &r, &tp)
+        });
+        assert_eq!(count(events), 0);
+
+        // a stack of layers warns of `z` once, rather than once per layer
+
+        let multi_layer =
+            MultiLayer::new([layer.clone(), Layer::from_f64(256, 0.01, 0.01, 10.0, 0.0)])
+                .expect("Overlapping layers");
+        let quadrature = GaussKronrod {
+            interval_limit: 4,
+            precision: 256,
+            rule: &G7_K15,
+        };
+        let (_, events) = crate::test_tracing::capture(|| {
+            multi_layer.temperature_rise(
+                256,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &z,
+                &r,
+                &tp,
+                (&r, &tp),
+            )
+        });
+        assert_eq!(count(events), 1);
+    }
+
+    #[cfg(all(feature = "tracing", debug_assertions))]
+    #[test]
+    fn trace_invalid_thermal_properties() {
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let z = Float::with_val_64(64, 0.001);
+        let tp = Float::with_val_64(64, 0.01);
+
+        let quadrature = GaussKronrod {
+            interval_limit: 4,
+            precision: 64,
+            rule: &G7_K15,
+        };
+
+        let message = "thermal properties are invalid";
+        let count = |events: Vec<String>| events.iter().filter(|event| *event == message).count();
+
+        // the integrand is evaluated many times, but the properties are only
+        // checked once. a k of 0 is rejected by `ThermalProperties::validate`,
+        // but is still integrable as the limit of no conduction
+
+        let (_, events) = crate::test_tracing::capture(|| {
+            super::temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &ThermalProperties::from_f64(64, 1.0, 4.178, 0.0),
+                &layer,
+                &z,
+                &ZERO,
+                &tp,
+                (&ZERO, &tp),
+            )
+        });
+        assert_eq!(count(events), 1);
+
+        let (_, events) = crate::test_tracing::capture(|| {
+            super::temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063),
+                &layer,
+                &z,
+                &ZERO,
+                &tp,
+                (&ZERO, &tp),
+            )
+        });
+        assert_eq!(count(events), 0);
+    }
+
+    #[cfg(all(feature = "tracing", debug_assertions))]
+    #[test]
+    fn trace_non_finite_integrand() {
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let z = Float::with_val_64(64, 0.001);
+        let tp = Float::with_val_64(64, 0.01);
+
+        let quadrature = GaussKronrod {
+            interval_limit: 4,
+            precision: 64,
+            rule: &G7_K15,
+        };
+
+        let message = "the integrand is not finite";
+        let count = |events: Vec<String>| events.iter().filter(|event| *event == message).count();
+
+        // a massless medium makes every evaluation infinite, which is warned
+        // of once rather than panicking
+
+        let (result, events) = crate::test_tracing::capture(|| {
+            super::temperature_rise(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &ThermalProperties::from_f64(64, 0.0, 4.178, 0.0063),
+                &layer,
+                &z,
+                &ZERO,
+                &tp,
+                (&ZERO, &tp),
+            )
+        });
+        assert!(!result.expect("Invalid bounds").0.is_finite());
+        assert_eq!(count(events), 1);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn temperature_field_parallel() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let beam = crate::test_beams::GaussianBeam {
+            radius: Float::with_val_64(64, 0.01),
+        };
+        let end = Float::with_val_64(64, 0.01);
+        let epsilon = Float::with_val_64(64, 1e-10);
+
+        let depths = [0.001, 0.005].map(|z| Float::with_val_64(64, z));
+        let radii = [0.0, 0.005, 0.01].map(|r| Float::with_val_64(64, r));
+
+        let field = |parallel: bool| {
+            let field = if parallel {
+                MultiLayer::temperature_field_parallel
+            } else {
+                MultiLayer::temperature_field
+            };
+
+            field(
+                &multi_layer,
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &depths,
+                &radii,
+                &epsilon,
+                (&ZERO, &end),
+            )
+        };
+
+        let serial = field(false).expect("Invalid bounds");
+        let parallel = field(true).expect("Invalid bounds");
+
+        assert_eq!(parallel.len(), depths.len());
+
+        for (serial, parallel) in serial.iter().zip(&parallel) {
+            assert_eq!(parallel.len(), radii.len());
+
+            for (serial, parallel) in serial.iter().zip(parallel) {
+                assert_eq!(serial, parallel);
+                assert!(serial.is_sign_positive());
+            }
+        }
+
+        assert!(multi_layer
+            .temperature_field_parallel(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &depths,
+                &radii,
+                &epsilon,
+                (&end, &ZERO),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn multi_layer_single() {
+        let layer = Layer::from_f64(64, 0.01, 0.001, 100.0, 2.0);
+
+        assert_eq!(
+            MultiLayer::single(layer.clone()),
+            MultiLayer::new([layer]).expect("Unable to construct a MultiLayer")
+        );
+    }
+
+    #[test]
+    fn multi_layer_keeps_interned_values() {
+        let mut interner = crate::interner::Interner::new();
+
+        // the depths are exact in binary, so no layer is snapped
+
+        let d = interner.intern_f64(64, 0.25);
+        let mu_a = interner.intern_f64(64, 1.0);
+        let e0 = interner.intern_f64(64, 1.0);
+        let depths = (0..100)
+            .map(|i| interner.intern_f64(64, f64::from(i) * 0.25))
+            .collect::<Vec<_>>();
+
+        let multi_layer = MultiLayer::new(depths.iter().map(|&z0| interner.layer(d, z0, mu_a, e0)))
+            .expect("Unable to construct a MultiLayer");
+
+        // only the irradiance propagated into each layer below the top one is
+        // computed, and every other value is still borrowed from the interner
+
+        for (layer, &z0) in multi_layer.layers.iter().zip(&depths) {
+            assert!(std::ptr::eq(layer.d.as_ref(), interner.get(d)));
+            assert!(std::ptr::eq(layer.z0.as_ref(), interner.get(z0)));
+            assert!(std::ptr::eq(layer.mu_a.as_ref(), interner.get(mu_a)));
+        }
+
+        assert!(std::ptr::eq(
+            multi_layer.layers[0].e0.as_ref(),
+            interner.get(e0)
+        ));
+        assert!(multi_layer.layers[1..]
+            .iter()
+            .all(|layer| matches!(layer.e0, Cow::Owned(_))));
+    }
+
+    #[test]
+    fn temperature_field_matches_direct() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let max_radius = Float::with_val_64(64, 0.01);
+        let beam = BoundedBeam {
+            inner: LargeBeam,
+            max_radius: Cow::Borrowed(&max_radius),
+        };
+        let end = Float::with_val_64(64, 0.01);
+
+        let depths = [0.0, 0.005].map(|z| Float::with_val_64(64, z));
+        let radii = [0.0, 0.02].map(|r| Float::with_val_64(64, r));
+
+        let field = multi_layer
+            .temperature_field(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &depths,
+                &radii,
+                &EPSILON,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds");
+        assert_eq!(field.len(), 2);
+        assert!(field.iter().all(|row| row.len() == 2));
+
+        let direct = multi_layer
+            .temperature_rise(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &depths[1],
+                &radii[0],
+                &EPSILON,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds")
+            .0;
+        assert_eq!(field[1][0], direct);
+        assert_eq!(field[1][1], 0);
+
+        let field = multi_layer
+            .temperature_field(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &depths,
+                &radii,
+                &EPSILON,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds");
+        assert_eq!(field[0][0], field[0][1]);
+    }
+
+    #[test]
+    fn compensated_summation() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::discretize(
+            64,
+            &Float::with_val_64(64, 0.1),
+            &ZERO,
+            |_| Float::with_val_64(64, 100),
+            &ONE,
+            48,
+        );
+        let z = Float::with_val_64(64, 0.03);
+        let tp = Float::with_val_64(64, 0.01);
+
+        // the reference sums the same contributions, exactly, at a higher
+        // precision, so that only the error of the summation is compared
+
+        let mut reference = Float::with_val_64(256, Special::Zero);
+        for layer in &multi_layer.layers {
+            reference += LargeBeam.evaluate_with(64, &thermal_properties, layer, &z, &ZERO, &tp);
+        }
+
+        let error = |value: Float| {
+            let mut error = Float::with_val_64(256, &value - &reference);
+            error.abs_mut();
+            error
+        };
+
+        let naive =
+            error(multi_layer.evaluate_with(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp));
+        let compensated = error(multi_layer.evaluate_with_compensated(
+            64,
+            &LargeBeam,
+            &thermal_properties,
+            &z,
+            &ZERO,
+            &tp,
+        ));
+
+        assert!(naive > 0);
+        assert!(compensated < naive);
+    }
+
+    #[test]
+    fn integrated_energy_conserved() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-4);
+        let tp = Float::with_val_64(64, 0.001);
+        let radius = Float::with_val_64(64, 0.1);
+
+        // heat has diffused only a few hundredths of a millimeter by tp, so
+        // nearly all of it remains within z_bounds
+
+        let (energy, _) = multi_layer
+            .integrated_energy(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &tp,
+                &epsilon,
+                (
+                    &Float::with_val_64(64, -0.01),
+                    &Float::with_val_64(64, 0.02),
+                ),
+                (&ZERO, &radius),
+            )
+            .expect("Invalid bounds");
+
+        // reference result: pi * radius^2 * e0 * (1 - e^(-mu_a * d)) * tp
+        let mut reference = Float::with_val_64(64, -1);
+        reference.exp_mut();
+        reference = 1 - reference;
+        reference *= Float::with_val_64(64, Constant::Pi);
+        reference *= radius.square();
+        reference *= &tp;
+
+        let mut error = energy;
+        error -= &reference;
+        error /= &reference;
+        error.abs_mut();
+        assert!(error < 1e-5);
+
+        // a negative radial distance is rejected rather than integrated over
+
+        let negative = Float::with_val_64(64, -0.1);
+        assert_eq!(
+            multi_layer.integrated_energy(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &tp,
+                &epsilon,
+                (&ZERO, &ONE),
+                (&negative, &ZERO),
+            ),
+            Err(BoundsError::NegativeLower {
+                a: negative.clone()
+            })
+        );
+    }
+
+    #[test]
+    fn with_precision_round_trip() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::new([
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+            Layer::from_f64(64, 0.01, 0.001, 10.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+
+        let raised_thermal_properties = thermal_properties.with_precision(256);
+        let raised = multi_layer.with_precision(256);
+        assert_eq!(raised_thermal_properties.rho.prec_64(), 256);
+        assert!(raised.layers.iter().all(|layer| layer.e0.prec_64() == 256));
+
+        assert_eq!(
+            raised_thermal_properties.with_precision(64),
+            thermal_properties
+        );
+        assert_eq!(raised.with_precision(64), multi_layer);
+
+        // the inputs are the same, so the results only differ past the
+        // precision of the lower one
+
+        let z = Float::with_val_64(64, 0.0005);
+        let tp = Float::with_val_64(64, 0.01);
+
+        let low = multi_layer.evaluate_with(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp);
+        let high =
+            raised.evaluate_with(256, &LargeBeam, &raised_thermal_properties, &z, &ZERO, &tp);
+        assert_eq!(high.prec_64(), 256);
+        assert_ne!(low, high);
+
+        let mut error = Float::with_val_64(256, &low - &high);
+        error /= &high;
+        error.abs_mut();
+        assert!(error < 1e-17);
+    }
+
+    #[test]
+    fn bessel_beam_on_axis() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let beam = BesselBeam {
+            k_r: Cow::Owned(Float::with_val_64(64, 1000)),
+            quadrature: GaussKronrod {
+                interval_limit: 1024,
+                precision: 64,
+                rule: &G7_K15,
+            },
+            epsilon: Cow::Borrowed(&epsilon),
+        };
+
+        let mut alpha = Float::with_val_64(64, thermal_properties.k.as_ref());
+        alpha /= thermal_properties.rho.as_ref();
+        alpha /= thermal_properties.c.as_ref();
+
+        for tp in [1e-6, 1e-4] {
+            let tp = Float::with_val_64(64, tp);
+
+            let mut result = beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &tp);
+            result /= LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &tp);
+
+            // reference result: exp(-x) * I0(x), where x = 2 * k_r^2 * alpha * tp
+            let mut x = Float::with_val_64(64, beam.k_r.square_ref());
+            x *= &alpha;
+            x *= &tp;
+            x *= 2.0;
+            let mut reference = bessel::i_n(&ZERO, &x, 64);
+            x *= -1;
+            reference *= x.exp();
+
+            result -= &reference;
+            result.abs_mut();
+            assert!(result < 1e-9);
+        }
+
+        // the central intensity is 1, and heat has yet to diffuse at tp = 0
+
+        assert_eq!(
+            beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &ZERO),
+            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &ZERO)
+        );
+
+        // the sign of k_r is irrelevant
+
+        let negated = BesselBeam {
+            k_r: Cow::Owned(Float::with_val_64(64, -1000)),
+            ..beam.clone()
+        };
+        let r = Float::with_val_64(64, 0.001);
+        let tp = Float::with_val_64(64, 1e-4);
+        assert_eq!(
+            negated.evaluate_with(64, &thermal_properties, &layer, &ZERO, &r, &tp),
+            beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &r, &tp)
+        );
+    }
+
+    #[test]
+    fn irradiance_at() {
+        let multi_layer = MultiLayer::new([
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 2.0),
+            Layer::from_f64(64, 0.01, 0.002, 10.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+
+        let irradiance_at = |z: f64| multi_layer.irradiance_at(&Float::with_val_64(64, z), 64);
+        let close = |value: Float, expected: f64| {
+            let mut error = value;
+            error -= expected;
+            error /= expected;
+            error.abs_mut();
+            error < 1e-15
+        };
+
+        // reference results: 2 * e^(-1000 * z) within the first layer, 2 *
+        // e^-1 in the gap, 2 * e^-1 * e^(-10 * (z - 0.002)) within the second
+        // layer, and 2 * e^-1 * e^-0.1 below it
+        assert_eq!(irradiance_at(-1.0), 2);
+        assert_eq!(irradiance_at(0.0), 2);
+        assert!(close(irradiance_at(0.0005), 1.2130613194252668));
+        assert!(close(irradiance_at(0.0015), 7.357588823428847e-1));
+        assert!(close(irradiance_at(0.007), 6.998754982223108e-1));
+        assert!(close(irradiance_at(1.0), 6.657421673961591e-1));
+    }
+
+    #[test]
+    fn incident_irradiance() {
+        // the layers are given out of order, with only the irradiance
+        // incident on the top one meaningful
+
+        let mut multi_layer = MultiLayer::new([
+            Layer::from_f64(64, 0.01, 0.02, 10.0, 7.0),
+            Layer::from_f64(64, 0.01, 0.001, 100.0, 2.5),
+        ])
+        .expect("Unable to construct a MultiLayer");
+
+        assert_eq!(multi_layer.incident_irradiance(64), 2.5);
+        assert_eq!(
+            multi_layer.incident_irradiance(64),
+            multi_layer.irradiance_at(&Float::with_val_64(64, -1.0), 64)
+        );
+
+        multi_layer.scale_irradiance(&Float::with_val_64(64, 2.0));
+        assert_eq!(multi_layer.incident_irradiance(64), 5);
+
+        assert_eq!(
+            MultiLayer::new([])
+                .expect("Unable to construct a MultiLayer")
+                .incident_irradiance(64),
+            0
+        );
+    }
+
+    #[test]
+    fn absorbed_power_density() {
+        let multi_layer = MultiLayer::new([
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 2.0),
+            Layer::from_f64(64, 0.01, 0.002, 10.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-15);
+
+        let depths = [-1.0, 0.0, 0.0005, 0.0015, 0.007, 1.0].map(|z| Float::with_val_64(64, z));
+        let profile = multi_layer.absorbed_power_density_profile(&depths, 64);
+
+        for (z, density) in depths.iter().zip(&profile) {
+            assert_eq!(*density, multi_layer.absorbed_power_density(z, 64));
+        }
+
+        assert_eq!(profile[0], 0);
+        assert_eq!(profile[1], 2000);
+        assert_eq!(profile[3], 0);
+        assert_eq!(profile[5], 0);
+
+        // the integral over each layer, as the density is discontinuous
+        // across their boundaries
+        let mut absorbed = Float::with_val_64(64, Special::Zero);
+        for layer in &multi_layer.layers {
+            let bottom = Float::with_val_64(64, layer.z0.as_ref() + layer.d.as_ref());
+
+            absorbed += quadrature
+                .integrate(
+                    |z| multi_layer.absorbed_power_density(&z, 64),
+                    &epsilon,
+                    (layer.z0.as_ref(), &bottom),
+                )
+                .0;
+        }
+
+        // reference result: the incident irradiance less that leaving the
+        // bottom of the stack, 2 * (1 - e^-1 * e^-0.1)
+        let mut error = absorbed;
+        error -= 1.334257832603841;
+        error /= 1.334257832603841;
+        error.abs_mut();
+        assert!(error < 1e-15);
+    }
+
+    #[test]
+    fn fresnel_transmission() {
+        let layers = |n_1: Option<f64>, n_2: Option<f64>| {
+            let index = |n: Option<f64>| n.map(|n| Cow::Owned(Float::with_val_64(64, n)));
+
+            MultiLayer::new([
+                Layer {
+                    n: index(n_1),
+                    ..Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0)
+                },
+                Layer {
+                    n: index(n_2),
+                    ..Layer::from_f64(64, 0.01, 0.001, 10.0, 0.0)
+                },
+            ])
+            .expect("Unable to construct a MultiLayer")
+        };
+
+        // without an index on both sides, nothing is reflected
+
+        let without = layers(None, None);
+        assert_eq!(layers(Some(1.5), None).layers[1].e0, without.layers[1].e0);
+        assert_eq!(layers(None, Some(1.5)).layers[1].e0, without.layers[1].e0);
+
+        // reference result: 1 - ((1.33 - 1.5) / (1.33 + 1.5))^2
+        let mut expected = Float::with_val_64(64, without.layers[1].e0.as_ref());
+        expected *= 9.963915144401853e-1;
+
+        let mut error = layers(Some(1.33), Some(1.5)).layers[1]
+            .e0
+            .clone()
+            .into_owned();
+        error -= &expected;
+        error /= &expected;
+        error.abs_mut();
+        assert!(error < 1e-15);
+
+        // the first layer is unaffected
+        assert_eq!(
+            layers(Some(1.33), Some(1.5)).layers[0].e0,
+            without.layers[0].e0
+        );
+    }
+
+    #[test]
+    fn time_to_threshold() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 1.0, 1.0);
+        let mu_a = Float::with_val_64(64, 1e-3);
+        let multi_layer = MultiLayer::single(Layer {
+            d: Cow::Owned(Float::with_val_64(64, 2000)),
+            z0: Cow::Owned(Float::with_val_64(64, -1000)),
+            mu_a: Cow::Borrowed(&mu_a),
+            e0: Cow::Borrowed(&ONE),
+            n: None,
+        });
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let baseline = Float::with_val_64(64, 37);
+        let threshold = Float::with_val_64(64, 37.01);
+        let end = Float::with_val_64(64, 100);
+        let bounds = (&*ONE, &end);
+
+        let time = multi_layer
+            .time_to_threshold(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &threshold,
+                &baseline,
+                &EPSILON,
+                bounds,
+            )
+            .expect("Invalid bounds")
+            .expect("Threshold not reached");
+
+        // far from the layer's boundaries, the difference of the error
+        // functions is 2, so the temperature rise over 0..tp is
+        // 2 * C * (exp(mu_a^2 * alpha * tp) - 1) / (mu_a^2 * alpha), where
+        // C = mu_a * e0 / (2 * rho * c) * exp(-mu_a * (z - z0)). here,
+        // alpha = 1 and mu_a * (z - z0) = 1
+        let mut coefficient = Float::with_val_64(64, -1);
+        coefficient.exp_mut();
+        coefficient *= &mu_a;
+
+        let mu_a_squared = Float::with_val_64(64, mu_a.square_ref());
+        let mut reference = Float::with_val_64(64, &threshold - &baseline);
+        reference *= &mu_a_squared;
+        reference /= &coefficient;
+        reference.ln_1p_mut();
+        reference /= &mu_a_squared;
+
+        let mut error = Float::with_val_64(64, &time - &reference);
+        error /= &reference;
+        error.abs_mut();
+        assert!(error < 1e-12);
+
+        assert_eq!(
+            multi_layer.time_to_threshold(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &Float::with_val_64(64, 38),
+                &baseline,
+                &EPSILON,
+                bounds,
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            multi_layer.time_to_threshold(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &baseline,
+                &baseline,
+                &EPSILON,
+                bounds,
+            ),
+            Ok(Some(Float::with_val_64(64, 1)))
+        );
+
+        // invalid bounds are distinguished from a threshold which is never
+        // reached
+
+        let negative = Float::with_val_64(64, -1);
+        let infinity = Float::with_val_64(64, Special::Infinity);
+
+        for (bounds, expected) in [
+            (
+                (&negative, &end),
+                BoundsError::NegativeLower {
+                    a: negative.clone(),
+                },
+            ),
+            ((&*ONE, &infinity), BoundsError::InfiniteUpper),
+            (
+                (&end, &*ONE),
+                BoundsError::Reversed {
+                    a: end.clone(),
+                    b: ONE.clone(),
+                },
+            ),
+        ] {
+            assert_eq!(
+                multi_layer.time_to_threshold(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &ZERO,
+                    &ZERO,
+                    &Float::with_val_64(64, 38),
+                    &baseline,
+                    &EPSILON,
+                    bounds,
+                ),
+                Err(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn decay_half_time() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-10);
+        let pulse_width = Float::with_val_64(64, 1e-4);
+        let z = Float::with_val_64(64, 0.001);
+        let end = Float::with_val_64(64, 10.0);
+
+        let rise = |observation_time: f64| {
+            multi_layer
+                .temperature_rise_exposure(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &z,
+                    &ZERO,
+                    &epsilon,
+                    (&ZERO, &pulse_width),
+                    &Float::with_val_64(64, observation_time),
+                )
+                .expect("Invalid bounds")
+                .0
+        };
+
+        let half_time = multi_layer
+            .decay_half_time(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &z,
+                &ZERO,
+                &epsilon,
+                &pulse_width,
+                (&ZERO, &end),
+            )
+            .expect("Invalid bounds")
+            .expect("The temperature rise did not decay to half of its peak");
+
+        // within the layer, the peak is at the end of the pulse, after which
+        // the point cools monotonically, reaching half of the peak once the
+        // heat has diffused over a few times the penetration depth
+
+        let peak = rise(1e-4);
+        let half_time = half_time.to_f64();
+        assert!(half_time > 1e-3 && half_time < 1.0);
+
+        let mut previous = peak.clone();
+        for fraction in [0.25, 0.5, 0.75] {
+            let current = rise(1e-4 + half_time * fraction);
+            assert!(current < previous && current > Float::with_val_64(64, &peak / 2.0));
+            previous = current;
+        }
+
+        let mut error = rise(1e-4 + half_time);
+        error /= &peak;
+        error -= 0.5;
+        error.abs_mut();
+        assert!(error < 1e-6);
+
+        // the temperature rise doesn't fall that far shortly after the pulse,
+        // and never falls at all during it
+
+        for end in [2e-4, 1e-4] {
+            assert_eq!(
+                multi_layer.decay_half_time(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &z,
+                    &ZERO,
+                    &epsilon,
+                    &pulse_width,
+                    (&ZERO, &Float::with_val_64(64, end)),
+                ),
+                Ok(None)
+            );
+        }
+
+        // invalid bounds are reported rather than treated as a rise which
+        // never decays
+
+        assert_eq!(
+            multi_layer.decay_half_time(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &z,
+                &ZERO,
+                &epsilon,
+                &pulse_width,
+                (&end, &ZERO),
+            ),
+            Err(BoundsError::Reversed {
+                a: end.clone(),
+                b: ZERO.clone(),
+            })
+        );
+    }
+
+    #[test]
+    fn radial_fwhm() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 1.0, 1.0);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 2000.0, -1000.0, 1e-6, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-9);
+        let tp = Float::with_val_64(64, 4);
+
+        // the beam has a radius of 1, well within the diffusion length of 4.
+        // as the layer hardly absorbs, the depth factor is nearly constant,
+        // so with u = w^2 + 8 * alpha * tp', the temperature rise over 0..tp
+        // is proportional to E1(2r^2 / (w^2 + 8 * alpha * tp)) -
+        // E1(2r^2 / w^2), or ln(1 + 8 * alpha * tp / w^2) on the axis. the
+        // reference was found from this with mpmath
+
+        let beam = GaussianBeam {
+            radius: ONE.clone(),
+        };
+        let mut error = multi_layer
+            .radial_fwhm(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &ZERO,
+                &tp,
+                &epsilon,
+            )
+            .expect("Invalid bounds");
+        error -= 2.668092406136158;
+        error.abs_mut();
+        assert!(error < 1e-6);
+
+        assert!(multi_layer
+            .radial_fwhm(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &tp,
+                &epsilon,
+            )
+            .expect("Invalid bounds")
+            .is_infinite());
+        assert!(multi_layer
+            .radial_fwhm(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &ZERO,
+                &ZERO,
+                &epsilon,
+            )
+            .expect("Invalid bounds")
+            .is_nan());
+
+        let tp = Float::with_val_64(64, -1);
+        assert_eq!(
+            multi_layer.radial_fwhm(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &ZERO,
+                &tp,
+                &epsilon,
+            ),
+            Err(BoundsError::Reversed {
+                a: ZERO.clone(),
+                b: tp.clone(),
+            })
+        );
+    }
+
+    #[test]
+    fn penetration_depth_1e() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 1.0, 0.0, 10.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-10);
+        let tp = Float::with_val_64(64, 0.01);
+
+        let rise = |multi_layer: &MultiLayer, z: &Float| {
+            multi_layer
+                .temperature_rise(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    z,
+                    &ZERO,
+                    &epsilon,
+                    (&ZERO, &tp),
+                )
+                .expect("Invalid bounds")
+                .0
+        };
+
+        let depth = multi_layer
+            .penetration_depth_1e(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &tp,
+                &epsilon,
+            )
+            .expect("Invalid bounds")
+            .expect("The temperature rise did not fall to 1/e");
+
+        let mut target = rise(&multi_layer, &ZERO);
+        target /= Float::with_val_64(64, 1).exp();
+
+        let mut error = rise(&multi_layer, &depth);
+        error -= &target;
+        error /= &target;
+        error.abs_mut();
+        assert!(error < 1e-8);
+
+        // the diffusion length of about 0.008 is short next to the optical
+        // penetration depth of 1 / mu_a = 0.1, so the temperature rise mostly
+        // follows the absorbed power density. at the surface, only the
+        // half-space below absorbs, halving the difference of the error
+        // functions, so it falls to 1/e at about (1 + ln(2)) / mu_a
+
+        let mut reference = Float::with_val_64(64, 2);
+        reference.ln_mut();
+        reference += 1;
+        reference /= 10;
+
+        let mut error = Float::with_val_64(64, &depth - &reference);
+        error /= &reference;
+        error.abs_mut();
+        assert!(error < 0.05);
+
+        // a layer much thinner than the optical penetration depth never
+        // cools that much
+
+        assert_eq!(
+            MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 10.0, 1.0)).penetration_depth_1e(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &tp,
+                &epsilon,
+            ),
+            Ok(None)
+        );
+
+        let tp = Float::with_val_64(64, Special::Nan);
+        assert_eq!(
+            multi_layer.penetration_depth_1e(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &ZERO,
+                &tp,
+                &epsilon,
+            ),
+            Err(BoundsError::NanUpper)
+        );
+    }
+
+    #[test]
+    fn thermal_centroid() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0));
+        let quadrature = GaussKronrod {
+            interval_limit: 2,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let beam = GaussianBeam {
+            radius: Float::with_val_64(64, 0.01),
+        };
+        let epsilon = Float::with_val_64(64, 1e-3);
+        let tp = Float::with_val_64(64, 0.01);
+        let depth = Float::with_val_64(64, 0.01);
+        let width = Float::with_val_64(64, 0.03);
+
+        let (z, r) = multi_layer
+            .thermal_centroid(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &tp,
+                &epsilon,
+                (&ZERO, &depth),
+                (&-width.clone(), &width),
+            )
+            .expect("Invalid bounds");
+
+        // the beam is radially symmetric and centered within the bounds, so
+        // heat is deposited evenly on either side of the axis. in depth, it
+        // is absorbed more near the surface
+
+        assert!(Float::with_val_64(64, r.abs_ref()) < 1e-12);
+        assert!(z > 0 && z < 0.005);
+
+        // shifting the bounds off-center moves the radial centroid toward the
+        // side with more of the beam
+
+        let (_, r) = multi_layer
+            .thermal_centroid(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &tp,
+                &epsilon,
+                (&ZERO, &depth),
+                (&Float::with_val_64(64, -0.01), &width),
+            )
+            .expect("Invalid bounds");
+        assert!(r > 0);
+
+        let tp = Float::with_val_64(64, -0.01);
+        assert_eq!(
+            multi_layer.thermal_centroid(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &tp,
+                &epsilon,
+                (&ZERO, &depth),
+                (&-width.clone(), &width),
+            ),
+            Err(BoundsError::Reversed {
+                a: ZERO.clone(),
+                b: tp.clone(),
+            })
+        );
+    }
+
+    #[test]
+    fn peak_on_axis() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 100.0, 1.0);
+        let beam = FlatTopBeam::new(&Float::with_val_64(64, 0.01)).expect("Invalid beam");
+
+        // below the layer, heat takes some time to arrive
+
+        let z = Float::with_val_64(64, 0.002);
+        let (peak_time, peak) = beam.peak_on_axis(64, &thermal_properties, &layer, &z);
+
+        let (scan_time, scan) = (1..=2000)
+            .map(|i| {
+                let tp = Float::with_val_64(64, f64::from(i) * 1e-6);
+                let value = beam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &tp);
+                (tp, value)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .expect("The scan was empty");
+
+        assert!(scan <= peak);
+        assert!(Float::with_val_64(64, &peak - &scan) < Float::with_val_64(64, &peak * 1e-6));
+        assert!(Float::with_val_64(64, &peak_time - &scan_time).abs() <= 1e-6);
+
+        // within the layer, the peak is immediate
+
+        let z = Float::with_val_64(64, 0.0005);
+        assert_eq!(
+            beam.peak_on_axis(64, &thermal_properties, &layer, &z),
+            (
+                ZERO.clone(),
+                beam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &ZERO)
+            )
+        );
+
+        // without conduction, heat never reaches a point below the layer
+
+        let insulating = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0);
+        let z = Float::with_val_64(64, 0.002);
+        let (peak_time, peak) = beam.peak_on_axis(64, &insulating, &layer, &z);
+        assert!(peak_time.is_infinite());
+        assert_eq!(peak, 0);
+
+        let massless = ThermalProperties::from_f64(64, 0.0, 4.178, 0.0063);
+        let (peak_time, peak) = beam.peak_on_axis(64, &massless, &layer, &z);
+        assert!(peak_time.is_nan() && peak.is_nan());
+    }
+
+    #[test]
+    fn no_conduction() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0);
+        let z = Float::with_val_64(64, 0.0005);
+        let tp = Float::with_val_64(64, 0.01);
+
+        // without conduction, the heat deposited stays where it was, at
+        // mu_a * e0 / (rho * c) * exp(-mu_a * (z - z0))
+
+        let mut expected = Float::with_val_64(64, -0.5);
+        expected.exp_mut();
+        expected *= 1000.0;
+        expected /= 4.178;
+
+        let flat_top = FlatTopBeam::new(&Float::with_val_64(64, 0.01)).expect("Invalid beam");
+        let inside = Float::with_val_64(64, 0.005);
+        let outside = Float::with_val_64(64, 0.02);
+
+        let initial = LargeBeam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &ZERO);
+
+        let mut error = Float::with_val_64(64, &initial - &expected);
+        error /= &expected;
+        error.abs_mut();
+        assert!(error < 1e-15);
+
+        assert_eq!(
+            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &tp),
+            initial
+        );
+        assert_eq!(
+            LargeBeam.try_evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &tp),
+            Ok(initial.clone())
+        );
+        assert_eq!(
+            flat_top.evaluate_with(64, &thermal_properties, &layer, &z, &inside, &tp),
+            initial
+        );
+        assert_eq!(
+            flat_top.evaluate_with(64, &thermal_properties, &layer, &z, &outside, &tp),
+            0
+        );
+
+        let plan = LargeBeam.plan(64, &thermal_properties, &layer, std::slice::from_ref(&tp));
+        let prepared = LargeBeam.prepare(64, &thermal_properties, &layer, &z);
+        assert_eq!(plan.evaluate(&prepared), [initial]);
+    }
+
+    #[test]
+    fn temperature_rise_series_log_time() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let epsilon = Float::with_val_64(64, 1e-12);
+        let z = Float::with_val_64(64, 0.0005);
+        let times = [1e-4, 1e-3, 1e-3, 1e-2, 1e-1].map(|time| Float::with_val_64(64, time));
+
+        for start in [0.0, 1e-5] {
+            let start = Float::with_val_64(64, start);
+            let linear = super::temperature_rise_series(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &epsilon,
+                &start,
+                &times,
+            );
+            let log_time = super::temperature_rise_series_log_time(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &epsilon,
+                &start,
+                &times,
+            )
+            .expect("Invalid bounds");
+
+            assert_eq!(log_time.len(), times.len());
+            assert_eq!(log_time[1].0, log_time[2].0);
+
+            for ((linear, _), (log_time, _)) in linear.iter().zip(&log_time) {
+                let mut error = Float::with_val_64(64, log_time - linear);
+                error /= linear;
+                error.abs_mut();
+                assert!(error < 1e-10);
+            }
+        }
+
+        // a first time equal to a start of 0 would otherwise be integrated
+        // over -ln(0)..-ln(0)
+
+        let times = [ZERO.clone(), Float::with_val_64(64, 1e-3)];
+        let series = super::temperature_rise_series_log_time(
+            64,
+            &quadrature,
+            &LargeBeam,
+            &thermal_properties,
+            &layer,
+            &z,
+            &ZERO,
+            &epsilon,
+            &ZERO,
+            &times,
+        )
+        .expect("Invalid bounds");
+        assert!(series[0].0.is_zero());
+        assert!(series[1].0.is_sign_positive() && !series[1].0.is_zero());
+
+        let times = [Float::with_val_64(64, 1e-3), Float::with_val_64(64, 1e-4)];
+        assert_eq!(
+            super::temperature_rise_series_log_time(
+                64,
+                &quadrature,
+                &LargeBeam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &ZERO,
+                &epsilon,
+                &ZERO,
+                &times,
+            ),
+            Err(BoundsError::Reversed {
+                a: times[0].clone(),
+                b: times[1].clone(),
+            })
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let layers = || {
+            [
+                Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+                Layer::from_f64(64, 0.002, 0.001, 100.0, 0.0),
+                Layer::from_f64(64, 0.01, 0.003, 10.0, 0.0),
+            ]
+        };
+        let stack = MultiLayer::new(layers()).expect("Unable to construct a MultiLayer");
+        let tolerance = Float::with_val_64(64, 1e-12);
+
+        assert!(stack.diff(&stack.clone(), &tolerance).is_empty());
+
+        // the bottom layer passes its irradiance on to nothing, so only its
+        // mu_a changes
+
+        let mut changed = layers();
+        changed[2].mu_a = Cow::Owned(Float::with_val_64(64, 20.0));
+        let changed = MultiLayer::new(changed).expect("Unable to construct a MultiLayer");
+
+        assert_eq!(
+            stack.diff(&changed, &tolerance),
+            [LayerDiff::Changed {
+                index: 2,
+                field: "mu_a",
+                from: Float::with_val_64(64, 10.0),
+                to: Float::with_val_64(64, 20.0),
+            }]
+        );
+
+        // differences within the tolerance are disregarded
+
+        let mut nudged = layers();
+        *nudged[2].d.to_mut() += 1e-15;
+        let nudged = MultiLayer::new(nudged).expect("Unable to construct a MultiLayer");
+        assert!(stack.diff(&nudged, &tolerance).is_empty());
+
+        let shorter = MultiLayer::new(layers().into_iter().take(2))
+            .expect("Unable to construct a MultiLayer");
+
+        assert_eq!(
+            stack.diff(&shorter, &tolerance),
+            [LayerDiff::Removed {
+                index: 2,
+                layer: stack.layers[2].clone(),
+            }]
+        );
+        assert!(matches!(
+            shorter.diff(&stack, &tolerance).as_slice(),
+            [LayerDiff::Added { index: 2, .. }]
+        ));
+    }
+
+    #[test]
+    fn absorbed_fraction() {
+        let relative_error = |value: Float, expected: &Float| {
+            let mut error = Float::with_val_64(64, &value - expected);
+            error /= expected;
+            error.abs_mut();
+            error
+        };
+
+        // 1 - exp(-mu_a * d) with mu_a * d = 1
+
+        let mut expected = Float::with_val_64(64, -1);
+        expected.exp_m1_mut();
+        expected *= -1;
+
+        let single = MultiLayer::single(Layer::from_f64(64, 0.01, 0.0, 100.0, 2.0));
+        assert!(relative_error(single.absorbed_fraction(64), &expected) < 1e-15);
+
+        // the irradiance propagated into the second layer is accounted for,
+        // giving 1 - exp(-(1 + 0.1))
+
+        let mut expected = Float::with_val_64(64, -1.1);
+        expected.exp_m1_mut();
+        expected *= -1;
+
+        let stack = MultiLayer::new([
+            Layer::from_f64(64, 0.01, 0.0, 100.0, 2.0),
+            Layer::from_f64(64, 0.01, 0.01, 10.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+        assert!(relative_error(stack.absorbed_fraction(64), &expected) < 1e-15);
+
+        let thick = MultiLayer::new([
+            Layer::from_f64(64, 0.01, 0.0, 1000.0, 1.0),
+            Layer::from_f64(64, 0.1, 0.01, 1000.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+        let fraction = thick.absorbed_fraction(64);
+        assert!(fraction <= 1);
+        assert!(fraction > 1.0 - 1e-15);
+    }
+
+    #[test]
+    fn split_at() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.002, 0.001, 500.0, 1.0);
+
+        for z_split in [0.001, 0.003, 0.0, 0.004] {
+            assert!(layer
+                .split_at(&Float::with_val_64(64, z_split), 64)
+                .is_none());
+        }
+
+        let (upper, lower) = layer
+            .split_at(&Float::with_val_64(64, 0.0015), 64)
+            .expect("The depth was not within the layer");
+        assert_eq!(*upper.d, 0.0005);
+        assert_eq!(*lower.d, 0.0015);
+        assert_eq!(*lower.z0, 0.0015);
+
+        // as the temperature rise is linear in the source, the halves sum to
+        // the whole
+
+        for z in [0.0, 0.0012, 0.0015, 0.0025, 0.004] {
+            let z = Float::with_val_64(64, z);
+
+            for tp in [0.0, 1e-4, 1e-2] {
+                let tp = Float::with_val_64(64, tp);
+                let whole =
+                    LargeBeam.evaluate_with(64, &thermal_properties, &layer, &z, &ZERO, &tp);
+                let mut halves =
+                    LargeBeam.evaluate_with(64, &thermal_properties, &upper, &z, &ZERO, &tp);
+                halves += LargeBeam.evaluate_with(64, &thermal_properties, &lower, &z, &ZERO, &tp);
+
+                let mut error = Float::with_val_64(64, &halves - &whole);
+                error.abs_mut();
+                assert!(error <= Float::with_val_64(64, whole.abs_ref()) * 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn evaluate_many_matches_evaluate_with() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let flat_top = FlatTopBeam::new(&Float::with_val_64(64, 0.1)).expect("Invalid beam");
+        let float = |value| Float::with_val_64(64, value);
+
+        // the flat-top beam is only evaluated on the axis and at tp = 0, as
+        // elsewhere it depends upon the (slow) marcum-q function
+
+        let points = [
+            (float(0.0), float(0.0), float(0.0)),
+            (float(0.0), float(0.0), float(1e-3)),
+            (float(0.0), float(0.2), float(0.0)),
+            (float(0.005), float(0.0), float(1e-3)),
+            (float(0.005), float(0.0), float(1e-2)),
+            (float(0.02), float(0.0), float(1e-2)),
+            (float(0.0), float(0.0), float(1e-2)),
+        ];
+
+        let evaluate_each = |beam: &dyn Beam| {
+            points
+                .iter()
+                .map(|(z, r, tp)| beam.evaluate_with(64, &thermal_properties, &layer, z, r, tp))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            LargeBeam.evaluate_many(64, &thermal_properties, &layer, &points),
+            evaluate_each(&LargeBeam)
+        );
+        assert_eq!(
+            flat_top.evaluate_many(64, &thermal_properties, &layer, &points),
+            evaluate_each(&flat_top)
+        );
+    }
 
-#[derive(Clone, PartialEq, Debug)]
-pub struct LargeBeam;
+    #[test]
+    fn evaluate_over_properties_matches_evaluate_with() {
+        let properties = [
+            ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063),
+            ThermalProperties::from_f64(64, 1.1, 4.178, 0.0063),
+            ThermalProperties::from_f64(64, 1.0, 3.6, 0.0063),
+            ThermalProperties::from_f64(64, 1.0, 4.178, 0.0052),
+        ];
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let flat_top = FlatTopBeam::new(&Float::with_val_64(64, 0.1)).expect("Invalid beam");
 
-impl Beam for LargeBeam {
-    //TODO: it (might?) be worthwhile to have a specialized method that
-    //      doesn't need to take r. however, this could also be addressed with
-    //      the genericization of this method at the trait level. see above
-    //      for more details
-    fn evaluate_with<'a>(
-        &self,
-        precision: u64,
-        thermal_properties: &ThermalProperties<'a>,
-        layer: &Layer<'a>,
-        z: &Float,
-        _r: &Float,
-        tp: &Float,
-    ) -> Float {
-        //TODO: make this less naive
+        for (z, tp) in [(0.0, 0.0), (0.005, 1e-3), (0.02, 1e-2)] {
+            let (z, tp) = (Float::with_val_64(64, z), Float::with_val_64(64, tp));
 
-        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
-        alpha /= thermal_properties.rho.as_ref();
-        alpha /= thermal_properties.c.as_ref();
+            let evaluate_each = |beam: &dyn Beam| {
+                properties
+                    .iter()
+                    .map(|thermal_properties| {
+                        beam.evaluate_with(64, thermal_properties, &layer, &z, &ZERO, &tp)
+                    })
+                    .collect::<Vec<_>>()
+            };
 
-        let mut term_1 = Float::with_val_64(precision, layer.mu_a.as_ref());
-        term_1 *= layer.e0.as_ref();
-        term_1 /= thermal_properties.rho.as_ref();
-        term_1 /= thermal_properties.c.as_ref();
-        term_1 /= 2.0;
+            assert_eq!(
+                LargeBeam.evaluate_over_properties(64, &properties, &layer, &z, &ZERO, &tp),
+                evaluate_each(&LargeBeam)
+            );
+            assert_eq!(
+                flat_top.evaluate_over_properties(64, &properties, &layer, &z, &ZERO, &tp),
+                evaluate_each(&flat_top)
+            );
+        }
+    }
 
-        let mut term_2 = Float::with_val_64(precision, z);
-        term_2 -= layer.z0.as_ref();
-        term_2 *= layer.mu_a.as_ref();
-        term_2 *= -1;
-        term_2.exp_mut();
+    #[test]
+    fn negative_radial_distance() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let flat_top = FlatTopBeam::new(&Float::with_val_64(64, 0.1)).expect("Invalid beam");
+        let epsilon = Float::with_val_64(64, 1e-10);
+        let bessel = BesselBeam {
+            k_r: Cow::Owned(Float::with_val_64(64, 1000)),
+            quadrature: GaussKronrod {
+                interval_limit: 1024,
+                precision: 64,
+                rule: &G7_K15,
+            },
+            epsilon: Cow::Borrowed(&epsilon),
+        };
+        let z = Float::with_val_64(64, 0.005);
 
-        if *tp == 0 {
-            return term_1 * term_2;
+        // at a time of 0, the flat-top beam is zero beyond its radius on
+        // either side of the axis, and within it once heat has diffused, the
+        // marcum-q function is only defined for nonnegative arguments
+
+        for (r, tp) in [(0.05, 0.0), (0.2, 0.0), (0.05, 1e-3)] {
+            let tp = Float::with_val_64(64, tp);
+            let r = Float::with_val_64(64, r);
+            let evaluate = |beam: &dyn Beam, r: &Float| {
+                beam.evaluate_with(64, &thermal_properties, &layer, &z, r, &tp)
+            };
+
+            let flat_top_result = evaluate(&flat_top, &r);
+            assert_eq!(evaluate(&flat_top, &-r.clone()), flat_top_result);
+            assert_eq!(
+                flat_top.evaluate_many(
+                    64,
+                    &thermal_properties,
+                    &layer,
+                    &[(z.clone(), -r.clone(), tp.clone())]
+                ),
+                [flat_top_result]
+            );
+
+            let r = Float::with_val_64(64, &r / 50);
+            assert_eq!(evaluate(&bessel, &-r.clone()), evaluate(&bessel, &r));
         }
+    }
 
-        let mut term_3 = Float::with_val_64(precision, layer.mu_a.as_ref());
-        term_3.square_mut();
-        term_3 *= tp;
-        term_3 *= &alpha;
-        term_3.exp_mut();
+    #[test]
+    fn above_layer() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0);
+        let h = 1e-7;
 
-        let mut reciprocal_sqrt = Float::with_val_64(precision, &alpha);
-        reciprocal_sqrt *= tp;
-        reciprocal_sqrt *= 4.0;
-        reciprocal_sqrt.sqrt_mut();
-        reciprocal_sqrt.recip_mut();
+        let evaluate = |z: f64, tp: f64| {
+            LargeBeam.evaluate_with(
+                64,
+                &thermal_properties,
+                &layer,
+                &Float::with_val_64(64, z),
+                &ZERO,
+                &Float::with_val_64(64, tp),
+            )
+        };
 
-        let mut sqrt_mu_a = alpha;
-        sqrt_mu_a *= tp;
-        sqrt_mu_a.sqrt_mut();
-        sqrt_mu_a *= layer.mu_a.as_ref();
+        // mu_a^2 * alpha * tp is large at later times, where the error
+        // functions alone cancel entirely
 
-        let mut argument_1 = Float::with_val_64(precision, layer.z0.as_ref());
-        argument_1 += layer.d.as_ref();
-        argument_1 -= z;
-        argument_1 *= &reciprocal_sqrt;
-        argument_1 += &sqrt_mu_a;
-        argument_1.erf_mut();
+        for tp in [1e-3, 1e-1, 1.0] {
+            let top = evaluate(0.0, tp);
+            let above = evaluate(-h, tp);
+            let below = evaluate(h, tp);
 
-        let mut argument_2 = Float::with_val_64(precision, layer.z0.as_ref());
-        argument_2 -= z;
-        argument_2 *= &reciprocal_sqrt;
-        argument_2 += &sqrt_mu_a;
-        argument_2.erf_mut();
+            assert!(above > 0);
+            assert!(above < top && top < below);
 
-        let mut term_4 = argument_1;
-        term_4 -= argument_2;
+            // the derivative with respect to z is continuous across the top of
+            // the layer, so the second difference is much smaller than the
+            // first
 
-        term_1 * term_2 * term_3 * term_4
-    }
-}
+            let mut first = Float::with_val_64(64, &below - &above);
+            first.abs_mut();
 
-//TODO: same todo as above
-#[derive(Clone, PartialEq, Debug)]
-pub struct FlatTopBeam<'a> {
-    /// Units: cm
-    pub radius: Cow<'a, Float>,
-}
+            let mut second = below;
+            second += above;
+            second -= Float::with_val_64(64, &top * 2.0);
+            second.abs_mut();
+            assert!(second * 100 < first);
 
-impl<'a> Beam for FlatTopBeam<'a> {
-    fn evaluate_with<'b>(
-        &self,
-        precision: u64,
-        thermal_properties: &ThermalProperties<'b>,
-        layer: &Layer<'b>,
-        z: &Float,
-        r: &Float,
-        tp: &Float,
-    ) -> Float {
-        let radius = self.radius.as_ref();
+            // the temperature rise decays with distance from the layer
 
-        if *tp == 0 && r > radius {
-            return Float::with_val_64(precision, Special::Zero);
+            assert!(evaluate(-0.01, tp) < evaluate(-0.001, tp));
+            assert!(evaluate(-0.01, tp) > 0);
         }
 
-        let z_factor = LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp);
+        // nothing has diffused out of the layer yet at a time of 0
 
-        if *tp == 0 {
-            return z_factor;
-        }
+        assert_eq!(evaluate(-h, 0.0), 0);
+        assert_eq!(evaluate(0.002, 0.0), 0);
 
-        //TODO: don't duplicate this between the code in LargeBeam and this
-        //      function
-        let mut alpha = Float::with_val_64(precision, thermal_properties.k.as_ref());
-        alpha /= thermal_properties.rho.as_ref();
-        alpha /= thermal_properties.c.as_ref();
+        // and inside of it, the result is the limit as tp approaches 0
 
-        z_factor
-            * if *r == 0 {
-                let mut r_factor = Float::with_val_64(precision, radius);
-                r_factor.square_mut();
-                r_factor /= -4.0;
-                r_factor /= alpha;
-                r_factor /= tp;
-                r_factor.exp_mut();
-                r_factor = 1 - r_factor;
-                r_factor
-            } else {
-                //TODO: this is not accurate at all. fix the marcum-q function
-                //      implementation
+        let mut error = evaluate(0.0005, 0.0);
+        error /= evaluate(0.0005, 1e-30);
+        error -= 1;
+        error.abs_mut();
+        assert!(error < 1e-12);
+    }
 
-                let mut a = Float::with_val_64(precision, 2.0);
-                a *= alpha;
-                a *= tp;
-                a.recip_mut();
+    #[test]
+    fn transparent_layer() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let multi_layer = MultiLayer::new([
+            Layer::from_f64(64, 0.01, 0.0, 0.0, 1.0),
+            Layer::from_f64(64, 0.01, 0.01, 100.0, 0.0),
+        ])
+        .expect("The layers should not overlap");
+        let z = Float::with_val_64(64, 0.009);
+        let tp = Float::with_val_64(64, 0.1);
 
-                let mut b = a.clone();
-                b *= radius;
-                a *= r;
+        // the transparent layer deposits nothing itself, but the point just
+        // inside of it warms by conduction from the absorber beneath
 
-                let mut r_factor = utilities::marcum_q(1, &a, &b, precision);
-                r_factor = 1 - r_factor;
-                r_factor
-            }
-    }
-}
+        let breakdown =
+            multi_layer.evaluate_breakdown(64, &LargeBeam, &thermal_properties, &z, &ZERO, &tp);
+        assert_eq!(breakdown[0], 0);
+        assert!(breakdown[1] > 0);
 
-/// Calculates the temperature rise over the interval a..b
-///
-/// This is really just a convenience wrapper around `Quadrature::integrate`
-#[inline]
-pub fn temperature_rise(
-    precision: u64,
-    quadrature: &impl Quadrature<Float>,
-    beam: &impl Beam,
-    thermal_properties: &ThermalProperties<'_>,
-    layer: &Layer<'_>,
-    z: &Float,
-    r: &Float,
-    epsilon: &Float,
-    bounds: (&Float, &Float),
-) -> (Float, Float) {
-    quadrature.integrate(
-        |t| beam.evaluate_with(precision, thermal_properties, layer, z, r, &t),
-        epsilon,
-        bounds,
-    )
-}
+        // the same holds on the axis of a finite beam
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let beam = GaussianBeam {
+            radius: Float::with_val_64(64, 0.1),
+        };
+        assert!(multi_layer.evaluate_with(64, &beam, &thermal_properties, &z, &ZERO, &tp) > 0);
 
-    #[ctor::ctor]
-    static ZERO: Float = Float::with_val_64(64, Special::Zero);
+        let (rise, _) = multi_layer
+            .temperature_rise(
+                64,
+                &GaussKronrod {
+                    interval_limit: 64,
+                    precision: 64,
+                    rule: &G7_K15,
+                },
+                &LargeBeam,
+                &thermal_properties,
+                &z,
+                &ZERO,
+                &EPSILON,
+                (&ZERO, &tp),
+            )
+            .expect("The bounds should be valid");
+        assert!(rise > 0);
 
-    #[ctor::ctor]
-    static ONE: Float = Float::with_val_64(64, 1.0);
+        // before anything has had time to diffuse, it is not warmed at all
 
-    #[ctor::ctor]
-    static EPSILON: Float = Float::with_val_64(64, 1e-16);
+        assert_eq!(
+            multi_layer.evaluate_with(64, &LargeBeam, &thermal_properties, &z, &ZERO, &ZERO),
+            0
+        );
+    }
 
     #[test]
-    fn large_beam_sanity() {
-        let thermal_properties = ThermalProperties {
-            rho: Cow::Borrowed(&ONE),
-            c: Cow::Borrowed(&ONE),
-            k: Cow::Borrowed(&ONE),
+    fn uniform_beam() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
         };
-        let layer = Layer {
-            d: Cow::Borrowed(&ONE),
-            z0: Cow::Borrowed(&ZERO),
-            mu_a: Cow::Borrowed(&ONE),
-            e0: Cow::Borrowed(&ONE),
+        let beam = UniformBeam {
+            power_density: Cow::Owned(Float::with_val_64(64, 2.5)),
         };
+        let tp = Float::with_val_64(64, 0.3);
 
-        assert_eq!(
-            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &ZERO),
-            5e-1
-        );
+        // the temperature rise is power_density * tp / (rho * c) everywhere,
+        // regardless of the layer
 
-        let mut result =
-            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ZERO);
-        // reference result: 0.5 * e^-1
-        result -= 1.8393972058572116080e-1;
-        result.abs_mut();
-        assert!(result < *EPSILON);
+        let mut reference = Float::with_val_64(64, 2.5);
+        reference *= &tp;
+        reference /= 4.178;
 
-        let mut result =
-            LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
-        // reference result: 0.5 * e^-1 * e^1 * (erf(1) - erf(-1/sqrt(4) + 1))
-        result -= 1.6110045756833416583e-1;
-        result.abs_mut();
-        println!("{}", result);
-        assert!(result < *EPSILON);
+        for (layer, z, r) in [
+            (Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0), 0.0, 0.0),
+            (Layer::from_f64(64, 0.01, 0.1, 10.0, 0.0), 5.0, 2.0),
+        ] {
+            let (rise, _) = temperature_rise(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &layer,
+                &Float::with_val_64(64, z),
+                &Float::with_val_64(64, r),
+                &EPSILON,
+                (&ZERO, &tp),
+            )
+            .expect("Invalid bounds");
+
+            let mut error = Float::with_val_64(64, &rise - &reference);
+            error /= &reference;
+            error.abs_mut();
+            assert!(error < 1e-15);
+        }
     }
 
     #[test]
-    fn flat_top_beam_sanity() {
+    fn bounded_beam() {
         let thermal_properties = ThermalProperties {
             rho: Cow::Borrowed(&ONE),
             c: Cow::Borrowed(&ONE),
@@ -394,82 +9507,210 @@ mod tests {
             z0: Cow::Borrowed(&ZERO),
             mu_a: Cow::Borrowed(&ONE),
             e0: Cow::Borrowed(&ONE),
+            n: None,
         };
-        let beam = FlatTopBeam {
-            radius: Cow::Borrowed(&ONE),
+        let inner = EllipticalFlatTopBeam {
+            radius_x: Cow::Borrowed(&ONE),
+            radius_y: Cow::Borrowed(&ONE),
+            quadrature: GaussKronrod {
+                interval_limit: 1024,
+                precision: 64,
+                rule: &G7_K15,
+            },
+            epsilon: Cow::Owned(Float::with_val_64(64, 1e-9)),
+        };
+        let beam = BoundedBeam {
+            inner: inner.clone(),
+            max_radius: Cow::Owned(Float::with_val_64(64, 2.0)),
         };
+        let tp = Float::with_val_64(64, 1e-2);
 
-        assert_eq!(
-            beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &ZERO),
-            5e-1
-        );
+        for r in [0.0, 1.0, 2.0] {
+            let r = Float::with_val_64(64, r);
+            assert_eq!(
+                beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp),
+                inner.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp)
+            );
+        }
 
-        let mut result = beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ZERO);
-        // reference result: 0.5 * e^-1 * (1 - 0)
-        result -= 1.8393972058572116080e-1;
-        result.abs_mut();
-        assert!(result < *EPSILON);
+        // beyond max_radius, the inner beam is nonzero but ignored
 
-        let mut result = beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
-        // reference result: 0.5 * e^-1 * e^1 * (erf(1) - erf(-1/sqrt(4) + 1))
-        //                       * (1 - e^(-1/4))
-        result -= 3.5635295060953884529e-2;
-        result.abs_mut();
-        println!("{}", result);
-        assert!(result < *EPSILON);
+        let r = Float::with_val_64(64, 2.5);
+        assert!(inner.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp) > 0);
+        assert_eq!(
+            beam.evaluate_with(64, &thermal_properties, &layer, &ONE, &r, &tp),
+            0
+        );
     }
 
     #[test]
-    fn multi_layer_sanity() {
-        let thermal_properties = ThermalProperties {
-            rho: Cow::Borrowed(&ONE),
-            c: Cow::Borrowed(&ONE),
-            k: Cow::Borrowed(&ONE),
+    fn cutoff_beam() {
+        use std::cell::Cell;
+
+        /// A beam counting the evaluations of `inner`
+        struct Counted<'a, B> {
+            inner: B,
+            evaluations: &'a Cell<usize>,
+        }
+
+        impl<'a, B: Beam> Beam for Counted<'a, B> {
+            fn evaluate_with<'b>(
+                &self,
+                precision: u64,
+                thermal_properties: &ThermalProperties<'b>,
+                layer: &Layer<'b>,
+                z: &Float,
+                r: &Float,
+                tp: &Float,
+            ) -> Float {
+                self.evaluations.set(self.evaluations.get() + 1);
+                self.inner
+                    .evaluate_with(precision, thermal_properties, layer, z, r, tp)
+            }
+        }
+
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.01, 0.0, 100.0, 1.0);
+        let inner = GaussianBeam {
+            radius: Float::with_val_64(64, 0.01),
         };
-        let layer = Layer {
-            d: Cow::Borrowed(&ONE),
-            z0: Cow::Borrowed(&ZERO),
-            mu_a: Cow::Borrowed(&ONE),
-            e0: Cow::Borrowed(&ONE),
+        let z = Float::with_val_64(64, 0.005);
+        let tp = Float::with_val_64(64, 0.01);
+
+        // a radial field out to 20 times the radius of the beam, over which
+        // heat diffuses about 0.008 cm
+
+        let field = |multiple: f64| {
+            let evaluations = Cell::new(0);
+            let beam = CutoffBeam {
+                inner: Counted {
+                    inner: GaussianBeam {
+                        radius: inner.radius.clone(),
+                    },
+                    evaluations: &evaluations,
+                },
+                radius: Cow::Owned(Float::with_val_64(64, 0.03)),
+                multiple: Cow::Owned(Float::with_val_64(64, multiple)),
+            };
+
+            let field = (0..100)
+                .map(|i| {
+                    let r = Float::with_val_64(64, f64::from(i) * 0.002);
+                    beam.evaluate_with(64, &thermal_properties, &layer, &z, &r, &tp)
+                })
+                .collect::<Vec<_>>();
+
+            (field, evaluations.get())
         };
-        let layers = MultiLayer::new([layer.clone()]).expect("Unable to construct a MultiLayer");
 
-        let mut result =
-            layers.evaluate_with(64, &LargeBeam, &thermal_properties, &ONE, &ZERO, &ONE);
-        result -= LargeBeam.evaluate_with(64, &thermal_properties, &layer, &ONE, &ZERO, &ONE);
-        assert!(result < *EPSILON);
+        let full = (0..100)
+            .map(|i| {
+                let r = Float::with_val_64(64, f64::from(i) * 0.002);
+                inner.evaluate_with(64, &thermal_properties, &layer, &z, &r, &tp)
+            })
+            .collect::<Vec<_>>();
 
-        let layers = MultiLayer::new([
-            Layer {
-                d: Cow::Borrowed(&ONE),
-                z0: Cow::Borrowed(&ZERO),
-                mu_a: Cow::Borrowed(&ONE),
-                e0: Cow::Borrowed(&ONE),
-            },
-            Layer {
-                d: Cow::Borrowed(&ONE),
-                z0: Cow::Borrowed(&ONE),
-                mu_a: Cow::Borrowed(&ONE),
-                e0: Cow::Borrowed(&ZERO),
-            },
+        // a generous multiple cuts off only what is already negligible, and
+        // a tight one skips most of the field. either errs by no more than
+        // exp(-multiple^2) of the value on the axis
+
+        let (generous, generous_evaluations) = field(6.0);
+        let (tight, tight_evaluations) = field(2.0);
+        assert!(generous_evaluations < 50);
+        assert!(tight_evaluations < 30);
+
+        for (result, multiple) in [(generous, 6.0), (tight, 2.0)] {
+            let mut bound = Float::with_val_64(64, -multiple * multiple);
+            bound.exp_mut();
+            bound *= &full[0];
+
+            for (result, full) in result.iter().zip(&full) {
+                assert!(Float::with_val_64(64, full - result) <= bound);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_irradiance() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let mut layers = MultiLayer::new([
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0),
+            Layer::from_f64(64, 0.01, 0.001, 10.0, 0.0),
         ])
         .expect("Unable to construct a MultiLayer");
-
-        let layer = Layer {
-            d: Cow::Owned(Float::with_val_64(64, 2)),
-            z0: Cow::Borrowed(&ZERO),
-            mu_a: Cow::Borrowed(&ONE),
-            e0: Cow::Borrowed(&ONE),
+        let original = layers.clone();
+        let quadrature = GaussKronrod {
+            interval_limit: 1024,
+            precision: 64,
+            rule: &G7_K15,
         };
+        let epsilon = Float::with_val_64(64, 1e-9);
+        let z = Float::with_val_64(64, 0.0005);
+        let end = Float::with_val_64(64, 0.01);
 
-        let beam = FlatTopBeam {
-            radius: Cow::Borrowed(&ONE),
+        let rise = |layers: &MultiLayer| {
+            layers
+                .temperature_rise(
+                    64,
+                    &quadrature,
+                    &LargeBeam,
+                    &thermal_properties,
+                    &z,
+                    &ZERO,
+                    &epsilon,
+                    (&ZERO, &end),
+                )
+                .expect("Invalid bounds")
+                .0
         };
 
-        let small = Float::with_val_64(64, 1e-6);
+        layers.scale_irradiance(&Float::with_val_64(64, 2.0));
 
-        let mut result = layers.evaluate_with(64, &beam, &thermal_properties, &ZERO, &ZERO, &small);
-        result -= beam.evaluate_with(64, &thermal_properties, &layer, &ZERO, &ZERO, &small);
-        assert!(result < *EPSILON);
+        for (scaled, layer) in layers.layers.iter().zip(&original.layers) {
+            assert_eq!(*scaled.e0, Float::with_val_64(64, &*layer.e0 * 2.0));
+        }
+
+        let mut error = rise(&original);
+        error *= 2.0;
+        error -= rise(&layers);
+        error.abs_mut();
+        assert!(error < 1e-12);
+    }
+
+    #[test]
+    fn attenuator() {
+        let filter = Attenuator::from_f64(64, 0.5);
+        let lens = Attenuator::from_f64(64, 0.9);
+        let combined = filter.then(&lens, 64);
+        assert_eq!(*combined.transmission, Float::with_val_64(64, 0.5) * 0.9);
+        assert_eq!(combined, Attenuator::stack(64, [&filter, &lens]));
+        assert_eq!(*Attenuator::stack(64, []).transmission, 1);
+
+        let irradiance = Float::with_val_64(64, 2.0);
+        assert_eq!(
+            combined.attenuate(&irradiance, 64),
+            lens.attenuate(&filter.attenuate(&irradiance, 64), 64)
+        );
+
+        // applying both in turn matches scaling the stack by hand
+
+        let mut layers = MultiLayer::new([
+            Layer::from_f64(64, 0.001, 0.0, 1000.0, 2.0),
+            Layer::from_f64(64, 0.01, 0.001, 10.0, 0.0),
+        ])
+        .expect("Unable to construct a MultiLayer");
+        let mut scaled = layers.clone();
+
+        filter.apply(&mut layers);
+        lens.apply(&mut layers);
+        scaled.scale_irradiance(&combined.transmission);
+
+        for (layer, scaled) in layers.layers.iter().zip(&scaled.layers) {
+            let mut error = Float::with_val_64(64, &*layer.e0 - &*scaled.e0);
+            error.abs_mut();
+            assert!(error < 1e-15);
+        }
+
+        assert_eq!(layers.incident_irradiance(64), 0.9);
     }
 }
@@ -0,0 +1,181 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use rug::{float::OrdFloat, Float};
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::greens::{Layer, ThermalProperties};
+
+#[cfg(doc)]
+use crate::greens::MultiLayer;
+
+/// A handle to a [`struct@Float`] stored in an [`struct@Interner`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Interned(usize);
+
+/// A store of [`struct@Float`]s which are shared between the
+/// [`struct@Layer`]s and [`struct@ThermalProperties`] borrowing from it
+///
+/// Each distinct value (of a given precision) is only stored once, and
+/// interning it again returns the same [`struct@Interned`] handle. Once every
+/// value is interned, [`Interner::layer`] and
+/// [`Interner::thermal_properties`] construct structures holding
+/// `Cow::Borrowed` references into the interner, so building thousands of
+/// near-identical layers doesn't clone the values they share
+///
+/// A [`struct@MultiLayer`] built from such layers keeps borrowing from the
+/// interner, apart from the values it computes itself (see
+/// [`MultiLayer::new`])
+///
+/// Handles are only meaningful to the interner which returned them, and
+/// using one with another interner may panic or return an unrelated value
+#[derive(Clone, Default, Debug)]
+pub struct Interner {
+    values: Vec<Float>,
+    indices: BTreeMap<(u64, OrdFloat), usize>,
+}
+
+impl Interner {
+    /// Creates a new, empty [`struct@Interner`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `value` if an equal value of the same precision isn't already
+    /// stored, returning a handle to the stored value
+    ///
+    /// As with [`struct@OrdFloat`], zeroes of differing signs are distinct,
+    /// and all NaNs are considered equal
+    pub fn intern(&mut self, value: Float) -> Interned {
+        let key = (value.prec_64(), OrdFloat::from(value));
+
+        if let Some(&index) = self.indices.get(&key) {
+            return Interned(index);
+        }
+
+        let index = self.values.len();
+        self.values.push(key.1.as_float().clone());
+        self.indices.insert(key, index);
+
+        Interned(index)
+    }
+
+    /// Stores `value` as in [`Interner::intern`], converting it to a
+    /// [`struct@Float`] at the given precision
+    pub fn intern_f64(&mut self, precision: u64, value: f64) -> Interned {
+        self.intern(Float::with_val_64(precision, value))
+    }
+
+    /// Returns the value referred to by `handle`
+    pub fn get(&self, handle: Interned) -> &Float {
+        &self.values[handle.0]
+    }
+
+    /// The number of distinct values stored
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no values are stored
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Constructs a [`struct@Layer`] borrowing its fields from the interner
+    pub fn layer(&self, d: Interned, z0: Interned, mu_a: Interned, e0: Interned) -> Layer<'_> {
+        Layer {
+            d: Cow::Borrowed(self.get(d)),
+            z0: Cow::Borrowed(self.get(z0)),
+            mu_a: Cow::Borrowed(self.get(mu_a)),
+            e0: Cow::Borrowed(self.get(e0)),
+            n: None,
+        }
+    }
+
+    /// Constructs [`struct@ThermalProperties`] borrowing their fields from
+    /// the interner
+    pub fn thermal_properties(
+        &self,
+        rho: Interned,
+        c: Interned,
+        k: Interned,
+    ) -> ThermalProperties<'_> {
+        ThermalProperties {
+            rho: Cow::Borrowed(self.get(rho)),
+            c: Cow::Borrowed(self.get(c)),
+            k: Cow::Borrowed(self.get(k)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::greens::{Beam, LargeBeam};
+
+    #[test]
+    fn interned_layers() {
+        let mut interner = Interner::new();
+
+        let rho = interner.intern_f64(64, 1.0);
+        let c = interner.intern_f64(64, 4.178);
+        let k = interner.intern_f64(64, 0.0063);
+        let d = interner.intern_f64(64, 0.001);
+        let e0 = interner.intern_f64(64, 1.0);
+        assert_eq!(rho, e0);
+
+        // the same value at another precision is stored separately
+
+        assert_ne!(interner.intern_f64(128, 1.0), e0);
+
+        let depths = (0..1000)
+            .map(|i| interner.intern_f64(64, f64::from(i) * 0.001))
+            .collect::<Vec<_>>();
+        let mu_a = [10.0, 100.0, 1000.0].map(|mu_a| interner.intern_f64(64, mu_a));
+
+        // 1000 layers hold 4000 values between them, of which only the depths
+        // differ. the depth of 0.001 is shared with d
+
+        assert_eq!(interner.len(), 5 + 999 + 3);
+
+        let thermal_properties = interner.thermal_properties(rho, c, k);
+        let owned_thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+
+        let layers = depths
+            .iter()
+            .zip(mu_a.iter().cycle())
+            .map(|(&z0, &mu_a)| interner.layer(d, z0, mu_a, e0))
+            .collect::<Vec<_>>();
+
+        let z = Float::with_val_64(64, 0.0105);
+        let r = Float::with_val_64(64, 0.0);
+        let tp = Float::with_val_64(64, 0.01);
+
+        // equal values are shared across structures, too
+
+        assert!(std::ptr::eq(
+            thermal_properties.rho.as_ref(),
+            layers[0].e0.as_ref()
+        ));
+
+        for (i, layer) in layers.iter().enumerate() {
+            assert!(std::ptr::eq(layer.d.as_ref(), layers[0].d.as_ref()));
+            assert!(std::ptr::eq(
+                layer.mu_a.as_ref(),
+                layers[i % 3].mu_a.as_ref()
+            ));
+
+            let owned = Layer::from_f64(
+                64,
+                0.001,
+                i as f64 * 0.001,
+                [10.0, 100.0, 1000.0][i % 3],
+                1.0,
+            );
+            assert_eq!(
+                LargeBeam.evaluate_with(64, &thermal_properties, layer, &z, &r, &tp),
+                LargeBeam.evaluate_with(64, &owned_thermal_properties, &owned, &z, &r, &tp)
+            );
+        }
+    }
+}
@@ -13,7 +13,7 @@
 #![deny(clippy::cast_lossless)]
 #![deny(clippy::clone_on_ref_ptr)]
 #![deny(clippy::doc_markdown)]
-#![deny(clippy::empty_enum)]
+#![deny(clippy::empty_enums)]
 #![deny(clippy::enum_glob_use)]
 #![deny(clippy::exit)]
 #![deny(clippy::explicit_into_iter_loop)]
@@ -29,6 +29,18 @@
 #![deny(clippy::mut_mut)]
 
 pub mod bessel;
+pub mod damage;
+pub mod fitting;
 pub mod greens;
+pub mod interner;
+pub mod modulated;
 pub mod quadrature;
+pub mod spectrum;
+pub mod units;
 pub mod utilities;
+
+#[cfg(test)]
+mod test_beams;
+
+#[cfg(all(test, feature = "tracing"))]
+mod test_tracing;
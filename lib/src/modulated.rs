@@ -0,0 +1,213 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! The response to sinusoidally modulated sources, for frequency-domain
+//! (lock-in) thermal analysis
+//!
+//! Irradiance varying as `exp(i * omega * t)` produces, once the transient
+//! from switching it on has died away, a temperature rise varying at the
+//! same frequency as `T(omega) * exp(i * omega * t)`. The temperature rise is
+//! the convolution of the source with the kernel integrated over time in
+//! [`mod@crate::greens`], so `T(omega)` is that kernel integrated against
+//! `exp(-i * omega * t)` over 0..inf. Its modulus is the amplitude of the
+//! periodic temperature rise, and its argument is the phase of it relative
+//! to the source, which is negative as the temperature lags behind
+
+use rug::{
+    float::{Constant, Special},
+    Assign, Complex, Float,
+};
+
+use crate::{
+    greens::{Beam, Layer, ThermalProperties},
+    quadrature::{self, Quadrature},
+};
+
+/// The upper limit on the number of periods integrated over by
+/// [`fn@complex_temperature_rise`]
+pub const PERIOD_LIMIT: u64 = 1 << 12;
+
+/// Calculates the complex temperature rise `T(omega)` at the point (z, r)
+/// resulting from irradiance modulated at `frequency` (units: Hz), returning
+/// it alongside its approximate error
+///
+/// At a frequency of 0, this is the steady state, i.e.
+/// [`fn@crate::greens::temperature_rise`] over 0..inf. Otherwise, the kernel is
+/// integrated against `exp(-i * omega * t)` one period at a time, with the
+/// remainder beyond the last period approximated by integration by parts as
+/// `-i * G(t) / omega`. Periods are added until that estimate of the whole
+/// changes by no more than `epsilon` or [`PERIOD_LIMIT`] periods have been
+/// integrated over, and the last change is reported as the error. `epsilon`
+/// is also passed to `quadrature`
+///
+/// As with a continuous exposure, the steady state of a beam which is
+/// radially uniform is infinite, as is the response to it at a frequency of 0
+#[allow(clippy::too_many_arguments)]
+pub fn complex_temperature_rise(
+    precision: u64,
+    quadrature: &impl Quadrature<Float>,
+    beam: &impl Beam,
+    thermal_properties: &ThermalProperties<'_>,
+    layer: &Layer<'_>,
+    z: &Float,
+    r: &Float,
+    epsilon: &Float,
+    frequency: &Float,
+) -> (Complex, Float) {
+    let kernel = |t: &Float| beam.evaluate_with(precision, thermal_properties, layer, z, r, t);
+    let mut start = Float::with_val_64(precision, Special::Zero);
+
+    if frequency.is_zero() {
+        let (f, (a, b)) = quadrature::finite_bounds(
+            |t| kernel(&t),
+            (&start, &Float::with_val_64(precision, Special::Infinity)),
+            precision,
+        );
+        let (value, error) = quadrature.integrate(f, epsilon, (&a, &b));
+
+        return (Complex::with_val_64(precision, value), error);
+    }
+
+    let mut omega = Float::with_val_64(precision, Constant::Pi);
+    omega *= 2;
+    omega *= frequency;
+
+    let mut period = Float::with_val_64(precision, Constant::Pi);
+    period *= 2;
+    period /= Float::with_val_64(precision, omega.abs_ref());
+
+    let weighted = |t: &Float, sine: bool| {
+        let mut weight = Float::with_val_64(precision, t * &omega);
+
+        if sine {
+            weight.sin_mut();
+            weight *= -1;
+        } else {
+            weight.cos_mut();
+        }
+
+        weight *= kernel(t);
+        weight
+    };
+
+    let mut sum = Complex::new_64(precision);
+    let mut estimate = Complex::new_64(precision);
+    let mut change = Float::with_val_64(precision, Special::Infinity);
+    let mut end = Float::new_64(precision);
+
+    for periods in 1..=PERIOD_LIMIT {
+        // the end of each period is computed afresh so that rounding doesn't
+        // accumulate across them
+
+        end.assign(&period);
+        end *= periods;
+
+        *sum.mut_real() += quadrature
+            .integrate(|t| weighted(&t, false), epsilon, (&start, &end))
+            .0;
+        *sum.mut_imag() += quadrature
+            .integrate(|t| weighted(&t, true), epsilon, (&start, &end))
+            .0;
+
+        // exp(-i * omega * t) is 1 at the end of each period, leaving only
+        // -i * G(t) / omega as the remainder
+
+        let mut next = sum.clone();
+        let mut remainder = kernel(&end);
+        remainder /= &omega;
+        *next.mut_imag() -= remainder;
+
+        change.assign(Complex::with_val_64(precision, &next - &estimate).abs_ref());
+        estimate = next;
+        start.assign(&end);
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(periods, estimate = %estimate, change = %change, "integrated period");
+
+        if change <= *epsilon {
+            break;
+        }
+    }
+
+    (estimate, change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        greens::{self, FlatTopBeam},
+        quadrature::{GaussKronrod, G7_K15},
+    };
+
+    #[test]
+    fn complex_temperature_rise() {
+        let thermal_properties = ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063);
+        let layer = Layer::from_f64(64, 0.001, 0.0, 1000.0, 1.0);
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let beam = FlatTopBeam::new(&Float::with_val_64(64, 0.01)).expect("Invalid beam");
+        let z = Float::with_val_64(64, 0.0005);
+        let r = Float::with_val_64(64, Special::Zero);
+        let epsilon = Float::with_val_64(64, 1e-6);
+
+        let response = |frequency: f64| {
+            super::complex_temperature_rise(
+                64,
+                &quadrature,
+                &beam,
+                &thermal_properties,
+                &layer,
+                &z,
+                &r,
+                &epsilon,
+                &Float::with_val_64(64, frequency),
+            )
+            .0
+        };
+
+        // without modulation, this is the steady state
+
+        let (steady_state, _) = greens::temperature_rise(
+            64,
+            &quadrature,
+            &beam,
+            &thermal_properties,
+            &layer,
+            &z,
+            &r,
+            &epsilon,
+            (
+                &Float::with_val_64(64, Special::Zero),
+                &Float::with_val_64(64, Special::Infinity),
+            ),
+        )
+        .expect("Invalid bounds");
+
+        let unmodulated = response(0.0);
+        assert_eq!(*unmodulated.real(), steady_state);
+        assert!(unmodulated.imag().is_zero());
+
+        // modulation attenuates the temperature rise more the faster it is,
+        // and it lags behind the source
+
+        let mut amplitude = steady_state;
+
+        for frequency in [10.0, 100.0, 1000.0] {
+            let modulated = response(frequency);
+            let (modulus, phase) = (
+                Float::with_val_64(64, modulated.abs_ref()),
+                Float::with_val_64(64, modulated.arg_ref()),
+            );
+
+            assert!(modulus < amplitude);
+            assert!(phase.is_sign_negative());
+            assert!(phase > -Float::with_val_64(64, Constant::Pi) / 2);
+
+            amplitude = modulus;
+        }
+    }
+}
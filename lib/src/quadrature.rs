@@ -1,7 +1,27 @@
 // SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
 
-use rug::{Assign, Float};
-use std::borrow::Borrow;
+//! Numerical integration
+//!
+//! If the `tracing` feature is enabled, each integration is wrapped in a
+//! span, and the progress of its refinement is emitted as `TRACE` level
+//! events. Otherwise, no instrumentation is compiled in at all
+
+use rug::{
+    float::{Constant, Special},
+    ops::PowAssign,
+    Assign, Float,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, LazyLock, Mutex,
+    },
+};
 
 //TODO: genericize the parameters here. taking arbitrary-precision floats
 //      everywhere is excessive
@@ -9,15 +29,357 @@ use std::borrow::Borrow;
 //TODO: consider swapping (or supporting as an option) `f64` usage in
 //      gauss-kronrod rules for `rug::Float`
 
+/// An error encountered while integrating with
+/// `Quadrature::try_integrate`
+#[derive(Clone, PartialEq, Debug)]
+pub enum QuadratureError<T> {
+    /// The approximate error still exceeded the tolerance once the limit on
+    /// refinement was reached
+    NotConverged {
+        /// The integral as it was when refinement stopped
+        value: T,
+
+        /// The approximate error of `value`
+        error: T,
+
+        /// The tolerance which was not met
+        epsilon: T,
+    },
+
+    /// Refinement stopped without meeting the tolerance, and successive
+    /// refinements changed the integral by no less than the ones before them,
+    /// as happens when the integral diverges
+    Divergent {
+        /// The integral as it was when refinement stopped
+        value: T,
+
+        /// The approximate error of `value`
+        error: T,
+    },
+
+    /// The integration was cancelled before it completed
+    Cancelled {
+        /// The integral as of the last refinement completed before the
+        /// integration was cancelled, which is NaN if there was none
+        value: T,
+
+        /// The approximate error of `value`
+        error: T,
+    },
+}
+
+impl<T: fmt::Display> fmt::Display for QuadratureError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConverged { error, epsilon, .. } => write!(
+                f,
+                "the integral did not converge, with an approximate error of {error} exceeding the tolerance of {epsilon}"
+            ),
+            Self::Divergent { value, .. } => write!(
+                f,
+                "the integral appears to diverge, with successive refinements growing it to {value} without shrinking"
+            ),
+            Self::Cancelled { value, .. } => write!(
+                f,
+                "the integration was cancelled, with a partial estimate of {value}"
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> Error for QuadratureError<T> {}
+
 pub trait Quadrature<T> {
     /// Integrate over the region a..b and return the integral and approximate
     /// error
+    ///
+    /// The implementations here accept an upper bound of positive infinity
+    /// (see [`fn@finite_bounds`])
     fn integrate(
         &self,
         f: impl Fn(T) -> T,
         epsilon: impl Borrow<T>,
         bounds: (impl Borrow<T>, impl Borrow<T>),
     ) -> (T, T);
+
+    /// Integrate over the region a..b as in `Quadrature::integrate`, returning
+    /// [`QuadratureError::NotConverged`] rather than an integral which does
+    /// not meet the tolerance
+    ///
+    /// By default, this is an error if the approximate error exceeds
+    /// `epsilon`. An approximate error which is unordered with respect to
+    /// `epsilon` (such as NaN, for methods which have no estimate) is never
+    /// an error. Implementations with a different notion of convergence
+    /// override this, as do those which recognize a divergent integral
+    /// ([`struct@GaussKronrod`], [`struct@Romberg`] and
+    /// [`struct@AdaptiveSimpson`]), returning
+    /// [`QuadratureError::Divergent`] instead
+    fn try_integrate(
+        &self,
+        f: impl Fn(T) -> T,
+        epsilon: impl Borrow<T>,
+        bounds: (impl Borrow<T>, impl Borrow<T>),
+    ) -> Result<(T, T), QuadratureError<T>>
+    where
+        T: PartialOrd + Clone,
+    {
+        let epsilon = epsilon.borrow();
+        let (value, error) = self.integrate(f, epsilon, bounds);
+
+        if error > *epsilon {
+            return Err(QuadratureError::NotConverged {
+                value,
+                error,
+                epsilon: epsilon.clone(),
+            });
+        }
+
+        Ok((value, error))
+    }
+
+    /// Integrate over the region a..b as in `Quadrature::try_integrate`,
+    /// stopping early with [`QuadratureError::Cancelled`] once `cancel` is
+    /// set
+    ///
+    /// This is meant for interactive use, where `cancel` is shared (e.g. as
+    /// an `Arc<AtomicBool>`) with whatever decides to abort the integration.
+    /// [`struct@GaussKronrod`] and [`struct@Romberg`] check it before each
+    /// subinterval or node, reporting the integral as of the last refinement
+    /// they completed. [`struct@AdaptiveSimpson`] checks it before each
+    /// subinterval it bisects, reporting the accepted subintervals plus the
+    /// estimates of those remaining (with an infinite error), and
+    /// [`struct@TanhSinh`] checks it before each level. By default, as for
+    /// the methods of fixed order, the integration runs to completion and is
+    /// only then reported as cancelled if `cancel` was set
+    ///
+    /// Most of the functions of [`mod@crate::greens`] integrate with
+    /// `Quadrature::integrate` and take no flag. The `*_cancellable` methods
+    /// of `MultiLayer` (such as `MultiLayer::temperature_rise_cancellable`)
+    /// pass theirs to this, and anything else which is to be cancellable
+    /// must call this directly
+    fn try_integrate_cancellable(
+        &self,
+        f: impl Fn(T) -> T,
+        epsilon: impl Borrow<T>,
+        bounds: (impl Borrow<T>, impl Borrow<T>),
+        cancel: &AtomicBool,
+    ) -> Result<(T, T), QuadratureError<T>>
+    where
+        T: PartialOrd + Clone,
+    {
+        let result = self.try_integrate(f, epsilon, bounds);
+
+        if !cancel.load(Ordering::Relaxed) {
+            return result;
+        }
+
+        match result {
+            Ok((value, error))
+            | Err(
+                QuadratureError::NotConverged { value, error, .. }
+                | QuadratureError::Divergent { value, error }
+                | QuadratureError::Cancelled { value, error },
+            ) => Err(QuadratureError::Cancelled { value, error }),
+        }
+    }
+}
+
+/// Whether `cancel` is present and set
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+}
+
+/// The number of bits of precision held back by [`fn@default_epsilon`] to
+/// allow for the rounding error accumulated over an integration
+pub const DEFAULT_EPSILON_GUARD_BITS: u64 = 11;
+
+/// A default tolerance for integrating at the given precision (in bits),
+/// `2^-(precision - DEFAULT_EPSILON_GUARD_BITS)`
+///
+/// The unit roundoff (the largest relative error of rounding) at a precision
+/// is `2^-precision`, and the rounding error of an integral accumulates to
+/// several times that. A tolerance far below this can never be met, so
+/// refinement continues all the way to its limit for nothing, while one far
+/// above it gives up digits the precision could have held. At 64 bits, this
+/// is about `1.1e-16`, the same as the unit roundoff of an `f64` (half of
+/// its machine epsilon), and it is 1 at or below
+/// `DEFAULT_EPSILON_GUARD_BITS` bits
+pub fn default_epsilon(precision: u64) -> Float {
+    let mut epsilon = Float::with_val_64(precision, 1);
+    epsilon >>= precision.saturating_sub(DEFAULT_EPSILON_GUARD_BITS) as usize;
+    epsilon
+}
+
+/// How the `epsilon` passed to `Quadrature::integrate` is interpreted by the
+/// integrators with an absolute tolerance ([`struct@Romberg`] and
+/// [`struct@AdaptiveSimpson`])
+///
+/// [`struct@GaussKronrod`] and [`struct@TanhSinh`] always treat `epsilon` as
+/// a relative tolerance
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Tolerance {
+    /// `epsilon` bounds the error itself
+    #[default]
+    Absolute,
+
+    /// `epsilon` bounds the error relative to the magnitude of the integral,
+    /// so the same number of digits is sought regardless of its scale. An
+    /// integral of zero can't be reached this way
+    Relative,
+
+    /// `epsilon * max(1, |I|)`, which is absolute for integrals smaller than
+    /// 1 in magnitude and relative for those larger
+    Mixed,
+}
+
+impl Tolerance {
+    /// Calculates the absolute tolerance given `epsilon` and an estimate of
+    /// the integral
+    pub fn scale(self, epsilon: &Float, integral: &Float) -> Float {
+        let mut tolerance = Float::with_val_64(epsilon.prec_64(), integral.abs_ref());
+
+        match self {
+            Self::Absolute => tolerance.assign(epsilon),
+            Self::Relative => tolerance *= epsilon,
+            Self::Mixed => {
+                tolerance.max_mut(&Float::with_val_64(epsilon.prec_64(), 1));
+                tolerance *= epsilon;
+            }
+        }
+
+        tolerance
+    }
+}
+
+/// Checks the approximate error reported alongside `value` against
+/// `epsilon` interpreted according to `tolerance`, reporting an integral
+/// which isn't accepted as [`QuadratureError::Divergent`] if `divergence`
+/// was tracked and recognized it as such
+fn check_tolerance(
+    tolerance: Tolerance,
+    epsilon: &Float,
+    (value, error): (Float, Float),
+    divergence: Option<&Divergence>,
+) -> Result<(Float, Float), QuadratureError<Float>> {
+    let epsilon = tolerance.scale(epsilon, &value);
+
+    if error > epsilon {
+        if divergence.is_some_and(Divergence::is_divergent) {
+            return Err(QuadratureError::Divergent { value, error });
+        }
+
+        return Err(QuadratureError::NotConverged {
+            value,
+            error,
+            epsilon,
+        });
+    }
+
+    Ok((value, error))
+}
+
+/// The number of successive refinements which must each change the integral
+/// by no less than (nine tenths of) the change before them for it to be
+/// considered divergent
+pub const DIVERGENCE_REFINEMENTS: u32 = 3;
+
+/// Tracks the changes to an estimate of an integral across successive
+/// refinements to recognize one which diverges
+///
+/// For a convergent integral, the changes shrink as the estimate settles,
+/// even if only slowly near an integrable singularity (by a factor of about
+/// `1 / sqrt(2)` per halving for `1 / sqrt(t)` with gauss-kronrod). Those of
+/// a divergent one keep the same sign and don't shrink, such as the `ln(2)`
+/// added by each halving for `1 / t`. Non-finite estimates give no
+/// indication either way
+#[derive(Clone, PartialEq, Debug, Default)]
+struct Divergence {
+    previous: Option<Float>,
+    change: Option<Float>,
+
+    /// The number of consecutive refinements whose changes didn't shrink
+    growing: u32,
+}
+
+impl Divergence {
+    /// Records the estimate resulting from the latest refinement
+    fn refine(&mut self, estimate: &Float) {
+        if let Some(previous) = &self.previous {
+            let change = Float::with_val_64(estimate.prec_64(), estimate - previous);
+
+            let grew = self.change.as_ref().is_some_and(|last| {
+                let mut shrunk = Float::with_val_64(last.prec_64(), last.abs_ref());
+                shrunk *= 0.9;
+
+                change.is_finite()
+                    && !change.is_zero()
+                    && change.is_sign_positive() == last.is_sign_positive()
+                    && Float::with_val_64(change.prec_64(), change.abs_ref()) >= shrunk
+            });
+
+            if grew {
+                self.growing += 1;
+            } else {
+                self.growing = 0;
+            }
+
+            self.change = Some(change);
+        }
+
+        self.previous = Some(estimate.clone());
+    }
+
+    /// Whether the estimates recorded so far bear the signature of a
+    /// divergent integral
+    fn is_divergent(&self) -> bool {
+        self.growing >= DIVERGENCE_REFINEMENTS
+    }
+}
+
+/// An integrand returned by [`fn@finite_bounds`]
+pub type Integrand<'a> = Box<dyn Fn(Float) -> Float + 'a>;
+
+/// Maps an integral over a..b onto finite bounds, so that an upper bound of
+/// positive infinity may be passed to any of the integrators here
+///
+/// If `b` is positive infinity, `t` over `a..inf` is substituted with
+/// `a + u / (1 - u)` over `0..1`, scaling `f` by the jacobian
+/// `1 / (1 - u)^2`. The substituted integrand is taken to be 0 at `u = 1`,
+/// which only holds if `f` decays faster than `1 / t^2`. Otherwise, `f` and
+/// the bounds are returned as they are
+pub fn finite_bounds<'a>(
+    f: impl Fn(Float) -> Float + 'a,
+    (a, b): (&Float, &Float),
+    precision: u64,
+) -> (Integrand<'a>, (Float, Float)) {
+    if !b.is_infinite() || b.is_sign_negative() {
+        return (Box::new(f), (a.clone(), b.clone()));
+    }
+
+    let a = a.clone();
+
+    (
+        Box::new(move |u| {
+            if u >= 1 {
+                return Float::with_val_64(precision, Special::Zero);
+            }
+
+            let mut jacobian = Float::with_val_64(precision, 1);
+            jacobian -= &u;
+            jacobian.recip_mut();
+
+            let mut t = u;
+            t *= &jacobian;
+            t += &a;
+
+            jacobian.square_mut();
+            f(t) * jacobian
+        }),
+        (
+            Float::with_val_64(precision, Special::Zero),
+            Float::with_val_64(precision, 1),
+        ),
+    )
 }
 
 /// A struct providing an implementation of the [`trait@Quadrature`] trait for
@@ -43,18 +405,83 @@ impl Quadrature<Float> for TanhSinh {
         epsilon: impl Borrow<Float>,
         (a, b): (impl Borrow<Float>, impl Borrow<Float>),
     ) -> (Float, Float) {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+
         tanh_sinh(
             f,
             epsilon.borrow(),
-            (a.borrow(), b.borrow()),
+            (&a, &b),
             self.iteration_limit,
             self.precision,
         )
     }
+
+    /// The approximate error of [`fn@tanh_sinh`] is relative, and converges
+    /// once within ten times `epsilon`, so that is the tolerance checked
+    /// against here
+    fn try_integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, None)
+    }
+
+    fn try_integrate_cancellable(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, Some(cancel))
+    }
+}
+
+impl TanhSinh {
+    /// `Quadrature::try_integrate`, stopping early once `cancel` is set
+    fn try_integrate_with(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: &Float,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: Option<&AtomicBool>,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+
+        let (value, error) = tanh_sinh_cancellable(
+            f,
+            epsilon,
+            (&a, &b),
+            self.iteration_limit,
+            self.precision,
+            cancel,
+        );
+
+        if is_cancelled(cancel) {
+            return Err(QuadratureError::Cancelled { value, error });
+        }
+
+        let tolerance = Float::with_val_64(self.precision, epsilon * 10);
+        if error > tolerance {
+            return Err(QuadratureError::NotConverged {
+                value,
+                error,
+                epsilon: tolerance,
+            });
+        }
+
+        Ok((value, error))
+    }
 }
 
 /// A struct providing an implementation of the [`trait@Quadrature`] trait for
 /// the Gauss-Kronrod quadrature method
+///
+/// The nodes and weights of `rule` are multiplied in as they are, so unlike
+/// [`struct@GaussLegendre`], there is nothing to compute (or cache) ahead of
+/// an integration
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct GaussKronrod<'a> {
     /// The upper limit on intervals
@@ -74,15 +501,363 @@ impl<'a> Quadrature<Float> for GaussKronrod<'a> {
         epsilon: impl Borrow<Float>,
         (a, b): (impl Borrow<Float>, impl Borrow<Float>),
     ) -> (Float, Float) {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+
         gauss_kronrod(
             f,
             self.rule,
             epsilon.borrow(),
-            (a.borrow(), b.borrow()),
+            (&a, &b),
+            self.interval_limit,
+            self.precision,
+        )
+    }
+
+    /// As by default, the approximate error (which is relative for
+    /// [`fn@gauss_kronrod`]) is checked against `epsilon`. An integral which
+    /// isn't accepted is reported as [`QuadratureError::Divergent`] if its
+    /// successive subdivisions changed it by no less than the ones before
+    /// them
+    fn try_integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, None)
+    }
+
+    fn try_integrate_cancellable(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, Some(cancel))
+    }
+}
+
+impl<'a> GaussKronrod<'a> {
+    /// `Quadrature::try_integrate`, stopping early once `cancel` is set
+    fn try_integrate_with(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: &Float,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: Option<&AtomicBool>,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+        let mut divergence = Divergence::default();
+
+        let (value, error) = gauss_kronrod_tracked(
+            f,
+            self.rule,
+            epsilon,
+            (&a, &b),
             self.interval_limit,
             self.precision,
+            &mut divergence,
+            cancel,
+        );
+
+        if is_cancelled(cancel) {
+            return Err(QuadratureError::Cancelled { value, error });
+        }
+
+        // the approximate error is already relative, so it is compared with
+        // `epsilon` as it is
+
+        check_tolerance(
+            Tolerance::Absolute,
+            epsilon,
+            (value, error),
+            Some(&divergence),
+        )
+    }
+}
+
+/// A struct providing an implementation of the [`trait@Quadrature`] trait for
+/// the Romberg quadrature method
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Romberg {
+    /// The upper limit on iteration (the number of times the trapezoid rule's
+    /// step size is halved). Limits above 64 are treated as 64, as the next
+    /// halving would need 2^64 new nodes
+    pub iteration_limit: u64,
+
+    /// Floating point precision (in bits) for MPFR floats
+    pub precision: u64,
+
+    /// How `epsilon` is interpreted
+    pub tolerance: Tolerance,
+}
+
+impl Quadrature<Float> for Romberg {
+    fn integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> (Float, Float) {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+
+        romberg(
+            f,
+            epsilon.borrow(),
+            self.tolerance,
+            (&a, &b),
+            self.iteration_limit,
+            self.precision,
         )
     }
+
+    /// The approximate error is always absolute, so it is checked against
+    /// `epsilon` as interpreted according to `tolerance`. An integral which
+    /// isn't accepted is reported as [`QuadratureError::Divergent`] if its
+    /// successive extrapolations changed it by no less than the ones before
+    /// them
+    fn try_integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, None)
+    }
+
+    fn try_integrate_cancellable(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, Some(cancel))
+    }
+}
+
+impl Romberg {
+    /// `Quadrature::try_integrate`, stopping early once `cancel` is set
+    fn try_integrate_with(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: &Float,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: Option<&AtomicBool>,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+        let mut divergence = Divergence::default();
+
+        let (value, error) = romberg_tracked(
+            f,
+            epsilon,
+            self.tolerance,
+            (&a, &b),
+            self.iteration_limit,
+            self.precision,
+            &mut divergence,
+            cancel,
+        );
+
+        if is_cancelled(cancel) {
+            return Err(QuadratureError::Cancelled { value, error });
+        }
+
+        check_tolerance(self.tolerance, epsilon, (value, error), Some(&divergence))
+    }
+}
+
+/// A struct providing an implementation of the [`trait@Quadrature`] trait for
+/// fixed-order Gauss-Legendre quadrature
+///
+/// This is exact for polynomials of degree up to `2 * order - 1`, and is the
+/// fastest option for very smooth integrands. As the order is fixed, the
+/// `epsilon` passed to `Quadrature::integrate` is ignored and the error is
+/// always reported as NaN. The nodes and weights are computed once for each
+/// combination of order and precision, then shared by every later
+/// integration (see [`fn@gauss_legendre_rule`])
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct GaussLegendre {
+    /// The number of nodes
+    pub order: u64,
+
+    /// Floating point precision (in bits) for MPFR floats
+    pub precision: u64,
+}
+
+impl Quadrature<Float> for GaussLegendre {
+    fn integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        _epsilon: impl Borrow<Float>,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> (Float, Float) {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+
+        gauss_legendre_with_rule(
+            f,
+            &gauss_legendre_rule(self.order, self.precision),
+            (&a, &b),
+            self.precision,
+        )
+    }
+}
+
+/// A struct providing an implementation of the [`trait@Quadrature`] trait for
+/// adaptive Simpson quadrature
+///
+/// This is cheap and simple, so it is useful for quick estimates and for
+/// cross-checking the other methods. Each subinterval is bisected until the
+/// Simpson estimates over it and over its two halves differ by less than its
+/// share of `epsilon`, which is an absolute tolerance split in proportion to
+/// the width of each subinterval. The reported error is the sum of the error
+/// estimates of every accepted subinterval
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AdaptiveSimpson {
+    /// The upper limit on the number of times a subinterval is bisected
+    pub depth_limit: u64,
+
+    /// Floating point precision (in bits) for MPFR floats
+    pub precision: u64,
+
+    /// How `epsilon` is interpreted. Relative tolerances are relative to the
+    /// Simpson estimate over the whole interval
+    pub tolerance: Tolerance,
+}
+
+impl Quadrature<Float> for AdaptiveSimpson {
+    fn integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> (Float, Float) {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+
+        adaptive_simpson(
+            f,
+            epsilon.borrow(),
+            self.tolerance,
+            (&a, &b),
+            self.depth_limit,
+            self.precision,
+        )
+    }
+
+    /// The approximate error is always absolute, so it is checked against
+    /// `epsilon` as interpreted according to `tolerance`. An integral which
+    /// isn't accepted is reported as [`QuadratureError::Divergent`] if each
+    /// new depth of bisection changed it by no less than the one before it
+    /// (see [`fn@adaptive_simpson`])
+    fn try_integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, None)
+    }
+
+    fn try_integrate_cancellable(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: impl Borrow<Float>,
+        bounds: (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: &AtomicBool,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        self.try_integrate_with(f, epsilon.borrow(), bounds, Some(cancel))
+    }
+}
+
+impl AdaptiveSimpson {
+    /// `Quadrature::try_integrate`, stopping early once `cancel` is set
+    fn try_integrate_with(
+        &self,
+        f: impl Fn(Float) -> Float,
+        epsilon: &Float,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+        cancel: Option<&AtomicBool>,
+    ) -> Result<(Float, Float), QuadratureError<Float>> {
+        let (f, (a, b)) = finite_bounds(f, (a.borrow(), b.borrow()), self.precision);
+        let mut divergence = Divergence::default();
+
+        let (value, error) = adaptive_simpson_tracked(
+            f,
+            epsilon,
+            self.tolerance,
+            (&a, &b),
+            self.depth_limit,
+            self.precision,
+            &mut divergence,
+            cancel,
+        );
+
+        if is_cancelled(cancel) {
+            return Err(QuadratureError::Cancelled { value, error });
+        }
+
+        check_tolerance(self.tolerance, epsilon, (value, error), Some(&divergence))
+    }
+}
+
+/// A struct providing an implementation of the [`trait@Quadrature`] trait for
+/// the trapezoid rule over a user-specified set of nodes
+///
+/// This is useful for reproducing integrations done elsewhere, or for
+/// placing nodes where the integrand is known to vary quickly. Only the nodes
+/// strictly within the bounds are used, alongside the bounds themselves, so
+/// the same nodes may be reused across different bounds. As with
+/// [`struct@GaussLegendre`], the `epsilon` passed to `Quadrature::integrate`
+/// is ignored and the error is always reported as NaN
+///
+/// If the upper bound is positive infinity, the nodes are mapped through the
+/// same substitution as the integrand (see [`fn@finite_bounds`]), so they
+/// still fall where they were placed
+#[derive(Clone, PartialEq, Debug)]
+pub struct CustomNodeQuadrature {
+    /// The nodes to evaluate the integrand at, sorted in ascending order
+    pub nodes: Vec<Float>,
+
+    /// Floating point precision (in bits) for MPFR floats
+    pub precision: u64,
+}
+
+impl Quadrature<Float> for CustomNodeQuadrature {
+    fn integrate(
+        &self,
+        f: impl Fn(Float) -> Float,
+        _epsilon: impl Borrow<Float>,
+        (a, b): (impl Borrow<Float>, impl Borrow<Float>),
+    ) -> (Float, Float) {
+        let (a, b) = (a.borrow(), b.borrow());
+        let infinite = b.is_infinite() && b.is_sign_positive();
+
+        // t over a..inf becomes (t - a) / (1 + t - a) over 0..1
+
+        let nodes = self
+            .nodes
+            .iter()
+            .filter(|node| *node > a && *node < b)
+            .map(|node| {
+                let mut node = Float::with_val_64(self.precision, node - a);
+
+                if infinite {
+                    let mut denominator = Float::with_val_64(self.precision, &node + 1);
+                    denominator.recip_mut();
+                    node *= denominator;
+                } else {
+                    node += a;
+                }
+
+                node
+            })
+            .collect::<Vec<_>>();
+
+        let (f, (a, b)) = finite_bounds(f, (a, b), self.precision);
+
+        trapezoid(f, &nodes, (&a, &b), self.precision)
+    }
 }
 
 /// Nodes and weights from G7 / K15 as a triplet of node, Kronrod weight,
@@ -163,17 +938,49 @@ pub const G7_K15: [(f64, f64, Option<f64>); 15] = [
         -9.914553711208126392068546975263285e-01,
         2.293532201052922496373200805896959e-02,
         None,
-    ),
-];
+    ),
+];
+
+pub fn gauss_kronrod(
+    f: impl Fn(Float) -> Float,
+    rule: &[(f64, f64, Option<f64>)],
+    epsilon: &Float,
+    (a, b): (&Float, &Float),
+    interval_limit: u64,
+    precision: u64,
+) -> (Float, Float) {
+    gauss_kronrod_tracked(
+        f,
+        rule,
+        epsilon,
+        (a, b),
+        interval_limit,
+        precision,
+        &mut Divergence::default(),
+        None,
+    )
+}
 
-pub fn gauss_kronrod(
+/// [`fn@gauss_kronrod`], recording the estimate after each subdivision in
+/// `divergence`
+///
+/// If `cancel` is set before a subinterval, the integral and error of the
+/// last subdivision completed are returned (or NaN and infinity, if there
+/// was none)
+#[allow(clippy::too_many_arguments)]
+fn gauss_kronrod_tracked(
     f: impl Fn(Float) -> Float,
     rule: &[(f64, f64, Option<f64>)],
     epsilon: &Float,
     (a, b): (&Float, &Float),
     interval_limit: u64,
     precision: u64,
+    divergence: &mut Divergence,
+    cancel: Option<&AtomicBool>,
 ) -> (Float, Float) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("gauss_kronrod", a = %a, b = %b).entered();
+
     let mut n_intervals = 1;
 
     let mut kahan_t = Float::new_64(precision);
@@ -192,6 +999,9 @@ pub fn gauss_kronrod(
     let mut absolute_region_midpoint = Float::new_64(precision);
     let mut half_region_width = Float::new_64(precision);
 
+    let mut completed_integral = Float::with_val_64(precision, Special::Nan);
+    let mut completed_error = Float::with_val_64(precision, Special::Infinity);
+
     while n_intervals <= interval_limit {
         region_width.assign(b - a);
         region_width /= n_intervals;
@@ -206,6 +1016,10 @@ pub fn gauss_kronrod(
         gauss_compensation.assign(0);
 
         for interval in 0..n_intervals {
+            if is_cancelled(cancel) {
+                return (completed_integral, completed_error);
+            }
+
             absolute_region_midpoint.assign(&region_width);
             absolute_region_midpoint *= &interval;
             absolute_region_midpoint += a;
@@ -233,6 +1047,14 @@ pub fn gauss_kronrod(
             gauss_kronrod_acc *= &half_region_width;
             gauss_acc *= &half_region_width;
 
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                lower = %Float::with_val_64(precision, &absolute_region_midpoint - &half_region_width),
+                upper = %Float::with_val_64(precision, &absolute_region_midpoint + &half_region_width),
+                error = %Float::with_val_64(precision, &gauss_kronrod_acc - &gauss_acc).abs(),
+                "evaluated subinterval",
+            );
+
             // the following is just the kahan summation algorithm
 
             gauss_kronrod_acc -= &gauss_kronrod_compensation;
@@ -255,16 +1077,228 @@ pub fn gauss_kronrod(
         relative_error /= &gauss_kronrod_integral;
         relative_error.abs_mut();
 
+        divergence.refine(&gauss_kronrod_integral);
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            n_intervals,
+            integral = %gauss_kronrod_integral,
+            relative_error = %relative_error,
+            "completed subdivision",
+        );
+
         if &relative_error <= epsilon {
             break;
         }
 
+        completed_integral.assign(&gauss_kronrod_integral);
+        completed_error.assign(&relative_error);
+
         n_intervals <<= 1;
     }
 
     (gauss_kronrod_integral, relative_error)
 }
 
+/// The nodes and weights of a Gauss-Legendre rule over -1..1 as pairs of node
+/// and weight
+pub type GaussLegendreRule = Arc<[(Float, Float)]>;
+
+/// A thread-safe cache of Gauss-Legendre rules, keyed by order and precision
+///
+/// Entries are never evicted, so the cache grows with each distinct
+/// combination of order and precision it is asked for. Each entry holds
+/// `order` nodes and as many weights, each of which takes
+/// `precision / 8` bytes of limbs plus a fixed overhead of a few tens of
+/// bytes, so a 64-node rule at 256 bits is roughly 8 KiB. This is negligible
+/// for the handful of rules a sweep uses, but [`RuleCache::clear`] may be
+/// used to release them if many precisions are tried
+#[derive(Default, Debug)]
+pub struct RuleCache {
+    rules: Mutex<HashMap<(u64, u64), GaussLegendreRule>>,
+}
+
+impl RuleCache {
+    /// Constructs an empty [`struct@RuleCache`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the rule of the given order and precision, calling `compute` to
+    /// produce it if it is not already cached
+    ///
+    /// The lock is held while `compute` runs, so concurrent requests for a
+    /// rule which is not yet cached compute it only once
+    pub fn get_or_insert_with(
+        &self,
+        order: u64,
+        precision: u64,
+        compute: impl FnOnce() -> GaussLegendreRule,
+    ) -> GaussLegendreRule {
+        let mut rules = self.rules.lock().unwrap_or_else(|error| error.into_inner());
+
+        Arc::clone(rules.entry((order, precision)).or_insert_with(compute))
+    }
+
+    /// The number of rules in the cache
+    pub fn len(&self) -> usize {
+        self.rules
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every rule from the cache. Rules still held elsewhere are not
+    /// freed until they are dropped
+    pub fn clear(&self) {
+        self.rules
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clear();
+    }
+}
+
+/// Gauss-Legendre rules which have already been computed
+pub static GAUSS_LEGENDRE_RULES: LazyLock<RuleCache> = LazyLock::new(RuleCache::new);
+
+/// The upper limit on iterations of newton's method for each node of a
+/// Gauss-Legendre rule
+const NEWTON_LIMIT: u32 = 100;
+
+/// Gets the Gauss-Legendre rule of the given order, computing it if it is not
+/// already cached
+///
+/// The nodes are the roots of the Legendre polynomial of degree `order`,
+/// which are found with newton's method starting from the approximation
+/// `cos(pi * (i - 1/4) / (order + 1/2))`
+pub fn gauss_legendre_rule(order: u64, precision: u64) -> GaussLegendreRule {
+    GAUSS_LEGENDRE_RULES.get_or_insert_with(order, precision, || {
+        compute_gauss_legendre_rule(order, precision)
+    })
+}
+
+fn compute_gauss_legendre_rule(order: u64, precision: u64) -> GaussLegendreRule {
+    let mut rule = Vec::new();
+
+    // newton's method stops once the step is within a few ulps of 1
+
+    let mut tolerance = Float::with_val_64(precision, 1);
+    tolerance.next_up();
+    tolerance -= 1;
+    tolerance *= 4;
+
+    // the roots are symmetric about 0, so only the positive half is searched
+    // for
+
+    for i in 1..=order.div_ceil(2) {
+        let mut x = Float::with_val_64(precision, Constant::Pi);
+        x *= 4 * i - 1;
+        x /= 4 * order + 2;
+        x.cos_mut();
+
+        for _ in 0..NEWTON_LIMIT {
+            let (p, derivative) = legendre(order, &x, precision);
+
+            let mut step = p;
+            step /= derivative;
+            x -= &step;
+
+            step.abs_mut();
+            if step <= tolerance {
+                break;
+            }
+        }
+
+        // w = 2 / ((1 - x^2) * P_n'(x)^2)
+
+        let (_, mut weight) = legendre(order, &x, precision);
+        weight.square_mut();
+        weight *= 1 - Float::with_val_64(precision, x.square_ref());
+        weight.recip_mut();
+        weight *= 2;
+
+        if !x.is_zero() {
+            rule.push((Float::with_val_64(precision, -&x), weight.clone()));
+        }
+
+        rule.push((x, weight));
+    }
+
+    rule.into()
+}
+
+/// Evaluates the Legendre polynomial of degree `n` and its derivative at `x`
+/// using the three-term recurrence
+fn legendre(n: u64, x: &Float, precision: u64) -> (Float, Float) {
+    let mut p = Float::with_val_64(precision, 1);
+    let mut p_previous = Float::with_val_64(precision, Special::Zero);
+
+    for k in 1..=n {
+        // k * P_k(x) = (2k - 1) * x * P_(k - 1)(x) - (k - 1) * P_(k - 2)(x)
+
+        let mut p_next = Float::with_val_64(precision, x * &p);
+        p_next *= 2 * k - 1;
+        p_previous *= k - 1;
+        p_next -= &p_previous;
+        p_next /= k;
+
+        p_previous = p;
+        p = p_next;
+    }
+
+    // P_n'(x) = n * (x * P_n(x) - P_(n - 1)(x)) / (x^2 - 1)
+
+    let mut derivative = Float::with_val_64(precision, x * &p);
+    derivative -= p_previous;
+    derivative *= n;
+    derivative /= Float::with_val_64(precision, x.square_ref()) - 1;
+
+    (p, derivative)
+}
+
+pub fn gauss_legendre(
+    f: impl Fn(Float) -> Float,
+    (a, b): (&Float, &Float),
+    order: u64,
+    precision: u64,
+) -> (Float, Float) {
+    gauss_legendre_with_rule(f, &gauss_legendre_rule(order, precision), (a, b), precision)
+}
+
+fn gauss_legendre_with_rule(
+    f: impl Fn(Float) -> Float,
+    rule: &[(Float, Float)],
+    (a, b): (&Float, &Float),
+    precision: u64,
+) -> (Float, Float) {
+    let mut midpoint = Float::with_val_64(precision, a + b);
+    midpoint /= 2.0;
+
+    let mut half_width = Float::with_val_64(precision, b - a);
+    half_width /= 2.0;
+
+    let mut sum = Float::with_val_64(precision, Special::Zero);
+    let mut node = Float::new_64(precision);
+
+    for (x, weight) in rule {
+        node.assign(&half_width * x);
+        node += &midpoint;
+
+        let mut y = f(node.clone());
+        y *= weight;
+        sum += y;
+    }
+
+    sum *= half_width;
+
+    (sum, Float::with_val_64(precision, Special::Nan))
+}
+
 pub fn tanh_sinh(
     f: impl Fn(Float) -> Float,
     epsilon: &Float,
@@ -272,6 +1306,25 @@ pub fn tanh_sinh(
     limit: u64,
     precision: u64,
 ) -> (Float, Float) {
+    tanh_sinh_cancellable(f, epsilon, (a, b), limit, precision, None)
+}
+
+/// [`fn@tanh_sinh`], stopping before the next level once `cancel` is set
+///
+/// The sum and error as of the last level completed are returned (the
+/// integrand at the center of the region and an infinite error, if there
+/// was none)
+fn tanh_sinh_cancellable(
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    (a, b): (&Float, &Float),
+    limit: u64,
+    precision: u64,
+    cancel: Option<&AtomicBool>,
+) -> (Float, Float) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("tanh_sinh", a = %a, b = %b).entered();
+
     let tolerance = epsilon.clone() * 10;
 
     let mut region_center = Float::new_64(precision);
@@ -288,7 +1341,7 @@ pub fn tanh_sinh(
 
     //TODO: get more descriptive names for these and/or use an arena allocator
 
-    let mut v = Float::new_64(precision);
+    let mut v = Float::with_val_64(precision, Special::Infinity);
 
     let mut p = Float::new_64(precision);
     let mut q = Float::new_64(precision);
@@ -297,111 +1350,651 @@ pub fn tanh_sinh(
     let mut t = Float::new_64(precision);
     let mut eh = Float::new_64(precision);
 
-    let mut u = Float::new_64(precision);
-    let mut r = Float::new_64(precision);
-    let mut w = Float::new_64(precision);
-    let mut x = Float::new_64(precision);
+    let mut u = Float::new_64(precision);
+    let mut r = Float::new_64(precision);
+    let mut w = Float::new_64(precision);
+    let mut x = Float::new_64(precision);
+
+    let mut temporary;
+
+    loop {
+        if is_cancelled(cancel) {
+            break;
+        }
+
+        p.assign(0);
+        fp.assign(0);
+        fm.assign(0);
+
+        h /= 2;
+        eh.assign(&h);
+        eh.exp_mut();
+        t.assign(&eh);
+        if iteration > 0 {
+            eh.square_mut();
+        }
+
+        loop {
+            u.assign(&t);
+            u.recip_mut();
+            u -= &t;
+            u.exp_mut();
+
+            r.assign(&u);
+            r += 1;
+            r.recip_mut();
+            r *= &u;
+            r *= 2;
+
+            w.assign(&u);
+            w += 1;
+            w.recip_mut();
+            w *= &r;
+
+            x.assign(&t);
+            x.recip_mut();
+            x += &t;
+
+            w *= &x;
+
+            x.assign(&half_region_width);
+            x *= &r;
+
+            temporary = a.clone();
+            temporary += &x;
+
+            if &temporary > a {
+                let y = f(temporary);
+                if y.is_finite() {
+                    fp = y;
+                }
+            }
+
+            temporary = b.clone();
+            temporary -= &x;
+
+            if &temporary < b {
+                let y = f(temporary);
+                if y.is_finite() {
+                    fm = y;
+                }
+            }
+
+            q.assign(&fp);
+            q += &fm;
+            q *= &w;
+            p += &q;
+            t *= &eh;
+
+            temporary = p.clone();
+            temporary.abs_mut();
+            temporary *= epsilon;
+
+            q.abs_mut();
+            if q <= temporary {
+                break;
+            }
+        }
+
+        v.assign(&s);
+        v -= &p;
+        s += &p;
+        v.abs_mut();
+
+        temporary = s.clone();
+        temporary.abs_mut();
+        temporary *= &tolerance;
+
+        iteration += 1;
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            iteration,
+            h = %h,
+            sum = %s,
+            difference = %v,
+            "completed level",
+        );
+
+        if v <= temporary || iteration > limit {
+            break;
+        }
+    }
+
+    let mut e = s.clone();
+    e.abs_mut();
+    e += epsilon;
+    e.recip_mut();
+    e *= v;
+
+    (half_region_width * s * h, e)
+}
+
+pub fn romberg(
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    tolerance: Tolerance,
+    (a, b): (&Float, &Float),
+    limit: u64,
+    precision: u64,
+) -> (Float, Float) {
+    romberg_tracked(
+        f,
+        epsilon,
+        tolerance,
+        (a, b),
+        limit,
+        precision,
+        &mut Divergence::default(),
+        None,
+    )
+}
+
+/// [`fn@romberg`], recording the extrapolated estimate after each halving in
+/// `divergence`
+///
+/// If `cancel` is set before a node, the extrapolated estimate and error of
+/// the last halving completed are returned (the trapezoid rule over the
+/// endpoints and an infinite error, if there was none)
+#[allow(clippy::too_many_arguments)]
+fn romberg_tracked(
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    tolerance: Tolerance,
+    (a, b): (&Float, &Float),
+    limit: u64,
+    precision: u64,
+    divergence: &mut Divergence,
+    cancel: Option<&AtomicBool>,
+) -> (Float, Float) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("romberg", a = %a, b = %b).entered();
+
+    let mut step = Float::with_val_64(precision, b - a);
+
+    let mut endpoints = f(Float::with_val_64(precision, a));
+    endpoints += f(Float::with_val_64(precision, b));
+
+    // only the previous and current rows of the richardson extrapolation
+    // table are kept around
+
+    let mut previous_row = vec![endpoints * &step / 2.0];
+    let mut current_row = Vec::with_capacity(previous_row.len() + 1);
+
+    let mut difference = Float::with_val_64(precision, f64::INFINITY);
+    let mut node = Float::new_64(precision);
+    let mut sum = Float::new_64(precision);
+    let mut factor = Float::new_64(precision);
+
+    // halving `iteration` evaluates 2^(iteration - 1) new nodes, which can't
+    // be counted in a u64 past the 64th halving (nor ever be evaluated)
+
+    'halving: for iteration in 1..=limit.min(u64::from(u64::BITS)) {
+        step /= 2.0;
+
+        // the trapezoid rule with half the step size only needs the function
+        // evaluated at the new midpoints
+
+        sum.assign(0);
+
+        for k in 0..(1u64 << (iteration - 1)) {
+            if is_cancelled(cancel) {
+                break 'halving;
+            }
+
+            node.assign(&step);
+            node *= 2 * k + 1;
+            node += a;
+            sum += f(node.clone());
+        }
+
+        sum *= &step;
+
+        current_row.clear();
+        current_row.push(Float::with_val_64(precision, &previous_row[0] / 2.0));
+        current_row[0] += &sum;
+
+        for m in 1..=(iteration as usize) {
+            factor.assign(4);
+            factor.pow_assign(m as u64);
+            factor -= 1;
+
+            let mut extrapolated = Float::with_val_64(precision, &current_row[m - 1]);
+            extrapolated -= &previous_row[m - 1];
+            extrapolated /= &factor;
+            extrapolated += &current_row[m - 1];
+            current_row.push(extrapolated);
+        }
+
+        difference.assign(&current_row[iteration as usize]);
+        difference -= &previous_row[iteration as usize - 1];
+        difference.abs_mut();
+
+        divergence.refine(&current_row[iteration as usize]);
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            iteration,
+            step = %step,
+            estimate = %current_row[iteration as usize],
+            difference = %difference,
+            "completed halving",
+        );
+
+        std::mem::swap(&mut previous_row, &mut current_row);
+
+        if difference < tolerance.scale(epsilon, &previous_row[iteration as usize]) {
+            break;
+        }
+    }
+
+    (
+        previous_row
+            .pop()
+            .expect("the romberg table is never empty"),
+        difference,
+    )
+}
+
+/// A subinterval awaiting evaluation in [`fn@adaptive_simpson`], along with
+/// the function evaluated at its endpoints and midpoint and its Simpson
+/// estimate
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct SimpsonInterval {
+    a: Float,
+    f_a: Float,
+    m: Float,
+    f_m: Float,
+    b: Float,
+    f_b: Float,
+    estimate: Float,
+    tolerance: Float,
+    depth: u64,
+}
+
+impl SimpsonInterval {
+    /// Evaluates the function over the whole of a..b, splitting `epsilon`
+    /// (interpreted according to `tolerance`) across it
+    fn new(
+        f: impl Fn(Float) -> Float,
+        epsilon: &Float,
+        tolerance: Tolerance,
+        (a, b): (&Float, &Float),
+        precision: u64,
+    ) -> Self {
+        let f_a = f(Float::with_val_64(precision, a));
+        let f_b = f(Float::with_val_64(precision, b));
+        let m = simpson_midpoint(a, b, precision);
+        let f_m = f(m.clone());
+
+        let estimate = simpson((a, b), (&f_a, &f_m, &f_b), precision);
+        let tolerance = tolerance.scale(&Float::with_val_64(precision, epsilon), &estimate);
+
+        Self {
+            estimate,
+            a: Float::with_val_64(precision, a),
+            f_a,
+            m,
+            f_m,
+            b: Float::with_val_64(precision, b),
+            f_b,
+            tolerance,
+            depth: 0,
+        }
+    }
+
+    /// Compares the Simpson estimate over the subinterval with the sum of
+    /// those over its halves, either accepting it (adding to `integral` and
+    /// `error`) or pushing its halves onto `stack`
+    ///
+    /// The difference between the sum and the estimate is returned
+    fn bisect(
+        self,
+        f: impl Fn(Float) -> Float,
+        stack: &mut Vec<SimpsonInterval>,
+        (integral, error): (&mut Float, &mut Float),
+        depth_limit: u64,
+        precision: u64,
+    ) -> Float {
+        let left_m = simpson_midpoint(&self.a, &self.m, precision);
+        let f_left_m = f(left_m.clone());
+        let right_m = simpson_midpoint(&self.m, &self.b, precision);
+        let f_right_m = f(right_m.clone());
+
+        let left = simpson(
+            (&self.a, &self.m),
+            (&self.f_a, &f_left_m, &self.f_m),
+            precision,
+        );
+        let right = simpson(
+            (&self.m, &self.b),
+            (&self.f_m, &f_right_m, &self.f_b),
+            precision,
+        );
+
+        // the error of the halves is approximately a fifteenth of the
+        // difference between their sum and the estimate over the whole
+
+        let mut difference = Float::with_val_64(precision, &left + &right);
+        difference -= &self.estimate;
+
+        let mut local_error = Float::with_val_64(precision, difference.abs_ref());
+        local_error /= 15;
+
+        if local_error <= self.tolerance || self.depth >= depth_limit {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                lower = %self.a,
+                upper = %self.b,
+                depth = self.depth,
+                error = %local_error,
+                "accepted subinterval",
+            );
+
+            // richardson extrapolation of the two estimates
+
+            let extrapolation = Float::with_val_64(precision, &difference / 15);
+            *integral += left;
+            *integral += right;
+            *integral += extrapolation;
+            *error += local_error;
+            return difference;
+        }
 
-    let mut temporary;
+        let mut tolerance = self.tolerance;
+        tolerance /= 2.0;
+
+        stack.push(SimpsonInterval {
+            a: self.m.clone(),
+            f_a: self.f_m.clone(),
+            m: right_m,
+            f_m: f_right_m,
+            b: self.b,
+            f_b: self.f_b,
+            estimate: right,
+            tolerance: tolerance.clone(),
+            depth: self.depth + 1,
+        });
+        stack.push(SimpsonInterval {
+            a: self.a,
+            f_a: self.f_a,
+            m: left_m,
+            f_m: f_left_m,
+            b: self.m,
+            f_b: self.f_m,
+            estimate: left,
+            tolerance,
+            depth: self.depth + 1,
+        });
+
+        difference
+    }
+}
 
-    loop {
-        p.assign(0);
-        fp.assign(0);
-        fm.assign(0);
+/// Calculates the midpoint of a..b
+fn simpson_midpoint(a: &Float, b: &Float, precision: u64) -> Float {
+    let mut m = Float::with_val_64(precision, a + b);
+    m /= 2.0;
+    m
+}
 
-        h /= 2;
-        eh.assign(&h);
-        eh.exp_mut();
-        t.assign(&eh);
-        if iteration > 0 {
-            eh.square_mut();
-        }
+/// Calculates the Simpson estimate over a..b given the function evaluated at
+/// the endpoints and the midpoint
+fn simpson(
+    (a, b): (&Float, &Float),
+    (f_a, f_m, f_b): (&Float, &Float, &Float),
+    precision: u64,
+) -> Float {
+    let mut estimate = Float::with_val_64(precision, f_m);
+    estimate *= 4;
+    estimate += f_a;
+    estimate += f_b;
+    estimate *= Float::with_val_64(precision, b - a);
+    estimate /= 6;
+    estimate
+}
 
-        loop {
-            u.assign(&t);
-            u.recip_mut();
-            u -= &t;
-            u.exp_mut();
+pub fn adaptive_simpson(
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    tolerance: Tolerance,
+    (a, b): (&Float, &Float),
+    depth_limit: u64,
+    precision: u64,
+) -> (Float, Float) {
+    adaptive_simpson_tracked(
+        f,
+        epsilon,
+        tolerance,
+        (a, b),
+        depth_limit,
+        precision,
+        &mut Divergence::default(),
+        None,
+    )
+}
 
-            r.assign(&u);
-            r += 1;
-            r.recip_mut();
-            r *= &u;
-            r *= 2;
+/// [`fn@adaptive_simpson`], recording an estimate of the whole integral
+/// after each depth of bisection in `divergence`
+///
+/// The subintervals are refined depth-first, so there is no sequence of
+/// refinements of the whole interval as in [`fn@romberg`]. Instead, the
+/// changes made by bisecting each subinterval (the sum of the estimates over
+/// its halves less the estimate over the whole) are summed by depth, and the
+/// estimate after each depth is the estimate over a..b plus the sums up to
+/// it. Beside a singularity, bisecting at every depth changes the integral
+/// by about as much if it diverges
+///
+/// If `cancel` is set before a subinterval, the accepted subintervals plus
+/// the Simpson estimates of those remaining are returned, with an infinite
+/// error
+#[allow(clippy::too_many_arguments)]
+fn adaptive_simpson_tracked(
+    f: impl Fn(Float) -> Float,
+    epsilon: &Float,
+    tolerance: Tolerance,
+    (a, b): (&Float, &Float),
+    depth_limit: u64,
+    precision: u64,
+    divergence: &mut Divergence,
+    cancel: Option<&AtomicBool>,
+) -> (Float, Float) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("adaptive_simpson", a = %a, b = %b).entered();
+
+    let mut stack = vec![SimpsonInterval::new(
+        &f,
+        epsilon,
+        tolerance,
+        (a, b),
+        precision,
+    )];
+
+    let mut estimate = stack[0].estimate.clone();
+    let mut changes = Vec::<Float>::new();
+
+    let mut integral = Float::with_val_64(precision, Special::Zero);
+    let mut error = Float::with_val_64(precision, Special::Zero);
+
+    while let Some(interval) = stack.pop() {
+        if is_cancelled(cancel) {
+            integral += interval.estimate;
+            for interval in stack {
+                integral += interval.estimate;
+            }
 
-            w.assign(&u);
-            w += 1;
-            w.recip_mut();
-            w *= &r;
+            error.assign(Special::Infinity);
+            break;
+        }
 
-            x.assign(&t);
-            x.recip_mut();
-            x += &t;
+        let depth = interval.depth as usize;
+        let change = interval.bisect(
+            &f,
+            &mut stack,
+            (&mut integral, &mut error),
+            depth_limit,
+            precision,
+        );
 
-            w *= &x;
+        if depth == changes.len() {
+            changes.push(change);
+        } else {
+            changes[depth] += change;
+        }
+    }
 
-            x.assign(&half_region_width);
-            x *= &r;
+    divergence.refine(&estimate);
+    for change in changes {
+        estimate += change;
+        divergence.refine(&estimate);
+    }
 
-            temporary = a.clone();
-            temporary += &x;
+    (integral, error)
+}
 
-            if &temporary > a {
-                let y = f(temporary);
-                if y.is_finite() {
-                    fp = y;
-                }
-            }
+/// An integration with the adaptive Simpson's rule, as done by
+/// [`struct@AdaptiveSimpson`], which may be stopped partway through and
+/// resumed later
+///
+/// The state held is the queue of subintervals remaining to be evaluated
+/// (each with the integrand evaluated at its endpoints and midpoint, and its
+/// Simpson estimate), alongside the sums of the estimates and errors of the
+/// subintervals which were already accepted. It can be serialized with
+/// `serde`, such as to a file on disk every so often during a long
+/// integration, and deserialized to continue from where it left off rather
+/// than starting over. The integrand must be the same one the integration
+/// was started with, in which case the result is identical to that of
+/// [`fn@adaptive_simpson`]
+///
+/// As with [`struct@AdaptiveSimpson`], an upper bound of positive infinity is
+/// accepted (see [`fn@finite_bounds`])
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ResumableSimpson {
+    /// The bounds as they were passed to [`ResumableSimpson::new`]
+    bounds: (Float, Float),
+
+    depth_limit: u64,
+    precision: u64,
 
-            temporary = b.clone();
-            temporary -= &x;
+    /// The subintervals remaining to be evaluated, the last of which is
+    /// evaluated next
+    stack: Vec<SimpsonInterval>,
 
-            if &temporary < b {
-                let y = f(temporary);
-                if y.is_finite() {
-                    fm = y;
-                }
-            }
+    integral: Float,
+    error: Float,
+}
 
-            q.assign(&fp);
-            q += &fm;
-            q *= &w;
-            p += &q;
-            t *= &eh;
+impl ResumableSimpson {
+    /// Begins integrating over a..b, evaluating `f` at the endpoints and
+    /// midpoint
+    ///
+    /// The parameters are the same as those of [`struct@AdaptiveSimpson`]
+    pub fn new(
+        f: impl Fn(Float) -> Float,
+        epsilon: &Float,
+        tolerance: Tolerance,
+        (a, b): (&Float, &Float),
+        depth_limit: u64,
+        precision: u64,
+    ) -> Self {
+        let (f, (mapped_a, mapped_b)) = finite_bounds(f, (a, b), precision);
+
+        Self {
+            bounds: (a.clone(), b.clone()),
+            depth_limit,
+            precision,
+            stack: vec![SimpsonInterval::new(
+                f,
+                epsilon,
+                tolerance,
+                (&mapped_a, &mapped_b),
+                precision,
+            )],
+            integral: Float::with_val_64(precision, Special::Zero),
+            error: Float::with_val_64(precision, Special::Zero),
+        }
+    }
 
-            temporary = p.clone();
-            temporary.abs_mut();
-            temporary *= epsilon;
+    /// Evaluates up to `steps` subintervals from the queue, returning whether
+    /// the integration is finished
+    pub fn advance(&mut self, f: impl Fn(Float) -> Float, steps: u64) -> bool {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+            "resumable_simpson",
+            a = %self.bounds.0,
+            b = %self.bounds.1,
+            remaining = self.stack.len(),
+        )
+        .entered();
 
-            q.abs_mut();
-            if q <= temporary {
+        let (f, _) = finite_bounds(f, (&self.bounds.0, &self.bounds.1), self.precision);
+
+        for _ in 0..steps {
+            let Some(interval) = self.stack.pop() else {
                 break;
-            }
+            };
+
+            interval.bisect(
+                &f,
+                &mut self.stack,
+                (&mut self.integral, &mut self.error),
+                self.depth_limit,
+                self.precision,
+            );
         }
 
-        v.assign(&s);
-        v -= &p;
-        s += &p;
-        v.abs_mut();
+        self.is_finished()
+    }
 
-        temporary = s.clone();
-        temporary.abs_mut();
-        temporary *= &tolerance;
+    /// Whether there are no subintervals remaining to be evaluated
+    pub fn is_finished(&self) -> bool {
+        self.stack.is_empty()
+    }
 
-        iteration += 1;
+    /// The number of subintervals remaining to be evaluated. Evaluating one
+    /// may add two more
+    pub fn remaining(&self) -> usize {
+        self.stack.len()
+    }
 
-        if v <= temporary || iteration > limit {
-            break;
-        }
+    /// The integral and approximate error over the subintervals accepted so
+    /// far, which is the result of the whole integration once it is finished
+    pub fn result(&self) -> (&Float, &Float) {
+        (&self.integral, &self.error)
     }
+}
 
-    let mut e = s.clone();
-    e.abs_mut();
-    e += epsilon;
-    e.recip_mut();
-    e *= v;
+/// Integrates over a..b with the trapezoid rule, taking `a`, each of `nodes`,
+/// and `b` as the endpoints of the subintervals
+///
+/// `nodes` must be sorted in ascending order and lie strictly within a..b.
+/// There is no estimate of the error, so it is reported as NaN
+pub fn trapezoid(
+    f: impl Fn(Float) -> Float,
+    nodes: &[Float],
+    (a, b): (&Float, &Float),
+    precision: u64,
+) -> (Float, Float) {
+    let mut sum = Float::with_val_64(precision, Special::Zero);
+    let mut width = Float::new_64(precision);
 
-    (half_region_width * s * h, e)
+    let mut previous = Float::with_val_64(precision, a);
+    let mut f_previous = f(previous.clone());
+
+    for node in nodes.iter().chain([b]) {
+        let node = Float::with_val_64(precision, node);
+        let f_node = f(node.clone());
+
+        width.assign(&node - &previous);
+        width *= Float::with_val_64(precision, &f_previous + &f_node);
+        width /= 2.0;
+        sum += &width;
+
+        previous = node;
+        f_previous = f_node;
+    }
+
+    (sum, Float::with_val_64(precision, Special::Nan))
 }
 
 #[cfg(test)]
@@ -421,9 +2014,7 @@ mod tests {
         let a = Float::with_val(64, 0);
         let b = Float::with_val(64, 5);
         let (val, _) = gauss_kronrod(
-            |_| {
-                return Float::with_val(64, 3);
-            },
+            |_| Float::with_val(64, 3),
             &G7_K15,
             &EPSILON,
             (&a, &b),
@@ -439,9 +2030,7 @@ mod tests {
         let a = Float::with_val(64, 0);
         let b = Float::with_val(64, 5);
         let (val, _) = gauss_kronrod(
-            |x| {
-                return Float::with_val(64, 3) + x;
-            },
+            |x| Float::with_val(64, 3) + x,
             &G7_K15,
             &EPSILON,
             (&a, &b),
@@ -464,9 +2053,7 @@ mod tests {
         let a = Float::with_val(64, 0);
         let b = Float::with_val(64, 5);
         let (val, _) = gauss_kronrod(
-            |x| {
-                return x.clone() + 0.5 * x.square();
-            },
+            |x| x.clone() + 0.5 * x.square(),
             &G7_K15,
             &EPSILON,
             (&a, &b),
@@ -488,32 +2075,681 @@ mod tests {
     fn integrate_sin_squared() {
         let a = Float::with_val(64, 0);
         let b = 2 * Float::with_val(64, Constant::Pi);
-        let (val, _) = gauss_kronrod(
+        let (val, _) = gauss_kronrod(|x| x.sin().square(), &G7_K15, &EPSILON, (&a, &b), 64, 64);
+
+        assert!(Float::with_val(64, val - Float::with_val(64, Constant::Pi)).abs() < *EPSILON);
+
+        let a = Float::with_val(64, 0);
+        let b = -2 * Float::with_val(64, Constant::Pi);
+        let (val, _) = gauss_kronrod(|x| x.sin().square(), &G7_K15, &EPSILON, (&a, &b), 64, 64);
+
+        assert!(Float::with_val(64, val + Float::with_val(64, Constant::Pi)).abs() < *EPSILON);
+    }
+
+    #[test]
+    fn romberg_exponential() {
+        use std::cell::Cell;
+
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, 1);
+        let exact = Float::with_val(64, 1).exp() - 1;
+
+        let evaluations = Cell::new(0);
+        let (val, error) = romberg(
             |x| {
-                return x.sin().square();
+                evaluations.set(evaluations.get() + 1);
+                x.exp()
             },
-            &G7_K15,
             &EPSILON,
+            Tolerance::Absolute,
             (&a, &b),
-            64,
+            16,
             64,
         );
 
-        assert!(Float::with_val(64, val - Float::with_val(64, Constant::Pi)).abs() < *EPSILON);
+        assert!(Float::with_val(64, &val - &exact).abs() < *EPSILON);
+        assert!(error < *EPSILON);
+        assert!(evaluations.get() <= 65);
+
+        // the composite trapezoid rule with the same number of evaluations is
+        // nowhere near as accurate
+
+        let intervals = evaluations.get() - 1;
+        let step = Float::with_val(64, 1) / intervals;
+        let mut trapezoid = (a.clone().exp() + b.clone().exp()) / 2;
+        for k in 1..intervals {
+            trapezoid += Float::with_val(64, &step * k).exp();
+        }
+        trapezoid *= &step;
+
+        assert!(Float::with_val(64, trapezoid - &exact).abs() > 1e-6);
+    }
+
+    #[test]
+    fn try_integrate_cancellable() {
+        use std::cell::Cell;
 
         let a = Float::with_val(64, 0);
-        let b = -2 * Float::with_val(64, Constant::Pi);
-        let (val, _) = gauss_kronrod(
-            |x| {
-                return x.sin().square();
+        let b = Float::with_val(64, 1);
+        let exact = Float::with_val(64, 1).exp() - 1;
+
+        type Integrate<'a> = dyn Fn(
+                &dyn Fn(Float) -> Float,
+                &AtomicBool,
+            ) -> Result<(Float, Float), QuadratureError<Float>>
+            + 'a;
+
+        // a negative tolerance is never met, so without cancellation these
+        // would refine until 2^20 intervals or 2^30 nodes. the flag is set
+        // partway through the subdivision into eight intervals for
+        // gauss-kronrod and the eighth halving for romberg, after which no
+        // more evaluations are made (besides those of the rest of the
+        // subinterval for gauss-kronrod)
+
+        let integrate = |quadrature: &Integrate<'_>, cancel_after: u64| {
+            let cancel = Arc::new(AtomicBool::new(false));
+            let evaluations = Cell::new(0);
+
+            let result = quadrature(
+                &|x: Float| {
+                    evaluations.set(evaluations.get() + 1);
+                    if evaluations.get() == cancel_after {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+
+                    x.exp()
+                },
+                &cancel,
+            );
+
+            (result, evaluations.get())
+        };
+
+        let epsilon = Float::with_val(64, -1);
+
+        let gauss_kronrod = GaussKronrod {
+            interval_limit: 1 << 20,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let (result, evaluations) = integrate(
+            &|f, cancel| gauss_kronrod.try_integrate_cancellable(f, &epsilon, (&a, &b), cancel),
+            15 * (1 + 2 + 4) + 20,
+        );
+        assert_eq!(evaluations, 15 * (1 + 2 + 4 + 2));
+
+        let Err(QuadratureError::Cancelled { value, .. }) = result else {
+            panic!("The integration was not cancelled");
+        };
+        assert!(Float::with_val(64, &value - &exact).abs() < *EPSILON);
+
+        let romberg = Romberg {
+            iteration_limit: 30,
+            precision: 64,
+            tolerance: Tolerance::Absolute,
+        };
+        let (result, evaluations) = integrate(
+            &|f, cancel| romberg.try_integrate_cancellable(f, &epsilon, (&a, &b), cancel),
+            2 + 127 + 20,
+        );
+        assert_eq!(evaluations, 2 + 127 + 20);
+
+        let Err(QuadratureError::Cancelled { value, .. }) = result else {
+            panic!("The integration was not cancelled");
+        };
+        assert!(Float::with_val(64, &value - &exact).abs() < *EPSILON);
+
+        // adaptive simpson evaluates three points up front and two for each
+        // bisection, and reports the remaining subintervals' estimates
+
+        let adaptive_simpson = AdaptiveSimpson {
+            depth_limit: 48,
+            precision: 64,
+            tolerance: Tolerance::Absolute,
+        };
+        let (result, evaluations) = integrate(
+            &|f, cancel| adaptive_simpson.try_integrate_cancellable(f, &epsilon, (&a, &b), cancel),
+            3 + 2 * 20,
+        );
+        assert_eq!(evaluations, 3 + 2 * 20);
+
+        let Err(QuadratureError::Cancelled { value, error }) = result else {
+            panic!("The integration was not cancelled");
+        };
+        assert!(Float::with_val(64, &value - &exact).abs() < 1e-3);
+        assert!(error.is_infinite());
+
+        // tanh-sinh stops before the next level, so cancelling it during the
+        // first level leaves the rest unevaluated, and the estimate is as
+        // coarse as that level
+
+        let tanh_sinh = TanhSinh {
+            iteration_limit: 10,
+            precision: 64,
+        };
+        let (result, full) = integrate(
+            &|f, cancel| tanh_sinh.try_integrate_cancellable(f, &*EPSILON, (&a, &b), cancel),
+            0,
+        );
+        assert!(result.is_ok());
+
+        let (result, evaluations) = integrate(
+            &|f, cancel| tanh_sinh.try_integrate_cancellable(f, &*EPSILON, (&a, &b), cancel),
+            2,
+        );
+        assert!(evaluations < full);
+
+        let Err(QuadratureError::Cancelled { value, .. }) = result else {
+            panic!("The integration was not cancelled");
+        };
+        assert!(Float::with_val(64, &value - &exact).abs() < 1e-2);
+
+        // the other methods run to completion before being reported as
+        // cancelled, and an integration which was never cancelled completes
+        // as usual
+
+        let (result, _) = integrate(
+            &|f, cancel| {
+                GaussLegendre {
+                    order: 8,
+                    precision: 64,
+                }
+                .try_integrate_cancellable(f, &epsilon, (&a, &b), cancel)
             },
-            &G7_K15,
+            1,
+        );
+        assert!(matches!(result, Err(QuadratureError::Cancelled { .. })));
+
+        let (result, _) = integrate(
+            &|f, cancel| gauss_kronrod.try_integrate_cancellable(f, &*EPSILON, (&a, &b), cancel),
+            0,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn default_epsilon() {
+        let epsilon = super::default_epsilon(64);
+        assert!(epsilon > 5e-17 && epsilon < 2e-16);
+        assert_eq!(epsilon, f64::EPSILON / 2.0);
+
+        // each additional bit of precision halves the tolerance
+
+        assert_eq!(super::default_epsilon(128), epsilon.clone() >> 64);
+        assert_eq!(super::default_epsilon(8), 1);
+    }
+
+    #[test]
+    fn adaptive_simpson_sine() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, Constant::Pi);
+
+        let (val, error) = AdaptiveSimpson {
+            depth_limit: 32,
+            precision: 64,
+            tolerance: Tolerance::Absolute,
+        }
+        .integrate(|x| x.sin(), &*EPSILON, (&a, &b));
+
+        let true_error = Float::with_val(64, &val - 2).abs();
+        assert!(true_error < *EPSILON);
+        assert!(true_error <= error);
+        assert!(error < *EPSILON);
+    }
+
+    #[test]
+    fn relative_tolerance() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, 1);
+
+        // the same integrand scaled by 1e-12 and 1e12, whose integrals are
+        // scale * (e - 1). the absolute error of each is returned alongside
+        // the integral
+
+        let errors = |quadrature: &dyn Fn(&Float) -> (Float, Float)| {
+            [1e-12, 1e12].map(|scale| {
+                let scale = Float::with_val(64, scale);
+                let (value, _) = quadrature(&scale);
+
+                let mut exact = Float::with_val(64, 1).exp();
+                exact -= 1;
+                exact *= &scale;
+
+                let error = Float::with_val(64, &value - &exact).abs();
+                (error, exact)
+            })
+        };
+
+        // an absolute tolerance behaves as the mixed one does for the small
+        // integral, and for the large one asks for far more digits than 64
+        // bits can hold, so it isn't checked here
+
+        for tolerance in [Tolerance::Relative, Tolerance::Mixed] {
+            let romberg = Romberg {
+                iteration_limit: 16,
+                precision: 64,
+                tolerance,
+            };
+            let adaptive_simpson = AdaptiveSimpson {
+                depth_limit: 32,
+                precision: 64,
+                tolerance,
+            };
+
+            let errors = [
+                errors(&|scale| romberg.integrate(|x| x.exp() * scale, &*EPSILON, (&a, &b))),
+                errors(&|scale| {
+                    adaptive_simpson.integrate(|x| x.exp() * scale, &*EPSILON, (&a, &b))
+                }),
+            ];
+
+            for (error, exact) in errors.into_iter().flatten() {
+                // with a relative tolerance, both integrals are found to
+                // about the same number of digits. a mixed one only
+                // guarantees an error within max(1, |I|) * epsilon, which is
+                // absolute for the small integral. both are allowed ten
+                // times epsilon, as the error is only estimated
+
+                let mut bound = Float::with_val(64, exact.abs_ref());
+                if tolerance == Tolerance::Mixed {
+                    bound.max_mut(&Float::with_val(64, 1));
+                }
+                bound *= &*EPSILON;
+                bound *= 10;
+
+                assert!(error <= bound);
+            }
+        }
+    }
+
+    #[test]
+    fn infinite_upper_bound() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, Special::Infinity);
+        let epsilon = Float::with_val(64, 1e-9);
+
+        let integrals = [
+            TanhSinh {
+                iteration_limit: 8,
+                precision: 64,
+            }
+            .integrate(|t| (-t).exp(), &epsilon, (&a, &b)),
+            GaussKronrod {
+                interval_limit: 1024,
+                precision: 64,
+                rule: &G7_K15,
+            }
+            .integrate(|t| (-t).exp(), &epsilon, (&a, &b)),
+            Romberg {
+                iteration_limit: 20,
+                precision: 64,
+                tolerance: Tolerance::Absolute,
+            }
+            .integrate(|t| (-t).exp(), &epsilon, (&a, &b)),
+            AdaptiveSimpson {
+                depth_limit: 48,
+                precision: 64,
+                tolerance: Tolerance::Absolute,
+            }
+            .integrate(|t| (-t).exp(), &epsilon, (&a, &b)),
+        ];
+
+        for (value, _) in integrals {
+            assert!(Float::with_val(64, value - 1).abs() < epsilon);
+        }
+
+        // the lower bound is carried through the substitution
+
+        let (value, _) = Romberg {
+            iteration_limit: 20,
+            precision: 64,
+            tolerance: Tolerance::Absolute,
+        }
+        .integrate(|t| (-t).exp(), &epsilon, (&Float::with_val(64, 2), &b));
+        assert!(Float::with_val(64, value - Float::with_val(64, -2).exp()).abs() < epsilon);
+    }
+
+    #[test]
+    fn try_integrate_not_converged() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, 1);
+
+        // the integrand is singular at 0, which gauss-kronrod quadrature can
+        // only approach by subdividing far more times than allowed here
+
+        let quadrature = GaussKronrod {
+            interval_limit: 8,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let error = quadrature
+            .try_integrate(|x| x.sqrt().recip(), &*EPSILON, (&a, &b))
+            .expect_err("A non-convergent integral was accepted");
+        assert!(matches!(
+            error,
+            QuadratureError::NotConverged { ref error, ref epsilon, .. }
+                if error > epsilon && epsilon == &*EPSILON
+        ));
+
+        // a smooth integrand converges without issue
+
+        let (val, _) = quadrature
+            .try_integrate(|x| x.exp(), &*EPSILON, (&a, &b))
+            .expect("A convergent integral was rejected");
+        assert!(Float::with_val(64, val - (Float::with_val(64, 1).exp() - 1)).abs() < *EPSILON);
+
+        // fixed-order rules have no error estimate, so they are never rejected
+
+        assert!(GaussLegendre {
+            order: 4,
+            precision: 64,
+        }
+        .try_integrate(|x| x.sqrt().recip(), &*EPSILON, (&a, &b))
+        .is_ok());
+    }
+
+    #[test]
+    fn try_integrate_divergent() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, 1);
+
+        // each subdivision adds ln(2) to the integral of 1 / t over 0..1,
+        // which never shrinks as it would if the integral converged
+
+        let quadrature = GaussKronrod {
+            interval_limit: 64,
+            precision: 64,
+            rule: &G7_K15,
+        };
+        let error = quadrature
+            .try_integrate(|t| t.recip(), &*EPSILON, (&a, &b))
+            .expect_err("A divergent integral was accepted");
+        assert!(matches!(
+            error,
+            QuadratureError::Divergent { ref value, .. } if *value > 4
+        ));
+
+        // 1 / sqrt(t) is just as singular at 0, but converges however slowly
+
+        assert!(matches!(
+            quadrature.try_integrate(|t| t.sqrt().recip(), &*EPSILON, (&a, &b)),
+            Err(QuadratureError::NotConverged { .. })
+        ));
+
+        // the same is true of 1 / t over 1..inf, which is mapped onto 0..1,
+        // but not of exp(-t)
+
+        let romberg = Romberg {
+            iteration_limit: 12,
+            precision: 64,
+            tolerance: Tolerance::Absolute,
+        };
+        assert!(matches!(
+            romberg.try_integrate(
+                |t| t.recip(),
+                &*EPSILON,
+                (&b, &Float::with_val(64, Special::Infinity))
+            ),
+            Err(QuadratureError::Divergent { .. })
+        ));
+        assert!(romberg
+            .try_integrate(
+                |t| (-t).exp(),
+                &*EPSILON,
+                (&b, &Float::with_val(64, Special::Infinity))
+            )
+            .is_ok());
+
+        // adaptive simpson refines depth-first, but each new depth beside
+        // the singularity still adds about as much as the last
+
+        let simpson = AdaptiveSimpson {
+            depth_limit: 24,
+            precision: 64,
+            tolerance: Tolerance::Absolute,
+        };
+        assert!(matches!(
+            simpson.try_integrate(
+                |t| t.recip(),
+                &*EPSILON,
+                (&b, &Float::with_val(64, Special::Infinity))
+            ),
+            Err(QuadratureError::Divergent { .. })
+        ));
+        assert!(simpson
+            .try_integrate(
+                |t| (-t).exp(),
+                &*EPSILON,
+                (&b, &Float::with_val(64, Special::Infinity))
+            )
+            .is_ok());
+
+        // t^(-3/2) is mapped onto (1 - u)^(-1/2), which is as singular at
+        // u = 1 as 1 / t is, but the changes shrink by about 1 / sqrt(2) at
+        // each depth
+
+        assert!(matches!(
+            simpson.try_integrate(
+                |t| {
+                    let mut value = t.clone().sqrt();
+                    value *= &t;
+                    value.recip()
+                },
+                &*EPSILON,
+                (&b, &Float::with_val(64, Special::Infinity))
+            ),
+            Err(QuadratureError::NotConverged { .. })
+        ));
+    }
+
+    #[test]
+    fn custom_nodes() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, 1);
+
+        // |x - 0.3| is piecewise linear, so the trapezoid rule is exact with
+        // a node at its kink. nodes outside of the bounds are disregarded
+
+        let kink = Float::with_val(64, 0.3);
+        let f = |x: Float| Float::with_val(64, x - &kink).abs();
+        let exact = Float::with_val(64, 0.29);
+
+        let quadrature = CustomNodeQuadrature {
+            nodes: [-1.0, 0.1, 0.3, 0.65, 1.0, 2.0]
+                .map(|node| Float::with_val(64, node))
+                .to_vec(),
+            precision: 64,
+        };
+        let (val, error) = quadrature.integrate(f, &*EPSILON, (&a, &b));
+        assert!(Float::with_val(64, &val - &exact).abs() < *EPSILON);
+        assert!(error.is_nan());
+
+        let quadrature = CustomNodeQuadrature {
+            nodes: [0.1, 0.65].map(|node| Float::with_val(64, node)).to_vec(),
+            precision: 64,
+        };
+        let (val, _) = quadrature.integrate(f, &*EPSILON, (&a, &b));
+        assert!(Float::with_val(64, &val - &exact).abs() > 1e-3);
+
+        // exp(-t) over 0..inf, with nodes clustered where it decays
+
+        let quadrature = CustomNodeQuadrature {
+            nodes: (1..=400)
+                .map(|i| Float::with_val(64, f64::from(i) * 0.05))
+                .collect(),
+            precision: 64,
+        };
+        let (val, _) = quadrature.integrate(
+            |t| (-t).exp(),
+            &*EPSILON,
+            (&a, &Float::with_val(64, Special::Infinity)),
+        );
+        assert!(Float::with_val(64, val - 1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn resumable_simpson() {
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, Special::Infinity);
+        let f = |t: Float| (-t).exp();
+
+        let start = || ResumableSimpson::new(f, &EPSILON, Tolerance::Absolute, (&a, &b), 32, 64);
+
+        let mut uninterrupted = start();
+        while !uninterrupted.advance(f, u64::MAX) {}
+
+        let (val, error) = adaptive_simpson(
+            finite_bounds(f, (&a, &b), 64).0,
             &EPSILON,
-            (&a, &b),
-            64,
+            Tolerance::Absolute,
+            (&Float::with_val(64, 0), &Float::with_val(64, 1)),
+            32,
             64,
         );
+        assert_eq!(uninterrupted.result(), (&val, &error));
+        assert!(Float::with_val(64, val - 1).abs() < *EPSILON);
 
-        assert!(Float::with_val(64, val + Float::with_val(64, Constant::Pi)).abs() < *EPSILON);
+        // stop partway through, as a killed run would, and pick up again
+        // from a checkpoint on disk
+
+        let mut interrupted = start();
+        assert!(!interrupted.advance(f, 16));
+        assert!(interrupted.remaining() > 0);
+
+        let checkpoint =
+            std::env::temp_dir().join(format!("reedbed-resumable-{}.yml", std::process::id()));
+        std::fs::write(
+            &checkpoint,
+            serde_yml::to_string(&interrupted).expect("Unable to serialize the state"),
+        )
+        .expect("Unable to write the checkpoint");
+
+        let mut resumed: ResumableSimpson = serde_yml::from_str(
+            &std::fs::read_to_string(&checkpoint).expect("Unable to read the checkpoint"),
+        )
+        .expect("Unable to deserialize the state");
+        std::fs::remove_file(&checkpoint).expect("Unable to remove the checkpoint");
+        assert_eq!(resumed, interrupted);
+
+        while !resumed.advance(f, 16) {}
+        assert_eq!(resumed, uninterrupted);
+    }
+
+    #[test]
+    fn gauss_legendre_polynomials() {
+        let a = Float::with_val(128, -1);
+        let b = Float::with_val(128, 2);
+
+        for order in [1, 2, 5, 8] {
+            let quadrature = GaussLegendre {
+                order,
+                precision: 128,
+            };
+
+            // x^k over -1..2 is (2^(k + 1) - (-1)^(k + 1)) / (k + 1)
+
+            for k in 0..=(2 * order) {
+                let (val, _) = quadrature.integrate(
+                    |mut x| {
+                        x.pow_assign(k);
+                        x
+                    },
+                    &*EPSILON,
+                    (&a, &b),
+                );
+
+                let mut exact = Float::with_val(128, 2);
+                exact.pow_assign(k + 1);
+                exact -= if k % 2 == 0 { -1 } else { 1 };
+                exact /= k + 1;
+
+                let mut error = val - &exact;
+                error /= exact;
+                error.abs_mut();
+
+                if k < 2 * order {
+                    assert!(error < 1e-35);
+                } else {
+                    assert!(error > 1e-10);
+                }
+            }
+        }
+
+        assert!(Arc::ptr_eq(
+            &gauss_legendre_rule(5, 128),
+            &gauss_legendre_rule(5, 128)
+        ));
+    }
+
+    #[test]
+    fn rule_cache() {
+        let cache = RuleCache::new();
+        let computations = std::sync::atomic::AtomicU32::new(0);
+        let rule = || {
+            cache.get_or_insert_with(6, 96, || {
+                computations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                compute_gauss_legendre_rule(6, 96)
+            })
+        };
+
+        // the second integration reuses the rule computed for the first
+
+        let a = Float::with_val(96, 0);
+        let b = Float::with_val(96, 1);
+        let first = gauss_legendre_with_rule(|x| x.exp(), &rule(), (&a, &b), 96);
+        let second = gauss_legendre_with_rule(|x| x.exp(), &rule(), (&a, &b), 96);
+
+        assert_eq!(computations.into_inner(), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(first.0, second.0);
+
+        cache.clear();
+        assert!(cache.is_empty());
+
+        // `GaussLegendre` computes its rule into the global cache once, and
+        // reuses it thereafter. the order and precision are used by no other
+        // test, as the cache is shared between them
+
+        let quadrature = GaussLegendre {
+            order: 7,
+            precision: 97,
+        };
+        let a = Float::with_val(97, 0);
+        let b = Float::with_val(97, 1);
+        let first = quadrature.integrate(|x| x.exp(), &a, (&a, &b));
+
+        let cached = GAUSS_LEGENDRE_RULES.get_or_insert_with(7, 97, || {
+            panic!("The rule was not cached by the integration")
+        });
+        assert_eq!(
+            first.0,
+            gauss_legendre_with_rule(|x| x.exp(), &cached, (&a, &b), 97).0
+        );
+        assert_eq!(quadrature.integrate(|x| x.exp(), &a, (&a, &b)).0, first.0);
+        assert!(Arc::ptr_eq(&gauss_legendre_rule(7, 97), &cached));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn trace_subdivisions() {
+        // a negative tolerance is never met, so every subdivision up to the
+        // interval limit of 8 (1, 2, 4, and 8 intervals) is made
+
+        let a = Float::with_val(64, 0);
+        let b = Float::with_val(64, 1);
+        let (_, events) = crate::test_tracing::capture(|| {
+            gauss_kronrod(
+                |x| x.exp(),
+                &G7_K15,
+                &Float::with_val(64, -1),
+                (&a, &b),
+                8,
+                64,
+            )
+        });
+
+        let count = |message: &str| events.iter().filter(|event| *event == message).count();
+        assert_eq!(count("completed subdivision"), 4);
+        assert_eq!(count("evaluated subinterval"), 1 + 2 + 4 + 8);
     }
 }
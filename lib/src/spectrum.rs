@@ -0,0 +1,207 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+use rug::Float;
+use std::{error::Error, fmt};
+
+//TODO: the tables here are coarse. finer (and more) spectra would be nice
+
+/// Absorption spectrum of the interior of a melanosome as a pair of
+/// wavelength (units: nm) and absorption coefficient (units: cm^-1)
+///
+/// Tabulated from the fit `6.6e11 * wavelength^-3.33` given at
+/// <https://omlc.org/spectra/melanin/mua.html>
+pub const MELANIN: [(f64, f64); 16] = [
+    (350.0, 2227.4),
+    (400.0, 1427.9),
+    (450.0, 964.60),
+    (500.0, 679.16),
+    (550.0, 494.47),
+    (600.0, 370.08),
+    (650.0, 283.49),
+    (700.0, 221.50),
+    (750.0, 176.03),
+    (800.0, 141.99),
+    (850.0, 116.03),
+    (900.0, 95.922),
+    (950.0, 80.117),
+    (1000.0, 67.537),
+    (1050.0, 57.409),
+    (1100.0, 49.171),
+];
+
+/// Absorption spectrum of pure water as a pair of wavelength (units: nm) and
+/// absorption coefficient (units: cm^-1)
+///
+/// Values are approximate, taken from <https://omlc.org/spectra/water/>
+pub const WATER: [(f64, f64); 15] = [
+    (400.0, 0.000058),
+    (450.0, 0.000092),
+    (500.0, 0.00026),
+    (550.0, 0.00057),
+    (600.0, 0.0022),
+    (650.0, 0.0034),
+    (700.0, 0.0060),
+    (750.0, 0.026),
+    (800.0, 0.020),
+    (850.0, 0.043),
+    (900.0, 0.068),
+    (950.0, 0.39),
+    (1000.0, 0.36),
+    (1050.0, 0.15),
+    (1100.0, 0.17),
+];
+
+/// Absorption spectrum of oxygenated whole blood (150 g/L of hemoglobin) as a
+/// pair of wavelength (units: nm) and absorption coefficient (units: cm^-1)
+///
+/// Computed from the molar extinction coefficients of oxyhemoglobin compiled
+/// at <https://omlc.org/spectra/hemoglobin/summary.html>
+pub const HEMOGLOBIN: [(f64, f64); 13] = [
+    (400.0, 1426.0),
+    (450.0, 336.4),
+    (500.0, 112.1),
+    (550.0, 230.4),
+    (600.0, 17.14),
+    (650.0, 1.971),
+    (700.0, 1.553),
+    (750.0, 2.774),
+    (800.0, 4.370),
+    (850.0, 5.666),
+    (900.0, 6.416),
+    (950.0, 6.448),
+    (1000.0, 5.474),
+];
+
+/// The materials with tabulated absorption spectra
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Material {
+    Melanin,
+    Water,
+    Hemoglobin,
+}
+
+impl Material {
+    /// The absorption spectrum of the material, sorted by wavelength
+    pub fn spectrum(self) -> &'static [(f64, f64)] {
+        match self {
+            Self::Melanin => &MELANIN,
+            Self::Water => &WATER,
+            Self::Hemoglobin => &HEMOGLOBIN,
+        }
+    }
+}
+
+/// An error encountered while looking up an absorption coefficient
+#[derive(Clone, PartialEq, Debug)]
+pub enum SpectrumError {
+    /// The wavelength lies outside of the material's tabulated spectrum
+    OutOfRange {
+        /// Units: nm
+        wavelength: Float,
+
+        /// The shortest tabulated wavelength. Units: nm
+        min: f64,
+
+        /// The longest tabulated wavelength. Units: nm
+        max: f64,
+    },
+}
+
+impl fmt::Display for SpectrumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange {
+                wavelength,
+                min,
+                max,
+            } => write!(
+                f,
+                "the wavelength {wavelength} nm lies outside of the tabulated range of {min} nm to {max} nm"
+            ),
+        }
+    }
+}
+
+impl Error for SpectrumError {}
+
+/// Looks up the absorption coefficient (units: cm^-1) of a material at the
+/// given wavelength (units: nm), linearly interpolating between tabulated
+/// points
+///
+/// If the wavelength lies outside of the tabulated spectrum,
+/// [`SpectrumError::OutOfRange`] is returned
+pub fn mu_a_at(
+    material: Material,
+    wavelength: &Float,
+    precision: u64,
+) -> Result<Float, SpectrumError> {
+    let spectrum = material.spectrum();
+
+    let (min, _) = spectrum[0];
+    let (max, _) = spectrum[spectrum.len() - 1];
+
+    if !(*wavelength >= min && *wavelength <= max) {
+        return Err(SpectrumError::OutOfRange {
+            wavelength: wavelength.clone(),
+            min,
+            max,
+        });
+    }
+
+    for window in spectrum.windows(2) {
+        let [(wavelength_a, mu_a_a), (wavelength_b, mu_a_b)] = *window else {
+            unreachable!("windows are always of length 2");
+        };
+
+        if *wavelength > wavelength_b {
+            continue;
+        }
+
+        let mut mu_a = Float::with_val_64(precision, wavelength);
+        mu_a -= wavelength_a;
+        mu_a /= wavelength_b - wavelength_a;
+        mu_a *= mu_a_b - mu_a_a;
+        mu_a += mu_a_a;
+
+        return Ok(mu_a);
+    }
+
+    unreachable!("the wavelength was checked to be within the spectrum")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_midpoint() {
+        let wavelength = Float::with_val_64(64, 525.0);
+        let mu_a = mu_a_at(Material::Water, &wavelength, 64).expect("525 nm was out of range");
+
+        let mut result = Float::with_val_64(64, 0.00026 + 0.00057);
+        result /= 2.0;
+        result -= mu_a;
+        result.abs_mut();
+        assert!(result < 1e-12);
+
+        // tabulated points are reproduced exactly
+
+        let wavelength = Float::with_val_64(64, 400.0);
+        assert_eq!(
+            mu_a_at(Material::Hemoglobin, &wavelength, 64).expect("400 nm was out of range"),
+            1426.0
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        for wavelength in [200.0, 2000.0, f64::NAN] {
+            let wavelength = Float::with_val_64(64, wavelength);
+
+            assert!(matches!(
+                mu_a_at(Material::Melanin, &wavelength, 64),
+                Err(SpectrumError::OutOfRange { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! [`trait@Beam`] implementations with closed forms for tests
+
+use rug::Float;
+
+use crate::greens::{Beam, LargeBeam, Layer, ThermalProperties};
+
+/// A gaussian beam with the intensity profile `exp(-2r^2/w^2)`, whose
+/// convolution with the radial heat kernel has the closed form
+/// `w^2 / (w^2 + 8 alpha tp) * exp(-2r^2 / (w^2 + 8 alpha tp))`
+///
+/// This matches the LG00 mode of `LaguerreGaussianBeam` without its
+/// numerical convolution, which is far too slow to repeatedly evaluate
+/// off-axis in tests
+pub struct GaussianBeam {
+    /// Units: cm
+    pub radius: Float,
+}
+
+impl Beam for GaussianBeam {
+    fn evaluate_with<'b>(
+        &self,
+        precision: u64,
+        thermal_properties: &ThermalProperties<'b>,
+        layer: &Layer<'b>,
+        z: &Float,
+        r: &Float,
+        tp: &Float,
+    ) -> Float {
+        let mut spread = Float::with_val_64(precision, thermal_properties.k.as_ref());
+        spread /= thermal_properties.rho.as_ref();
+        spread /= thermal_properties.c.as_ref();
+        spread *= tp;
+        spread *= 8.0;
+        spread += Float::with_val_64(precision, self.radius.square_ref());
+
+        let mut r_factor = Float::with_val_64(precision, r.square_ref());
+        r_factor *= -2.0;
+        r_factor /= &spread;
+        r_factor.exp_mut();
+        r_factor *= Float::with_val_64(precision, self.radius.square_ref());
+        r_factor /= spread;
+
+        LargeBeam.evaluate_with(precision, thermal_properties, layer, z, r, tp) * r_factor
+    }
+}
@@ -0,0 +1,143 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! Newtypes over [`struct@Float`] tagging quantities with their units
+//!
+//! Everything else in this crate takes bare [`struct@Float`]s in a fixed set
+//! of units (centimeters, seconds, watts, grams, joules, and kelvin), so
+//! nothing stops a depth in millimeters from being passed where one in
+//! centimeters is expected. These are entirely optional, but constructing
+//! [`struct@Layer`]s and [`struct@ThermalProperties`] through
+//! [`Layer::from_units`] and [`ThermalProperties::from_units`] lets the type
+//! system catch such mistakes. Quantities in other units convert into those
+//! used internally with [`trait@From`]
+//!
+//! [`struct@Layer`]: crate::greens::Layer
+//! [`struct@ThermalProperties`]: crate::greens::ThermalProperties
+//! [`Layer::from_units`]: crate::greens::Layer::from_units
+//! [`ThermalProperties::from_units`]: crate::greens::ThermalProperties::from_units
+
+use rug::Float;
+
+macro_rules! unit {
+    ($(#[$attribute:meta])* $name:ident) => {
+        $(#[$attribute])*
+        #[derive(Clone, PartialEq, PartialOrd, Debug)]
+        pub struct $name(pub Float);
+
+        impl $name {
+            /// Returns the contained [`struct@Float`]
+            pub fn into_inner(self) -> Float {
+                self.0
+            }
+        }
+    };
+}
+
+/// Converts a quantity in `$from` into `$to` by scaling it by `$factor`
+macro_rules! conversion {
+    ($from:ident => $to:ident, $operation:tt $factor:expr) => {
+        impl From<$from> for $to {
+            fn from(value: $from) -> Self {
+                let mut value = value.0;
+                value $operation $factor;
+                Self(value)
+            }
+        }
+    };
+}
+
+unit!(
+    /// A length. Units: cm
+    Centimeters
+);
+
+unit!(
+    /// A length. Units: mm
+    Millimeters
+);
+
+unit!(
+    /// A length. Units: μm
+    Micrometers
+);
+
+unit!(
+    /// An absorption coefficient. Units: cm^-1
+    PerCentimeter
+);
+
+unit!(
+    /// An absorption coefficient. Units: mm^-1
+    PerMillimeter
+);
+
+unit!(
+    /// An irradiance. Units: W*cm^-2
+    WattsPerCm2
+);
+
+unit!(
+    /// An irradiance. Units: W*mm^-2
+    WattsPerMm2
+);
+
+unit!(
+    /// A density. Units: g*cm^-3
+    GramsPerCm3
+);
+
+unit!(
+    /// A specific heat capacity. Units: J*g^-1*K^-1
+    JoulesPerGramKelvin
+);
+
+unit!(
+    /// A thermal conductivity. Units: W*cm^-1*K^-1
+    WattsPerCmKelvin
+);
+
+conversion!(Millimeters => Centimeters, /= 10);
+conversion!(Micrometers => Centimeters, /= 10_000);
+conversion!(PerMillimeter => PerCentimeter, *= 10);
+conversion!(WattsPerMm2 => WattsPerCm2, *= 100);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::greens::{Layer, ThermalProperties};
+
+    #[test]
+    fn millimeters_to_centimeters() {
+        let parse = |value: &str| Float::with_val_64(64, Float::parse(value).unwrap());
+        let close = |value: &Float, expected: &str| {
+            let expected = parse(expected);
+            let mut error = Float::with_val_64(64, value - &expected);
+            error /= &expected;
+            error.abs_mut();
+            error < 1e-18
+        };
+
+        let layer = Layer::from_units(
+            Millimeters(parse("0.1")),
+            Micrometers(parse("10")),
+            PerMillimeter(parse("100")),
+            WattsPerMm2(parse("0.01")),
+        );
+
+        assert!(close(&layer.d, "0.01"));
+        assert!(close(&layer.z0, "0.001"));
+        assert!(close(&layer.mu_a, "1000"));
+        assert!(close(&layer.e0, "1"));
+        assert_eq!(layer.d.prec_64(), 64);
+
+        assert_eq!(
+            ThermalProperties::from_units(
+                GramsPerCm3(Float::with_val_64(64, 1)),
+                JoulesPerGramKelvin(Float::with_val_64(64, 4.178)),
+                WattsPerCmKelvin(Float::with_val_64(64, 0.0063)),
+            ),
+            ThermalProperties::from_f64(64, 1.0, 4.178, 0.0063)
+        );
+    }
+}
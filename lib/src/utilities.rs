@@ -3,11 +3,16 @@
 use rug::{
     float::Special,
     ops::{CompleteRound, Pow},
-    Float,
+    Assign, Float,
 };
+use std::{cmp::Ordering, mem};
 
 use crate::{bessel, quadrature};
 
+/// The upper limit on the number of times the bracket is expanded while
+/// searching for a minimum
+const BRACKET_LIMIT: u32 = 64;
+
 pub fn marcum_q(v: i32, a: &Float, b: &Float, precision: u64) -> Float {
     //TODO: use the variant of double-exponential quadrature supporting
     //      improper integration for this and see how it compares
@@ -51,3 +56,167 @@ pub fn marcum_q(v: i32, a: &Float, b: &Float, precision: u64) -> Float {
 
     1 - argument * integrated
 }
+
+/// Evaluates the generalized Laguerre polynomial `L_n^(alpha)(x)` using its
+/// three-term recurrence
+pub fn laguerre(n: u32, alpha: u32, x: &Float, precision: u64) -> Float {
+    let mut previous = Float::with_val_64(precision, 1);
+
+    if n == 0 {
+        return previous;
+    }
+
+    let mut current = Float::with_val_64(precision, 1 + alpha);
+    current -= x;
+
+    for k in 1..n {
+        let mut next = Float::with_val_64(precision, 2 * k + 1 + alpha);
+        next -= x;
+        next *= &current;
+        previous *= k + alpha;
+        next -= &previous;
+        next /= k + 1;
+
+        previous = current;
+        current = next;
+    }
+
+    current
+}
+
+/// Sums `values` using Neumaier's variant of Kahan summation, which carries
+/// the low bits lost by each addition in a separate compensation term
+pub fn neumaier_sum(values: impl IntoIterator<Item = Float>, precision: u64) -> Float {
+    let mut sum = Float::with_val_64(precision, Special::Zero);
+    let mut compensation = Float::with_val_64(precision, Special::Zero);
+    let mut lost = Float::new_64(precision);
+
+    for value in values {
+        let mut total = Float::with_val_64(precision, &sum + &value);
+
+        // whichever of the two operands is larger in magnitude is exact in
+        // the total, so the bits lost are recovered from the smaller one
+
+        if sum.cmp_abs(&value) != Some(Ordering::Less) {
+            lost.assign(&sum - &total);
+            lost += &value;
+        } else {
+            lost.assign(&value - &total);
+            lost += &sum;
+        }
+        compensation += &lost;
+
+        mem::swap(&mut sum, &mut total);
+    }
+
+    sum += compensation;
+    sum
+}
+
+/// Minimizes `f` by first expanding a bracket around `initial` (which must
+/// be positive) by factors of 2 until it contains a minimum, then narrowing
+/// it with a golden-section search until its width relative to the estimate
+/// is within `epsilon`
+///
+/// Each step of the search narrows the bracket by a factor of about 0.618,
+/// so after `2 * precision` of them it can't be narrowed any further and the
+/// search stops there even if `epsilon` is too small to ever be reached. It
+/// also stops as soon as the bracket no longer narrows or is no longer
+/// finite (e.g. for an infinite `initial`). Either way, the center of the
+/// bracket at that point is returned as the best estimate
+pub fn minimize(
+    precision: u64,
+    initial: &Float,
+    epsilon: &Float,
+    f: impl Fn(&Float) -> Float,
+) -> Float {
+    // expand a bracket (a, b, c) such that the value of f at b is no greater
+    // than that at either end
+
+    let mut b = Float::with_val_64(precision, initial);
+    let mut a = Float::with_val_64(precision, &b / 2.0);
+    let mut c = Float::with_val_64(precision, &b * 2.0);
+
+    let mut residual_a = f(&a);
+    let mut residual_b = f(&b);
+    let mut residual_c = f(&c);
+
+    for _ in 0..BRACKET_LIMIT {
+        if residual_a < residual_b {
+            c = b;
+            residual_c = residual_b;
+            b = a.clone();
+            residual_b = residual_a;
+            a /= 2.0;
+            residual_a = f(&a);
+        } else if residual_c < residual_b {
+            a = b;
+            residual_a = residual_b;
+            b = c.clone();
+            residual_b = residual_c;
+            c *= 2.0;
+            residual_c = f(&c);
+        } else {
+            break;
+        }
+    }
+
+    // golden-section search over a..c
+
+    let mut inverse_phi = Float::with_val_64(precision, 5);
+    inverse_phi.sqrt_mut();
+    inverse_phi -= 1;
+    inverse_phi /= 2.0;
+
+    let mut width = Float::with_val_64(precision, &c - &a);
+    let mut tolerance = Float::new_64(precision);
+
+    let mut x_1 = Float::with_val_64(precision, &width * &inverse_phi);
+    x_1 -= &c;
+    x_1 *= -1;
+    let mut x_2 = Float::with_val_64(precision, &width * &inverse_phi);
+    x_2 += &a;
+
+    let mut residual_1 = f(&x_1);
+    let mut residual_2 = f(&x_2);
+    let mut previous_width = Float::with_val_64(precision, Special::Infinity);
+
+    for _ in 0..precision.saturating_mul(2) {
+        width.assign(&c - &a);
+
+        tolerance.assign(&a + &c);
+        tolerance /= 2.0;
+        tolerance *= epsilon;
+
+        if width <= tolerance || !width.is_finite() || width >= previous_width {
+            break;
+        }
+
+        previous_width.assign(&width);
+
+        if residual_1 < residual_2 {
+            c.assign(&x_2);
+            x_2.assign(&x_1);
+            residual_2 = residual_1;
+
+            x_1.assign(&c - &a);
+            x_1 *= &inverse_phi;
+            x_1 -= &c;
+            x_1 *= -1;
+            residual_1 = f(&x_1);
+        } else {
+            a.assign(&x_1);
+            x_1.assign(&x_2);
+            residual_1 = residual_2;
+
+            x_2.assign(&c - &a);
+            x_2 *= &inverse_phi;
+            x_2 += &a;
+            residual_2 = f(&x_2);
+        }
+    }
+
+    a += c;
+    a /= 2.0;
+    a
+}
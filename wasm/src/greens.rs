@@ -0,0 +1,230 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! Green's function based computations over `f64`s
+//!
+//! These mirror their counterparts in `reedbed_lib::greens`, but are limited
+//! to the precision of an `f64`. In particular, `exp(mu_a^2 * alpha * tp)`
+//! overflows once `mu_a^2 * alpha * tp` exceeds roughly 709, past which the
+//! results are NaN
+
+use crate::quadrature::adaptive_simpson;
+
+/// The upper limit on the number of times a subinterval of time is bisected
+const DEPTH_LIMIT: u32 = 48;
+
+/// Thermal properties of the medium
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ThermalProperties {
+    /// Units: g*cm^3
+    pub rho: f64,
+
+    /// Units: J*g^-1*K^-1
+    pub c: f64,
+
+    /// Units: W*cm^-1*K^-1
+    pub k: f64,
+}
+
+impl ThermalProperties {
+    /// The thermal diffusivity, `k / (rho * c)`. Units: cm^2*s^-1
+    pub fn alpha(&self) -> f64 {
+        self.k / self.rho / self.c
+    }
+}
+
+/// A single absorbing layer
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Layer {
+    /// The thickness of the layer. Units: cm
+    pub d: f64,
+
+    /// The depth of the top of the layer. Units: cm
+    pub z0: f64,
+
+    /// The absorption coefficient of the layer. Units: cm^-1
+    pub mu_a: f64,
+
+    /// The irradiance at the top of the layer. Units: W*cm^-2
+    pub e0: f64,
+}
+
+/// A beam with a flat-top profile, evaluated on its axis
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FlatTopBeam {
+    /// Units: cm
+    pub radius: f64,
+}
+
+impl FlatTopBeam {
+    /// Run the beam over a given [`struct@Layer`] with the provided
+    /// [`struct@ThermalProperties`] at the depth `z` on its axis
+    pub fn evaluate_on_axis(
+        &self,
+        thermal_properties: &ThermalProperties,
+        layer: &Layer,
+        z: f64,
+        tp: f64,
+    ) -> f64 {
+        let alpha = thermal_properties.alpha();
+
+        let coefficient = layer.mu_a * layer.e0
+            / (2.0 * thermal_properties.rho * thermal_properties.c)
+            * (-layer.mu_a * (z - layer.z0)).exp();
+        let bottom = layer.z0 + layer.d - z;
+        let top = layer.z0 - z;
+
+        // at a time of 0, heat has not yet diffused anywhere, and the whole
+        // of the axis is within the beam
+
+        if tp == 0.0 {
+            return coefficient * (sign(bottom) - sign(top));
+        }
+
+        let term_3 = (layer.mu_a * layer.mu_a * alpha * tp).exp();
+
+        let reciprocal_sqrt = (4.0 * alpha * tp).sqrt().recip();
+        let sqrt_mu_a = (alpha * tp).sqrt() * layer.mu_a;
+        let term_4 = erf_difference(
+            bottom * reciprocal_sqrt + sqrt_mu_a,
+            top * reciprocal_sqrt + sqrt_mu_a,
+        );
+
+        let r_factor = 1.0 - (-self.radius * self.radius / 4.0 / alpha / tp).exp();
+
+        coefficient * term_3 * term_4 * r_factor
+    }
+}
+
+/// The sign of `x`, where the sign of zero (and NaN) is 0
+fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+/// Calculates `erf(a) - erf(b)`, where `a >= b`, using the complementary
+/// error function when both arguments are far from zero
+fn erf_difference(a: f64, b: f64) -> f64 {
+    if b.is_sign_positive() {
+        libm::erfc(b) - libm::erfc(a)
+    } else if a.is_sign_negative() {
+        libm::erfc(-a) - libm::erfc(-b)
+    } else {
+        libm::erf(a) - libm::erf(b)
+    }
+}
+
+/// Calculates the temperature rise on the axis of `beam` at the depth `z` at
+/// each of the provided times, with integration beginning at 0
+///
+/// As in `reedbed_lib::greens::temperature_rise_series`, only the interval
+/// between consecutive times is integrated and the results are accumulated,
+/// so `times` must be sorted in ascending order and must not be negative.
+/// `epsilon` is the absolute tolerance of each of these integrations
+pub fn temperature_rise_series(
+    beam: &FlatTopBeam,
+    thermal_properties: &ThermalProperties,
+    layer: &Layer,
+    z: f64,
+    epsilon: f64,
+    times: &[f64],
+) -> Vec<f64> {
+    let mut start = 0.0;
+    let mut temperature_rise = 0.0;
+
+    times
+        .iter()
+        .map(|&time| {
+            temperature_rise += adaptive_simpson(
+                |tp| beam.evaluate_on_axis(thermal_properties, layer, z, tp),
+                epsilon,
+                (start, time),
+                DEPTH_LIMIT,
+            )
+            .0;
+            start = time;
+
+            temperature_rise
+        })
+        .collect()
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    use reedbed_lib::greens::{self as reference, Beam};
+    use rug::Float;
+
+    const THERMAL_PROPERTIES: ThermalProperties = ThermalProperties {
+        rho: 1.0,
+        c: 4.178,
+        k: 0.0063,
+    };
+
+    const LAYER: Layer = Layer {
+        d: 0.001,
+        z0: 0.0,
+        mu_a: 1000.0,
+        e0: 1.0,
+    };
+
+    #[test]
+    fn evaluate_matches_reference() {
+        let thermal_properties = reference::ThermalProperties::from_f64(
+            64,
+            THERMAL_PROPERTIES.rho,
+            THERMAL_PROPERTIES.c,
+            THERMAL_PROPERTIES.k,
+        );
+        let layer = reference::Layer::from_f64(64, LAYER.d, LAYER.z0, LAYER.mu_a, LAYER.e0);
+        let flat_top =
+            reference::FlatTopBeam::new(&Float::with_val_64(64, 0.01)).expect("Invalid beam");
+        let beam = FlatTopBeam { radius: 0.01 };
+        let zero = Float::with_val_64(64, 0);
+
+        for z in [0.0, 0.0005, 0.002] {
+            for tp in [0.0, 1e-6, 1e-3, 1e-1] {
+                let expected = flat_top
+                    .evaluate_with(
+                        64,
+                        &thermal_properties,
+                        &layer,
+                        &Float::with_val_64(64, z),
+                        &zero,
+                        &Float::with_val_64(64, tp),
+                    )
+                    .to_f64();
+                let value = beam.evaluate_on_axis(&THERMAL_PROPERTIES, &LAYER, z, tp);
+
+                assert!((value - expected).abs() <= 1e-12 * expected.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn temperature_rise_series_converges() {
+        let beam = FlatTopBeam { radius: 0.01 };
+        let series = temperature_rise_series(
+            &beam,
+            &THERMAL_PROPERTIES,
+            &LAYER,
+            0.0005,
+            1e-10,
+            &[0.025, 0.05, 0.1],
+        );
+
+        // reference results computed with 120 significant digits
+        for (value, expected) in
+            series
+                .into_iter()
+                .zip([0.28109946216777776, 0.33457234970502337, 0.3769396916164825])
+        {
+            assert!((value - expected).abs() <= 1e-12 * expected);
+        }
+    }
+}
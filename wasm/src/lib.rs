@@ -0,0 +1,83 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+#![allow(clippy::cognitive_complexity)]
+#![warn(clippy::cargo_common_metadata)]
+#![warn(clippy::dbg_macro)]
+#![warn(clippy::explicit_deref_methods)]
+#![warn(clippy::filetype_is_file)]
+#![warn(clippy::imprecise_flops)]
+#![warn(clippy::large_stack_arrays)]
+#![warn(clippy::todo)]
+#![warn(clippy::unimplemented)]
+#![deny(clippy::await_holding_lock)]
+#![deny(clippy::cast_lossless)]
+#![deny(clippy::clone_on_ref_ptr)]
+#![deny(clippy::doc_markdown)]
+#![deny(clippy::empty_enums)]
+#![deny(clippy::enum_glob_use)]
+#![deny(clippy::exit)]
+#![deny(clippy::explicit_into_iter_loop)]
+#![deny(clippy::explicit_iter_loop)]
+#![deny(clippy::fallible_impl_from)]
+#![deny(clippy::inefficient_to_string)]
+#![deny(clippy::large_digit_groups)]
+#![deny(clippy::wildcard_dependencies)]
+#![deny(clippy::wildcard_imports)]
+#![deny(clippy::unused_self)]
+#![deny(clippy::single_match_else)]
+#![deny(clippy::option_option)]
+#![deny(clippy::mut_mut)]
+
+//! A double-precision subset of `reedbed_lib` which can be built for
+//! WebAssembly
+//!
+//! `reedbed_lib` is built upon `rug`, and the GMP and MPFR libraries beneath
+//! it don't readily target WebAssembly, so this reimplements the temperature
+//! rise on the axis of a single [`struct@FlatTopBeam`] over a single
+//! [`struct@Layer`] with `f64`s instead. See [`mod@greens`] for the
+//! limitations this brings
+//!
+//! If the `wasm-bindgen` feature is enabled, [`fn@temperature_rise`] is
+//! exported to JavaScript as `temperatureRise`, taking and returning
+//! `Float64Array`s. The headless tests under `tests/` are run with
+//! `wasm-pack test --headless --firefox --features wasm-bindgen`
+
+pub mod greens;
+pub mod quadrature;
+
+#[cfg(feature = "wasm-bindgen")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+pub use crate::greens::{FlatTopBeam, Layer, ThermalProperties};
+
+/// Calculates the temperature rise on the axis of a flat-top beam of the
+/// given `radius` at the depth `z` at each of `times`, with the exposure
+/// beginning at a time of 0
+///
+/// The thermal properties and layer are given as in `reedbed_lib`, with
+/// `epsilon` being the absolute tolerance of each integration over time.
+/// `times` must be sorted in ascending order and must not be negative
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen(js_name = temperatureRise))]
+#[allow(clippy::too_many_arguments)]
+pub fn temperature_rise(
+    rho: f64,
+    c: f64,
+    k: f64,
+    d: f64,
+    z0: f64,
+    mu_a: f64,
+    e0: f64,
+    radius: f64,
+    z: f64,
+    epsilon: f64,
+    times: &[f64],
+) -> Vec<f64> {
+    greens::temperature_rise_series(
+        &FlatTopBeam { radius },
+        &ThermalProperties { rho, c, k },
+        &Layer { d, z0, mu_a, e0 },
+        z,
+        epsilon,
+        times,
+    )
+}
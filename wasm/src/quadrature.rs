@@ -0,0 +1,107 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! Adaptive Simpson quadrature over `f64`s
+//!
+//! This mirrors `reedbed_lib::quadrature::adaptive_simpson`
+
+/// A subinterval awaiting evaluation, alongside the values of the integrand
+/// at its ends and midpoint and its Simpson estimate
+struct SimpsonInterval {
+    a: f64,
+    b: f64,
+    f_a: f64,
+    f_midpoint: f64,
+    f_b: f64,
+    estimate: f64,
+    epsilon: f64,
+    depth: u32,
+}
+
+/// Calculates the Simpson estimate over a..b given the values of the
+/// integrand at its ends and midpoint
+fn simpson(a: f64, b: f64, f_a: f64, f_midpoint: f64, f_b: f64) -> f64 {
+    (b - a) / 6.0 * (f_a + 4.0 * f_midpoint + f_b)
+}
+
+/// Integrates `f` over a..b to within the absolute tolerance `epsilon`,
+/// returning the result alongside the approximate error
+///
+/// Each subinterval is bisected until the Simpson estimates over it and over
+/// its two halves differ by less than its share of `epsilon`, or until it has
+/// been bisected `depth_limit` times
+pub fn adaptive_simpson(
+    f: impl Fn(f64) -> f64,
+    epsilon: f64,
+    (a, b): (f64, f64),
+    depth_limit: u32,
+) -> (f64, f64) {
+    let f_a = f(a);
+    let f_midpoint = f((a + b) / 2.0);
+    let f_b = f(b);
+
+    let mut stack = vec![SimpsonInterval {
+        a,
+        b,
+        f_a,
+        f_midpoint,
+        f_b,
+        estimate: simpson(a, b, f_a, f_midpoint, f_b),
+        epsilon,
+        depth: 0,
+    }];
+    let mut integral = 0.0;
+    let mut error = 0.0;
+
+    while let Some(interval) = stack.pop() {
+        let midpoint = (interval.a + interval.b) / 2.0;
+        let f_left = f((interval.a + midpoint) / 2.0);
+        let f_right = f((midpoint + interval.b) / 2.0);
+
+        let left = simpson(
+            interval.a,
+            midpoint,
+            interval.f_a,
+            f_left,
+            interval.f_midpoint,
+        );
+        let right = simpson(
+            midpoint,
+            interval.b,
+            interval.f_midpoint,
+            f_right,
+            interval.f_b,
+        );
+        let difference = left + right - interval.estimate;
+
+        if difference.abs() <= 15.0 * interval.epsilon || interval.depth >= depth_limit {
+            // richardson extrapolation removes the leading error term
+
+            integral += left + right + difference / 15.0;
+            error += difference.abs() / 15.0;
+            continue;
+        }
+
+        stack.push(SimpsonInterval {
+            a: interval.a,
+            b: midpoint,
+            f_a: interval.f_a,
+            f_midpoint: f_left,
+            f_b: interval.f_midpoint,
+            estimate: left,
+            epsilon: interval.epsilon / 2.0,
+            depth: interval.depth + 1,
+        });
+        stack.push(SimpsonInterval {
+            a: midpoint,
+            b: interval.b,
+            f_a: interval.f_midpoint,
+            f_midpoint: f_right,
+            f_b: interval.f_b,
+            estimate: right,
+            epsilon: interval.epsilon / 2.0,
+            depth: interval.depth + 1,
+        });
+    }
+
+    (integral, error)
+}
@@ -0,0 +1,36 @@
+// SPDX-LICENSE-IDENTIFIER: GPL-3.0-or-later
+
+//! Headless browser tests of the exported API, run with
+//! `wasm-pack test --headless --firefox --features wasm-bindgen`
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn temperature_rise() {
+    let series = reedbed_wasm::temperature_rise(
+        1.0,
+        4.178,
+        0.0063,
+        0.001,
+        0.0,
+        1000.0,
+        1.0,
+        0.01,
+        0.0005,
+        1e-10,
+        &[0.025, 0.05, 0.1],
+    );
+
+    // reference results computed with 120 significant digits
+    for (value, expected) in
+        series
+            .into_iter()
+            .zip([0.28109946216777776, 0.33457234970502337, 0.3769396916164825])
+    {
+        assert!((value - expected).abs() <= 1e-12 * expected);
+    }
+}